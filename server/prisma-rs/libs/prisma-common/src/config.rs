@@ -40,6 +40,24 @@ impl PrismaDatabase {
     }
 }
 
+impl ConnectionLimit for PrismaDatabase {
+    fn connection_limit(&self) -> Option<u32> {
+        match self {
+            PrismaDatabase::Explicit(config) => config.connection_limit(),
+            PrismaDatabase::ConnectionString(config) => config.connection_limit(),
+            PrismaDatabase::File(config) => config.connection_limit(),
+        }
+    }
+
+    fn pooled(&self) -> Option<bool> {
+        match self {
+            PrismaDatabase::Explicit(config) => config.pooled(),
+            PrismaDatabase::ConnectionString(config) => config.pooled(),
+            PrismaDatabase::File(config) => config.pooled(),
+        }
+    }
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct PrismaConfig {
@@ -0,0 +1,244 @@
+use crate::steps::*;
+use prisma_models::prelude::*;
+
+/// The statements managing the FTS5 table of a model, which indexes its string fields for
+/// full-text search. Triggers on the model table keep the index in sync with every write.
+///
+/// The index has to be recreated when string fields of the model change, which drops it and
+/// indexes the existing records again. The SQLite connector does the same for outdated indexes
+/// when the server starts.
+pub struct FullTextSearchMigration;
+
+impl FullTextSearchMigration {
+    /// Creates the index of a model with string fields and fills it with the existing records.
+    pub fn create(model: &Model) -> Vec<String> {
+        model.full_text_search_table().create_statements()
+    }
+
+    /// Drops the index of a model, if it exists.
+    pub fn drop(model: &Model) -> Vec<String> {
+        model.full_text_search_table().drop_statements()
+    }
+
+    /// Indexes the current string fields of a model again.
+    pub fn recreate(model: &Model) -> Vec<String> {
+        let mut statements = Self::drop(model);
+        statements.append(&mut Self::create(model));
+        statements
+    }
+
+    /// The statements keeping the indexes in sync with the migration steps, to run after the
+    /// steps. Created models are indexed, and models are indexed again when they are renamed or
+    /// their string fields change. Deleted fields and changed field types may have been strings
+    /// before, so they always lead to a new index. The index of a renamed model is dropped under
+    /// its name in the `previous` schema.
+    pub fn for_steps(steps: &[MigrationStep], previous: &Schema, next: &Schema) -> Vec<String> {
+        let is_string = |model: &str, field: &str| match next.find_model(model) {
+            Ok(model) => model
+                .fields()
+                .find_from_scalar(field)
+                .map(|field| field.type_identifier == TypeIdentifier::String)
+                .unwrap_or(false),
+            Err(_) => false,
+        };
+
+        // Models in the order of their first step, and whether they are created by the steps.
+        let mut models: Vec<(&str, bool)> = Vec::new();
+        let mut statements: Vec<String> = Vec::new();
+
+        for step in steps {
+            if let MigrationStep::UpdateModel(step) = step {
+                if step.new_name.is_some() || step.db_name.is_some() {
+                    if let Ok(model) = previous.find_model(&step.name) {
+                        statements.append(&mut Self::drop(&model));
+                    }
+                }
+            }
+
+            let (model, created) = match step {
+                MigrationStep::CreateModel(step) => (step.name.as_str(), true),
+                MigrationStep::UpdateModel(step) if step.new_name.is_some() || step.db_name.is_some() => {
+                    (step.new_name.as_ref().unwrap_or(&step.name).as_str(), false)
+                }
+                MigrationStep::CreateField(step) if is_string(&step.model, &step.name) => (step.model.as_str(), false),
+                MigrationStep::UpdateField(step)
+                    if step.tpe.is_some() || is_string(&step.model, step.new_name.as_ref().unwrap_or(&step.name)) =>
+                {
+                    (step.model.as_str(), false)
+                }
+                MigrationStep::DeleteField(step) => (step.model.as_str(), false),
+                _ => continue,
+            };
+
+            match models.iter_mut().find(|(name, _)| *name == model) {
+                Some((_, existing)) => *existing = *existing || created,
+                None => models.push((model, created)),
+            }
+        }
+
+        let indexes = models
+            .into_iter()
+            .filter_map(|(name, created)| next.find_model(name).ok().map(|model| (model, created)))
+            .flat_map(|(model, created)| match created {
+                true => Self::create(&model),
+                false => Self::recreate(&model),
+            });
+
+        statements.extend(indexes);
+        statements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn created_models_are_indexed() {
+        let previous = schema(vec![]);
+        let next = schema(vec![model("Blog", &[("title", "String"), ("views", "Int")])]);
+        let steps = steps(json!([
+            {"stepType":"CreateModel","name":"Blog"},
+            {"stepType":"CreateField","model":"Blog","name":"title","type":"String"},
+            {"stepType":"CreateField","model":"Blog","name":"views","type":"Int"}
+        ]));
+
+        let statements = FullTextSearchMigration::for_steps(&steps, &previous, &next);
+
+        assert_eq!(create(&next, "Blog"), statements);
+        assert!(statements[0]
+            .starts_with(r#"CREATE VIRTUAL TABLE "test"."Blog_fts" USING fts5("nodeId" UNINDEXED, "title")"#));
+    }
+
+    #[test]
+    fn models_without_string_fields_are_not_indexed() {
+        let previous = schema(vec![]);
+        let next = schema(vec![model("Counter", &[("count", "Int")])]);
+        let steps = steps(json!([
+            {"stepType":"CreateModel","name":"Counter"},
+            {"stepType":"CreateField","model":"Counter","name":"count","type":"Int"}
+        ]));
+
+        assert!(FullTextSearchMigration::for_steps(&steps, &previous, &next).is_empty());
+    }
+
+    #[test]
+    fn created_string_fields_index_the_model_again() {
+        let previous = schema(vec![model("Blog", &[("views", "Int")])]);
+        let next = schema(vec![model("Blog", &[("views", "Int"), ("title", "String")])]);
+        let steps = steps(json!([
+            {"stepType":"CreateField","model":"Blog","name":"title","type":"String"}
+        ]));
+
+        assert_eq!(
+            recreate(&next, "Blog"),
+            FullTextSearchMigration::for_steps(&steps, &previous, &next)
+        );
+    }
+
+    #[test]
+    fn created_fields_of_other_types_keep_the_index() {
+        let previous = schema(vec![model("Blog", &[("title", "String")])]);
+        let next = schema(vec![model("Blog", &[("title", "String"), ("views", "Int")])]);
+        let steps = steps(json!([
+            {"stepType":"CreateField","model":"Blog","name":"views","type":"Int"}
+        ]));
+
+        assert!(FullTextSearchMigration::for_steps(&steps, &previous, &next).is_empty());
+    }
+
+    #[test]
+    fn deleted_and_retyped_fields_index_the_model_again() {
+        let previous = schema(vec![model("Blog", &[("title", "String"), ("summary", "String")])]);
+        let next = schema(vec![model("Blog", &[("title", "Int")])]);
+        let steps = steps(json!([
+            {"stepType":"DeleteField","model":"Blog","name":"summary"},
+            {"stepType":"UpdateField","model":"Blog","name":"title","type":"Int"}
+        ]));
+
+        // The model is indexed once for both steps, and without string fields left it has no index.
+        assert_eq!(
+            recreate(&next, "Blog"),
+            FullTextSearchMigration::for_steps(&steps, &previous, &next)
+        );
+        assert!(create(&next, "Blog").is_empty());
+    }
+
+    #[test]
+    fn renamed_models_drop_the_index_under_the_previous_name() {
+        let previous = schema(vec![model("Blog", &[("title", "String")])]);
+        let next = schema(vec![model("Journal", &[("title", "String")])]);
+        let steps = steps(json!([
+            {"stepType":"UpdateModel","name":"Blog","newName":"Journal"}
+        ]));
+
+        let mut expected = FullTextSearchMigration::drop(&previous.find_model("Blog").unwrap());
+        expected.append(&mut recreate(&next, "Journal"));
+
+        assert_eq!(expected, FullTextSearchMigration::for_steps(&steps, &previous, &next));
+    }
+
+    #[test]
+    fn other_steps_keep_the_index() {
+        let previous = schema(vec![model("Blog", &[("title", "String"), ("views", "Int")])]);
+        let next = schema(vec![model("Blog", &[("title", "String"), ("views", "Int")])]);
+        let steps = steps(json!([
+            {"stepType":"UpdateModel","name":"Blog","embedded":false},
+            {"stepType":"UpdateField","model":"Blog","name":"views","isOptional":true}
+        ]));
+
+        assert!(FullTextSearchMigration::for_steps(&steps, &previous, &next).is_empty());
+    }
+
+    fn create(schema: &Schema, model: &str) -> Vec<String> {
+        FullTextSearchMigration::create(&schema.find_model(model).unwrap())
+    }
+
+    fn recreate(schema: &Schema, model: &str) -> Vec<String> {
+        FullTextSearchMigration::recreate(&schema.find_model(model).unwrap())
+    }
+
+    fn steps(json: Value) -> Vec<MigrationStep> {
+        serde_json::from_value(json).expect("Deserializing the steps failed.")
+    }
+
+    fn schema(models: Vec<Value>) -> SchemaRef {
+        let template: SchemaTemplate = serde_json::from_value(json!({
+            "models": models,
+            "relations": [],
+            "enums": [],
+        }))
+        .expect("Deserializing the schema failed.");
+
+        template.build("test".to_string())
+    }
+
+    /// A model with an id and the given scalar fields.
+    fn model(name: &str, fields: &[(&str, &str)]) -> Value {
+        let fields: Vec<Value> = Some(&("id", "GraphQLID"))
+            .into_iter()
+            .chain(fields)
+            .map(|(name, type_identifier)| {
+                let is_id = *name == "id";
+
+                json!({
+                    "name": name,
+                    "typeIdentifier": type_identifier,
+                    "isRequired": is_id,
+                    "isList": false,
+                    "isUnique": is_id,
+                    "isHidden": false,
+                    "isAutoGenerated": false,
+                })
+            })
+            .collect();
+
+        json!({
+            "name": name,
+            "stableIdentifier": name.to_lowercase(),
+            "isEmbedded": false,
+            "fields": fields,
+        })
+    }
+}
@@ -1,2 +1,3 @@
+pub mod full_text_search;
 pub mod migration_steps_inferrer;
 pub mod schema_inferer;
@@ -0,0 +1,171 @@
+use crate::{Model, ScalarField, TypeIdentifier};
+use prisma_query::ast::*;
+use std::sync::Arc;
+
+/// The FTS5 table indexing the string fields of a model for full-text search. It holds a row per
+/// record with the record id and is kept in sync with the model table by triggers.
+#[derive(Debug, Clone)]
+pub struct FullTextSearchTable<'a> {
+    model: &'a Model,
+    table_name: String,
+}
+
+impl<'a> FullTextSearchTable<'a> {
+    pub const NODE_ID_FIELD_NAME: &'static str = "nodeId";
+    pub const RANK_FIELD_NAME: &'static str = "rank";
+
+    pub fn new(model: &'a Model) -> Self {
+        let table_name = format!("{}_fts", model.db_name());
+
+        Self { model, table_name }
+    }
+
+    pub fn table_name(&self) -> &str {
+        self.table_name.as_ref()
+    }
+
+    pub fn table(&self) -> Table {
+        let schema = self.model.schema();
+        let database_name = schema.db_name.as_ref();
+
+        Table::from((database_name, self.table_name.as_ref()))
+    }
+
+    /// The string fields of the model, indexed in columns of their database names.
+    pub fn fields(&self) -> Vec<Arc<ScalarField>> {
+        self.model
+            .fields()
+            .scalar_non_list()
+            .into_iter()
+            .filter(|field| field.type_identifier == TypeIdentifier::String)
+            .collect()
+    }
+
+    pub fn node_id_column(&self) -> Column {
+        Column::from(Self::NODE_ID_FIELD_NAME).table(self.table())
+    }
+
+    /// The relevance of a row to the search it matched, with better matches ranking lower.
+    pub fn rank_column(&self) -> Column {
+        Column::from(Self::RANK_FIELD_NAME).table(self.table())
+    }
+
+    /// The hidden column named like the table, which takes a search query as its value.
+    pub fn search_column(&self) -> Column {
+        Column::from(self.table_name.as_ref()).table(self.table())
+    }
+
+    /// Creates the table with triggers on the model table and fills it with the existing records.
+    /// Models without string fields have no table.
+    pub fn create_statements(&self) -> Vec<String> {
+        let fields = self.fields();
+
+        if fields.is_empty() {
+            return vec![];
+        }
+
+        let database = quote(&self.model.schema().db_name);
+        let table = quote(self.model.db_name());
+        let fts_table = quote(&self.table_name);
+        let id = quote(self.model.fields().id().db_name());
+        let node_id = quote(Self::NODE_ID_FIELD_NAME);
+
+        let columns: Vec<String> = fields.iter().map(|field| quote(field.db_name())).collect();
+        let columns = columns.join(", ");
+
+        let values = |record: &str| -> String {
+            let values: Vec<String> = fields
+                .iter()
+                .map(|field| format!("{}.{}", record, quote(field.db_name())))
+                .collect();
+
+            values.join(", ")
+        };
+
+        let insert = format!(
+            "INSERT INTO {} ({}, {}) VALUES (new.{}, {});",
+            fts_table,
+            node_id,
+            columns,
+            id,
+            values("new")
+        );
+
+        let delete = format!("DELETE FROM {} WHERE {} = old.{};", fts_table, node_id, id);
+
+        vec![
+            format!(
+                "CREATE VIRTUAL TABLE {}.{} USING fts5({} UNINDEXED, {});",
+                database, fts_table, node_id, columns
+            ),
+            format!(
+                "INSERT INTO {}.{} ({}, {}) SELECT {}, {} FROM {}.{};",
+                database, fts_table, node_id, columns, id, columns, database, table
+            ),
+            format!(
+                "CREATE TRIGGER {}.{} AFTER INSERT ON {} BEGIN {} END;",
+                database,
+                quote(&self.trigger_name("insert")),
+                table,
+                insert
+            ),
+            format!(
+                "CREATE TRIGGER {}.{} AFTER DELETE ON {} BEGIN {} END;",
+                database,
+                quote(&self.trigger_name("delete")),
+                table,
+                delete
+            ),
+            format!(
+                "CREATE TRIGGER {}.{} AFTER UPDATE ON {} BEGIN {} {} END;",
+                database,
+                quote(&self.trigger_name("update")),
+                table,
+                delete,
+                insert
+            ),
+        ]
+    }
+
+    /// Drops the table and its triggers, if they exist.
+    pub fn drop_statements(&self) -> Vec<String> {
+        let database = quote(&self.model.schema().db_name);
+
+        let drop_trigger = |operation: &str| {
+            format!(
+                "DROP TRIGGER IF EXISTS {}.{};",
+                database,
+                quote(&self.trigger_name(operation))
+            )
+        };
+
+        vec![
+            drop_trigger("insert"),
+            drop_trigger("delete"),
+            drop_trigger("update"),
+            format!("DROP TABLE IF EXISTS {}.{};", database, quote(&self.table_name)),
+        ]
+    }
+
+    /// The columns of the table, in the order they are created.
+    pub fn column_names(&self) -> Vec<String> {
+        let fields = self.fields();
+
+        if fields.is_empty() {
+            return vec![];
+        }
+
+        Some(Self::NODE_ID_FIELD_NAME.to_string())
+            .into_iter()
+            .chain(fields.iter().map(|field| field.db_name().to_string()))
+            .collect()
+    }
+
+    fn trigger_name(&self, operation: &str) -> String {
+        format!("{}_fts_{}", self.model.db_name(), operation)
+    }
+}
+
+fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
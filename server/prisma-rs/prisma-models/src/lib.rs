@@ -8,6 +8,7 @@ extern crate debug_stub_derive;
 mod error;
 mod field;
 mod fields;
+mod full_text_search_table;
 mod model;
 mod node;
 mod order_by;
@@ -24,6 +25,7 @@ pub mod prelude;
 pub use error::*;
 pub use field::*;
 pub use fields::*;
+pub use full_text_search_table::*;
 pub use model::*;
 pub use node::*;
 pub use order_by::*;
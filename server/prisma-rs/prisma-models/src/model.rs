@@ -117,4 +117,8 @@ impl Model {
     pub fn id_column(&self) -> Column {
         self.fields().id().as_column()
     }
+
+    pub fn full_text_search_table<'a>(&'a self) -> FullTextSearchTable<'a> {
+        FullTextSearchTable::new(self)
+    }
 }
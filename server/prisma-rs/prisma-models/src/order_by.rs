@@ -1,4 +1,4 @@
-use crate::{ModelRef, ScalarField};
+use crate::{ModelRef, RelationField, ScalarField};
 use std::sync::Arc;
 
 pub trait IntoOrderBy {
@@ -13,6 +13,12 @@ pub enum SortOrder {
 
 #[derive(Clone, Debug)]
 pub struct OrderBy {
+    /// To-one relation fields leading from the queried model to the model of `field`
+    pub path: Vec<Arc<RelationField>>,
     pub field: Arc<ScalarField>,
     pub sort_order: SortOrder,
+
+    /// Sorts by the rank of the records in a full-text search on `field` instead of its value,
+    /// with better matches ranking lower
+    pub search: Option<String>,
 }
@@ -219,6 +219,17 @@ impl Relation {
         }
     }
 
+    /// The name of the table linking the records, if the relation is not inlined in a model.
+    pub fn relation_table_name(&self) -> Option<String> {
+        use RelationLinkManifestation::*;
+
+        match self.manifestation {
+            Some(RelationTable(ref m)) => Some(m.table.clone()),
+            Some(Inline(_)) => None,
+            None => Some(format!("_{}", self.name)),
+        }
+    }
+
     pub fn model_a_column(&self) -> Column {
         use RelationLinkManifestation::*;
 
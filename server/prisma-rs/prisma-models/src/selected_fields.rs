@@ -157,6 +157,15 @@ impl SelectedFields {
         result
     }
 
+    /// Scalar list fields are not read with the columns, but from their own tables.
+    pub fn scalar_lists(&self) -> Vec<Arc<ScalarField>> {
+        self.scalar
+            .iter()
+            .filter(|sf| sf.field.is_list)
+            .map(|sf| sf.field.clone())
+            .collect()
+    }
+
     pub fn model(&self) -> ModelRef {
         let field = self
             .scalar
@@ -166,7 +175,8 @@ impl SelectedFields {
         field.field.model()
     }
 
-    fn relation_inlined(&self) -> Vec<Arc<RelationField>> {
+    /// Relation fields read from a column of the model's table, after the scalar fields.
+    pub fn relation_inlined(&self) -> Vec<Arc<RelationField>> {
         self.relation
             .iter()
             .map(|rf| Arc::clone(&rf.field))
@@ -0,0 +1,76 @@
+//! Aggregations over the records of a page.
+//!
+//! Unlike list queries, aggregations read exactly the records of the page, without the extra
+//! record telling if there's more data.
+use crate::{ConnectorUnderTest, TestDatabase};
+use connector::{Aggregation, AggregationSelection, QueryArguments};
+use prisma_models::*;
+
+fn create_posts<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    for (i, id) in ["p1", "p2", "p3", "p4", "p5", "p6"].iter().enumerate() {
+        db.create(
+            "Post",
+            id,
+            vec![
+                ("title", PrismaValue::from(*id)),
+                ("score", PrismaValue::Int(i as i32 + 1)),
+            ],
+        );
+    }
+}
+
+pub fn aggregations_of_pages<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_posts(db);
+
+    let score = db.scalar_field("Post", "score");
+    let selection = AggregationSelection {
+        aggregations: vec![Aggregation::Count, Aggregation::Sum(score)],
+        ..Default::default()
+    };
+
+    let aggregate = |first: Option<u32>, last: Option<u32>, skip: Option<u32>| -> Vec<PrismaValue> {
+        let query_arguments = QueryArguments {
+            first,
+            last,
+            skip,
+            ..Default::default()
+        };
+
+        let mut rows = db
+            .connector()
+            .aggregate(db.model("Post"), query_arguments, selection.clone())
+            .unwrap();
+
+        assert_eq!(1, rows.len());
+        rows.remove(0).values
+    };
+
+    let count_and_sum = |count: i32, sum: i32| vec![PrismaValue::Int(count), PrismaValue::Int(sum)];
+
+    assert_eq!(count_and_sum(6, 21), aggregate(None, None, None));
+    assert_eq!(count_and_sum(2, 3), aggregate(Some(2), None, None));
+    assert_eq!(count_and_sum(2, 5), aggregate(Some(2), None, Some(1)));
+    assert_eq!(count_and_sum(2, 11), aggregate(None, Some(2), None));
+    assert_eq!(count_and_sum(6, 21), aggregate(Some(10), None, None));
+}
+
+pub fn counts_of_pages<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_posts(db);
+
+    let count = |first: Option<u32>, last: Option<u32>| {
+        let query_arguments = QueryArguments {
+            first,
+            last,
+            ..Default::default()
+        };
+
+        db.connector()
+            .count_by_model(db.model("Post"), query_arguments)
+            .unwrap()
+    };
+
+    assert_eq!(6, count(None, None));
+    assert_eq!(2, count(Some(2), None));
+    assert_eq!(3, count(None, Some(3)));
+    assert_eq!(0, count(Some(0), None));
+}
@@ -0,0 +1,116 @@
+//! Cursors combined with orderings.
+//!
+//! Records are ordered by the sort keys and then by their id, unless the id is one of the keys.
+//! A cursor compares all of them, so records with the same values in the sort keys as the
+//! cursor are told apart by their id. `NULL` values come first in ascending order and compare
+//! to nothing, so a record with a `NULL` sort key is never past a cursor.
+use crate::{graphql_ids, ConnectorUnderTest, TestDatabase};
+use connector::QueryArguments;
+use prisma_models::*;
+
+fn create_posts<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    let posts: Vec<(&str, PrismaValue, bool)> = vec![
+        ("p1", 3.into(), true),
+        ("p2", 1.into(), false),
+        ("p3", 2.into(), true),
+        ("p4", PrismaValue::Null, false),
+        ("p5", 2.into(), false),
+        ("p6", 1.into(), true),
+    ];
+
+    for (id, score, published) in posts {
+        db.create(
+            "Post",
+            id,
+            vec![("title", id.into()), ("score", score), ("published", published.into())],
+        );
+    }
+}
+
+fn order_by<C: ConnectorUnderTest>(db: &TestDatabase<C>, field: &str, sort_order: SortOrder) -> OrderBy {
+    OrderBy {
+        path: Vec::new(),
+        field: db.scalar_field("Post", field),
+        sort_order,
+        search: None,
+    }
+}
+
+pub fn cursors_with_ordering<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_posts(db);
+
+    let by_score = vec![order_by(db, "score", SortOrder::Ascending)];
+    let posts = |ordering: &[OrderBy], after: Option<&str>, before: Option<&str>| {
+        let query_arguments = QueryArguments {
+            after: after.map(GraphqlId::from),
+            before: before.map(GraphqlId::from),
+            order_by: ordering.to_vec(),
+            ..Default::default()
+        };
+
+        db.ids("Post", query_arguments)
+    };
+
+    assert_eq!(
+        graphql_ids(&["p4", "p2", "p6", "p3", "p5", "p1"]),
+        posts(&by_score, None, None)
+    );
+
+    assert_eq!(graphql_ids(&["p3", "p5", "p1"]), posts(&by_score, Some("p6"), None));
+    assert_eq!(graphql_ids(&["p2", "p6"]), posts(&by_score, None, Some("p3")));
+    assert_eq!(
+        graphql_ids(&["p6", "p3", "p5"]),
+        posts(&by_score, Some("p2"), Some("p1"))
+    );
+    assert_eq!(graphql_ids(&[]), posts(&by_score, Some("p4"), None));
+
+    // Ids stay an ascending tie-breaker in descending order.
+    let by_score_desc = vec![order_by(db, "score", SortOrder::Descending)];
+
+    assert_eq!(
+        graphql_ids(&["p1", "p3", "p5", "p2", "p6", "p4"]),
+        posts(&by_score_desc, None, None)
+    );
+
+    assert_eq!(graphql_ids(&["p2", "p6"]), posts(&by_score_desc, Some("p5"), None));
+
+    let by_id_desc = vec![order_by(db, "id", SortOrder::Descending)];
+    assert_eq!(graphql_ids(&["p2", "p1"]), posts(&by_id_desc, Some("p3"), None));
+}
+
+pub fn cursors_with_multiple_keys<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_posts(db);
+
+    let ordering = vec![
+        order_by(db, "published", SortOrder::Ascending),
+        order_by(db, "score", SortOrder::Descending),
+    ];
+
+    let posts = |after: Option<&str>| {
+        let query_arguments = QueryArguments {
+            after: after.map(GraphqlId::from),
+            order_by: ordering.clone(),
+            ..Default::default()
+        };
+
+        db.ids("Post", query_arguments)
+    };
+
+    assert_eq!(graphql_ids(&["p5", "p2", "p4", "p1", "p3", "p6"]), posts(None));
+
+    // The post without a score ties with the cursor on the first key, and is unknown on the second.
+    assert_eq!(graphql_ids(&["p1", "p3", "p6"]), posts(Some("p2")));
+}
+
+pub fn cursors_with_last<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_posts(db);
+
+    let query_arguments = QueryArguments {
+        before: Some(GraphqlId::from("p1")),
+        last: Some(2),
+        order_by: vec![order_by(db, "score", SortOrder::Ascending)],
+        ..Default::default()
+    };
+
+    assert_eq!(graphql_ids(&["p5", "p3", "p6"]), db.ids("Post", query_arguments));
+}
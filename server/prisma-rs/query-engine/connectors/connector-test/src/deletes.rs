@@ -0,0 +1,165 @@
+//! Deleting records, and what goes with them.
+//!
+//! A record required by a related record can't be deleted. Otherwise, deleting a record removes
+//! its scalar list values and its links to other records, which stay without the relation.
+//! Cascading deletes are executed by the caller before the delete, so a record required through a
+//! cascading relation can't be deleted either.
+use crate::{assert_relation_violation, graphql_ids, ConnectorUnderTest, TestDatabase};
+use connector::{filter::Filter, mutaction::*, *};
+use prisma_models::*;
+
+fn create_user_with_posts<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    let tags = vec![PrismaValue::from("a"), PrismaValue::from("b")];
+    db.create_with_lists("User", "u1", vec![("name", "Alice".into())], vec![("tags", tags)]);
+
+    for id in &["p1", "p2"] {
+        db.create("Post", id, vec![("title", PrismaValue::from(*id))]);
+        db.connect("User", "posts", "u1", id);
+    }
+}
+
+fn create_posts_with_revisions<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    for (post, revisions) in &[("p1", vec!["r1", "r2"]), ("p2", vec!["r3"])] {
+        db.create("Post", post, vec![("title", PrismaValue::from(*post))]);
+
+        for id in revisions {
+            db.create("Revision", id, vec![("text", PrismaValue::from(*id))]);
+            db.connect("Post", "revisions", post, id);
+        }
+    }
+}
+
+fn delete_node<C: ConnectorUnderTest>(db: &TestDatabase<C>, model: &str, id: &str) -> ConnectorResult<()> {
+    let delete = DeleteNode {
+        where_: db.selector(model, id),
+    };
+
+    db.execute(TopLevelDatabaseMutaction::DeleteNode(delete)).map(|_| ())
+}
+
+fn delete_nodes<C: ConnectorUnderTest>(db: &TestDatabase<C>, model: &str, filter: Filter) -> ConnectorResult<usize> {
+    let delete = DeleteNodes {
+        model: db.model(model),
+        filter,
+    };
+
+    let mut results = db.execute(TopLevelDatabaseMutaction::DeleteNodes(delete))?;
+    Ok(results.pop().unwrap().count())
+}
+
+pub fn delete_relation_violations<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_user_with_posts(db);
+
+    let nested_create = |model: &str, field: &str, parent_id: &str, non_list_args: PrismaArgs| {
+        let create = NestedCreateNode {
+            relation_field: db.relation_field(model, field),
+            non_list_args,
+            list_args: Vec::new(),
+            top_is_create: false,
+            nested_mutactions: NestedMutactions::default(),
+        };
+
+        db.execute_nested(parent_id, NestedDatabaseMutaction::CreateNode(create))
+            .unwrap();
+    };
+
+    let mut profile = PrismaArgs::new();
+    profile.insert("id", GraphqlId::from("pr1"));
+    nested_create("User", "profile", "u1", profile);
+
+    let mut comment = PrismaArgs::new();
+    comment.insert("id", GraphqlId::from("c1"));
+    comment.insert("text", "Hi");
+    nested_create("Post", "comments", "p1", comment);
+
+    assert_relation_violation("UserProfile", delete_node(db, "User", "u1"));
+    assert_relation_violation("PostComments", delete_node(db, "Post", "p1"));
+    assert_relation_violation("PostComments", delete_nodes(db, "Post", Filter::and(vec![])));
+
+    // Nothing was deleted by the failed mutactions.
+    assert!(db.exists("User", "u1"));
+    assert_eq!(2, db.count("Post"));
+
+    delete_node(db, "Comment", "c1").unwrap();
+    assert_eq!(2, delete_nodes(db, "Post", Filter::and(vec![])).unwrap());
+}
+
+pub fn deletes_remove_lists_and_links<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_user_with_posts(db);
+
+    assert_eq!(2, db.count_table("_UserPosts"));
+    assert_eq!(2, db.count_table("User_tags"));
+
+    delete_node(db, "User", "u1").unwrap();
+
+    assert!(!db.exists("User", "u1"));
+    assert_eq!(0, db.count_table("_UserPosts"));
+    assert_eq!(0, db.count_table("User_tags"));
+
+    let author = db.relation_field("Post", "author");
+    let without_author = QueryArguments::from(author.one_relation_is_null());
+
+    assert_eq!(graphql_ids(&["p1", "p2"]), db.ids("Post", without_author));
+}
+
+pub fn delete_many<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    for (id, published) in &[("p1", true), ("p2", false), ("p3", true), ("p4", false)] {
+        db.create(
+            "Post",
+            id,
+            vec![
+                ("title", PrismaValue::from(*id)),
+                ("published", PrismaValue::from(*published)),
+            ],
+        );
+    }
+
+    let published = db.scalar_field("Post", "published");
+
+    assert_eq!(2, delete_nodes(db, "Post", published.equals(true)).unwrap());
+    assert_eq!(0, delete_nodes(db, "Post", published.equals(true)).unwrap());
+    assert_eq!(graphql_ids(&["p2", "p4"]), db.ids("Post", QueryArguments::default()));
+}
+
+pub fn reset_data<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_user_with_posts(db);
+    db.reset();
+
+    assert_eq!(0, db.count("User"));
+    assert_eq!(0, db.count("Post"));
+    assert_eq!(0, db.count_table("_UserPosts"));
+    assert_eq!(0, db.count_table("User_tags"));
+}
+
+/// Relations cascading on delete require their records like any other relation.
+pub fn cascading_relation_violations<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_posts_with_revisions(db);
+    db.create("User", "u1", vec![("name", "Alice".into())]);
+    db.connect("User", "posts", "u1", "p2");
+
+    let nested_delete = NestedDeleteNode {
+        relation_field: db.relation_field("User", "posts"),
+        where_: Some(db.selector("Post", "p2")),
+    };
+
+    assert_relation_violation("PostRevisions", delete_node(db, "Post", "p1"));
+    assert_relation_violation("PostRevisions", delete_nodes(db, "Post", Filter::and(vec![])));
+    assert_relation_violation(
+        "PostRevisions",
+        db.execute_nested("u1", NestedDatabaseMutaction::DeleteNode(nested_delete)),
+    );
+
+    // Nothing was deleted by the failed mutactions.
+    assert_eq!(2, db.count("Post"));
+    assert_eq!(3, db.count("Revision"));
+    assert_eq!(3, db.count_table("_PostRevisions"));
+
+    for id in &["r1", "r2"] {
+        delete_node(db, "Revision", id).unwrap();
+    }
+
+    delete_node(db, "Post", "p1").unwrap();
+
+    assert_eq!(graphql_ids(&["p2"]), db.ids("Post", QueryArguments::default()));
+    assert_eq!(graphql_ids(&["r3"]), db.ids("Revision", QueryArguments::default()));
+}
@@ -0,0 +1,208 @@
+//! Filters on scalar fields, scalar lists and relations.
+//!
+//! Conditions compare like in SQL: a condition on a `NULL` value is unknown, and unknown
+//! conditions exclude a record even when negated.
+use crate::{graphql_ids, ConnectorUnderTest, TestDatabase};
+use connector::{filter::*, *};
+use prisma_models::*;
+use std::sync::Arc;
+
+fn create_users<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    let users: Vec<(&str, &str, PrismaValue, PrismaValue, Vec<&str>)> = vec![
+        ("u1", "Alice", "alice@prisma.io".into(), 30.into(), vec!["a", "b"]),
+        ("u2", "bob", PrismaValue::Null, PrismaValue::Null, vec!["b"]),
+        ("u3", "Carol", "carol@example.com".into(), 25.into(), vec![]),
+        ("u4", "Dave", "dave@prisma.io".into(), 30.into(), vec!["c"]),
+    ];
+
+    for (id, name, email, age, tags) in users {
+        let tags = tags.into_iter().map(PrismaValue::from).collect();
+
+        db.create_with_lists(
+            "User",
+            id,
+            vec![("name", name.into()), ("email", email), ("age", age)],
+            vec![("tags", tags)],
+        );
+    }
+}
+
+fn users<C: ConnectorUnderTest>(db: &TestDatabase<C>, filter: Filter) -> Vec<GraphqlId> {
+    db.ids("User", QueryArguments::from(filter))
+}
+
+fn with_condition(field: Arc<ScalarField>, condition: ScalarCondition, mode: StringMode) -> Filter {
+    Filter::from(ScalarFilter { field, condition, mode })
+}
+
+pub fn scalar_filters<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_users(db);
+
+    let name = db.scalar_field("User", "name");
+    let age = db.scalar_field("User", "age");
+
+    assert_eq!(graphql_ids(&["u1", "u4"]), users(db, age.equals(30)));
+    assert_eq!(graphql_ids(&["u1", "u4"]), users(db, age.greater_than(25)));
+    assert_eq!(
+        graphql_ids(&["u1", "u3", "u4"]),
+        users(db, age.greater_than_or_equals(25))
+    );
+    assert_eq!(graphql_ids(&["u3"]), users(db, age.less_than(30)));
+    assert_eq!(graphql_ids(&["u2", "u3"]), users(db, name.contains("o")));
+    assert_eq!(graphql_ids(&["u1", "u4"]), users(db, name.not_contains("o")));
+
+    // Every name contains the empty string.
+    assert_eq!(graphql_ids(&["u1", "u2", "u3", "u4"]), users(db, name.contains("")));
+    assert_eq!(graphql_ids(&[]), users(db, name.not_contains("")));
+    assert_eq!(graphql_ids(&["u1"]), users(db, name.starts_with("A")));
+    assert_eq!(graphql_ids(&["u1", "u4"]), users(db, name.ends_with("e")));
+    assert_eq!(graphql_ids(&["u2", "u3"]), users(db, name.not_ends_with("e")));
+
+    // Suffixes as long as or longer than the value.
+    assert_eq!(graphql_ids(&["u3"]), users(db, name.ends_with("Carol")));
+    assert_eq!(graphql_ids(&[]), users(db, name.ends_with("xCarol")));
+    assert_eq!(graphql_ids(&["u1", "u2", "u3", "u4"]), users(db, name.ends_with("")));
+
+    let names = vec!["bob".into(), "Dave".into(), "Eve".into()];
+    let filter = with_condition(name, ScalarCondition::In(names), StringMode::Default);
+
+    assert_eq!(graphql_ids(&["u2", "u4"]), users(db, filter));
+}
+
+pub fn null_filters<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_users(db);
+
+    let email = db.scalar_field("User", "email");
+    let age = db.scalar_field("User", "age");
+
+    assert_eq!(graphql_ids(&["u2"]), users(db, age.equals(PrismaValue::Null)));
+    assert_eq!(
+        graphql_ids(&["u1", "u3", "u4"]),
+        users(db, age.not_equals(PrismaValue::Null))
+    );
+
+    // The user without an age is neither 30 nor not 30.
+    assert_eq!(graphql_ids(&["u3"]), users(db, age.not_equals(30)));
+    assert_eq!(graphql_ids(&["u3"]), users(db, Filter::not(vec![age.equals(30)])));
+    assert_eq!(graphql_ids(&["u3"]), users(db, email.not_contains("prisma")));
+
+    let either = Filter::or(vec![age.equals(30), email.equals(PrismaValue::Null)]);
+    assert_eq!(graphql_ids(&["u1", "u2", "u4"]), users(db, either));
+
+    let in_null = with_condition(
+        Arc::clone(&age),
+        ScalarCondition::In(vec![PrismaValue::Null]),
+        StringMode::Default,
+    );
+    assert_eq!(graphql_ids(&["u2"]), users(db, in_null));
+
+    let not_in = with_condition(age, ScalarCondition::NotIn(vec![25.into()]), StringMode::Default);
+    assert_eq!(graphql_ids(&["u1", "u4"]), users(db, not_in));
+}
+
+pub fn insensitive_filters<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_users(db);
+
+    let name = db.scalar_field("User", "name");
+    let equals = ScalarCondition::Equals("BOB".into());
+
+    let sensitive = with_condition(Arc::clone(&name), equals.clone(), StringMode::Default);
+    assert_eq!(graphql_ids(&[]), users(db, sensitive));
+
+    let insensitive = with_condition(Arc::clone(&name), equals, StringMode::Insensitive);
+    assert_eq!(graphql_ids(&["u2"]), users(db, insensitive));
+
+    let starts_with = ScalarCondition::StartsWith("a".into());
+    let insensitive = with_condition(name, starts_with, StringMode::Insensitive);
+    assert_eq!(graphql_ids(&["u1"]), users(db, insensitive));
+}
+
+pub fn combined_filters<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_users(db);
+
+    let name = db.scalar_field("User", "name");
+    let age = db.scalar_field("User", "age");
+    let all = graphql_ids(&["u1", "u2", "u3", "u4"]);
+
+    // Empty lists of filters are no condition at all.
+    assert_eq!(all, users(db, Filter::and(vec![])));
+    assert_eq!(all, users(db, Filter::or(vec![])));
+    assert_eq!(all, users(db, Filter::not(vec![])));
+
+    let both = Filter::and(vec![age.equals(30), name.starts_with("D")]);
+    assert_eq!(graphql_ids(&["u4"]), users(db, both));
+
+    let neither = Filter::not(vec![name.starts_with("A"), name.starts_with("D")]);
+    assert_eq!(graphql_ids(&["u2", "u3"]), users(db, neither));
+}
+
+pub fn scalar_list_filters<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_users(db);
+
+    let tags = db.scalar_field("User", "tags");
+    let filter = |condition| {
+        Filter::from(ScalarListFilter {
+            field: Arc::clone(&tags),
+            condition,
+        })
+    };
+
+    let contains = ScalarListCondition::Contains("b".into());
+    assert_eq!(graphql_ids(&["u1", "u2"]), users(db, filter(contains)));
+
+    let contains_every = ScalarListCondition::ContainsEvery(vec!["a".into(), "b".into()]);
+    assert_eq!(graphql_ids(&["u1"]), users(db, filter(contains_every)));
+
+    let contains_some = ScalarListCondition::ContainsSome(vec!["a".into(), "c".into()]);
+    assert_eq!(graphql_ids(&["u1", "u4"]), users(db, filter(contains_some)));
+}
+
+pub fn relation_filters<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_users(db);
+
+    let posts = vec![
+        ("p1", "Hello", true),
+        ("p2", "World", false),
+        ("p3", "Hi", true),
+        ("p4", "Draft", false),
+    ];
+
+    for (id, title, published) in posts {
+        db.create(
+            "Post",
+            id,
+            vec![("title", title.into()), ("published", published.into())],
+        );
+    }
+
+    db.connect("User", "posts", "u1", "p1");
+    db.connect("User", "posts", "u1", "p2");
+    db.connect("User", "posts", "u2", "p3");
+
+    let user_posts = db.relation_field("User", "posts");
+    let author = db.relation_field("Post", "author");
+    let published = db.scalar_field("Post", "published");
+    let name = db.scalar_field("User", "name");
+
+    let some = user_posts.at_least_one_related(published.equals(true));
+    assert_eq!(graphql_ids(&["u1", "u2"]), users(db, some));
+
+    // Users without posts have only published posts, and no published ones.
+    let every = user_posts.every_related(published.equals(true));
+    assert_eq!(graphql_ids(&["u2", "u3", "u4"]), users(db, every));
+
+    let none = user_posts.no_related(published.equals(true));
+    assert_eq!(graphql_ids(&["u3", "u4"]), users(db, none));
+
+    let without_author = author.one_relation_is_null();
+    assert_eq!(
+        graphql_ids(&["p4"]),
+        db.ids("Post", QueryArguments::from(without_author))
+    );
+
+    let by_alice = author.at_least_one_related(name.equals("Alice"));
+    assert_eq!(
+        graphql_ids(&["p1", "p2"]),
+        db.ids("Post", QueryArguments::from(by_alice))
+    );
+}
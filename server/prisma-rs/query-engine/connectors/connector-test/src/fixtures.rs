@@ -0,0 +1,158 @@
+//! The schema of every case, built from templates.
+use prisma_models::*;
+
+/// Users with posts and a profile, and posts with comments and revisions.
+///
+/// Comments and revisions require a post and profiles require a user, so posts with comments or
+/// revisions and users with a profile can't be deleted. Revisions cascade from their post, which
+/// is left to the caller of the connector.
+pub fn blog() -> SchemaTemplate {
+    SchemaTemplate {
+        models: vec![
+            model(
+                "User",
+                vec![
+                    required(scalar("name", TypeIdentifier::String)),
+                    unique(scalar("email", TypeIdentifier::String)),
+                    scalar("age", TypeIdentifier::Int),
+                    list(scalar("tags", TypeIdentifier::String)),
+                    list(relation_field("posts", "UserPosts", RelationSide::B)),
+                    relation_field("profile", "UserProfile", RelationSide::B),
+                ],
+            ),
+            model(
+                "Post",
+                vec![
+                    required(scalar("title", TypeIdentifier::String)),
+                    scalar("score", TypeIdentifier::Int),
+                    scalar("published", TypeIdentifier::Boolean),
+                    relation_field("author", "UserPosts", RelationSide::A),
+                    list(relation_field("comments", "PostComments", RelationSide::B)),
+                    list(relation_field("revisions", "PostRevisions", RelationSide::B)),
+                ],
+            ),
+            model(
+                "Comment",
+                vec![
+                    required(scalar("text", TypeIdentifier::String)),
+                    required(relation_field("post", "PostComments", RelationSide::A)),
+                ],
+            ),
+            model(
+                "Profile",
+                vec![
+                    scalar("bio", TypeIdentifier::String),
+                    required(relation_field("user", "UserProfile", RelationSide::A)),
+                ],
+            ),
+            model(
+                "Revision",
+                vec![
+                    required(scalar("text", TypeIdentifier::String)),
+                    required(relation_field("post", "PostRevisions", RelationSide::A)),
+                ],
+            ),
+        ],
+        relations: vec![
+            relation("UserPosts", "Post", "User"),
+            relation("PostComments", "Comment", "Post"),
+            relation("UserProfile", "Profile", "User"),
+            RelationTemplate {
+                model_b_on_delete: OnDelete::Cascade,
+                ..relation("PostRevisions", "Revision", "Post")
+            },
+        ],
+        enums: Vec::new(),
+        version: None,
+    }
+}
+
+/// A model with an `id` field in front of the given fields.
+fn model(name: &str, fields: Vec<FieldTemplate>) -> ModelTemplate {
+    let id = ScalarFieldTemplate {
+        is_required: true,
+        is_unique: true,
+        ..scalar_template("id", TypeIdentifier::GraphQLID)
+    };
+
+    ModelTemplate {
+        name: name.to_string(),
+        stable_identifier: name.to_lowercase(),
+        is_embedded: false,
+        fields: Some(FieldTemplate::Scalar(id)).into_iter().chain(fields).collect(),
+        manifestation: None,
+    }
+}
+
+/// A relation kept in a relation table, setting the links to null when deleting on either side.
+fn relation(name: &str, model_a: &str, model_b: &str) -> RelationTemplate {
+    RelationTemplate {
+        name: name.to_string(),
+        model_a_on_delete: OnDelete::SetNull,
+        model_b_on_delete: OnDelete::SetNull,
+        manifestation: None,
+        model_a_name: model_a.to_string(),
+        model_b_name: model_b.to_string(),
+    }
+}
+
+fn scalar(name: &str, type_identifier: TypeIdentifier) -> FieldTemplate {
+    FieldTemplate::Scalar(scalar_template(name, type_identifier))
+}
+
+fn scalar_template(name: &str, type_identifier: TypeIdentifier) -> ScalarFieldTemplate {
+    ScalarFieldTemplate {
+        name: name.to_string(),
+        type_identifier,
+        is_required: false,
+        is_list: false,
+        is_unique: false,
+        is_hidden: false,
+        is_auto_generated: false,
+        manifestation: None,
+        behaviour: None,
+        internal_enum: None,
+    }
+}
+
+fn relation_field(name: &str, relation_name: &str, relation_side: RelationSide) -> FieldTemplate {
+    FieldTemplate::Relation(RelationFieldTemplate {
+        name: name.to_string(),
+        type_identifier: TypeIdentifier::Relation,
+        is_required: false,
+        is_list: false,
+        is_unique: false,
+        is_hidden: false,
+        is_auto_generated: false,
+        manifestation: None,
+        relation_name: relation_name.to_string(),
+        relation_side,
+    })
+}
+
+fn required(mut field: FieldTemplate) -> FieldTemplate {
+    match field {
+        FieldTemplate::Scalar(ref mut sf) => sf.is_required = true,
+        FieldTemplate::Relation(ref mut rf) => rf.is_required = true,
+    }
+
+    field
+}
+
+fn unique(mut field: FieldTemplate) -> FieldTemplate {
+    match field {
+        FieldTemplate::Scalar(ref mut sf) => sf.is_unique = true,
+        FieldTemplate::Relation(ref mut rf) => rf.is_unique = true,
+    }
+
+    field
+}
+
+fn list(mut field: FieldTemplate) -> FieldTemplate {
+    match field {
+        FieldTemplate::Scalar(ref mut sf) => sf.is_list = true,
+        FieldTemplate::Relation(ref mut rf) => rf.is_list = true,
+    }
+
+    field
+}
@@ -0,0 +1,101 @@
+//! # Connector conformance tests
+//!
+//! Cases checking that a connector resolves queries and executes mutactions the way the query
+//! engine expects: filters, pagination, aggregations, cursors with orderings, nested mutactions,
+//! deletes and the relation checks guarding them.
+//!
+//! Every case runs in a database of its own, named after the case, with the schema built in
+//! `fixtures::blog`. The records of earlier runs are removed before a case starts.
+//! Connectors keeping their data in SQL databases need the tables of the fixture to exist, so
+//! they pass a migration creating them.
+//!
+//! A connector crate runs all cases from an integration test:
+//!
+//! ```ignore
+//! use in_memory_connector::InMemory;
+//!
+//! connector_test::conformance_tests!(InMemory::new());
+//! ```
+//!
+//! With the tables created before every case:
+//!
+//! ```ignore
+//! connector_test::conformance_tests!(Sqlite::new(1, true).unwrap(), migrate);
+//! ```
+pub mod aggregations;
+pub mod cursors;
+pub mod deletes;
+pub mod filters;
+pub mod fixtures;
+pub mod nested_mutactions;
+pub mod pagination;
+
+mod test_database;
+
+pub use test_database::*;
+
+/// Generates a test for every case, each running against a connector created with the given
+/// expression. Connectors needing tables pass a function creating them for the schema of the
+/// fixture, which is called with the connector before the case runs.
+///
+/// Attributes for every test, like `#[ignore]` for cases needing a database server, are given
+/// before the named arguments:
+///
+/// ```ignore
+/// connector_test::conformance_tests!(#[ignore] connector: postgres(), migrate: migrate);
+/// ```
+#[macro_export]
+macro_rules! conformance_tests {
+    (@cases [$(#[$attr:meta])*] $connector:expr, $migrate:expr;) => {};
+    (@cases [$(#[$attr:meta])*] $connector:expr, $migrate:expr; $module:ident::$case:ident, $($cases:tt)*) => {
+        #[test]
+        $(#[$attr])*
+        fn $case() {
+            let connector = $connector;
+            $crate::TestDatabase::run(&connector, stringify!($case), $migrate, $crate::$module::$case);
+        }
+
+        $crate::conformance_tests!(@cases [$(#[$attr])*] $connector, $migrate; $($cases)*);
+    };
+    (@all [$(#[$attr:meta])*] $connector:expr, $migrate:expr) => {
+        $crate::conformance_tests!(@cases [$(#[$attr])*] $connector, $migrate;
+            filters::scalar_filters,
+            filters::null_filters,
+            filters::insensitive_filters,
+            filters::combined_filters,
+            filters::scalar_list_filters,
+            filters::relation_filters,
+            pagination::pagination,
+            pagination::pagination_edge_cases,
+            pagination::related_pagination,
+            pagination::related_counts,
+            aggregations::aggregations_of_pages,
+            aggregations::counts_of_pages,
+            cursors::cursors_with_ordering,
+            cursors::cursors_with_multiple_keys,
+            cursors::cursors_with_last,
+            nested_mutactions::nested_creates,
+            nested_mutactions::nested_connects,
+            nested_mutactions::nested_disconnects,
+            nested_mutactions::nested_sets,
+            nested_mutactions::nested_updates,
+            nested_mutactions::nested_upserts,
+            nested_mutactions::nested_deletes,
+            nested_mutactions::nested_mutactions_without_parent,
+            deletes::delete_relation_violations,
+            deletes::deletes_remove_lists_and_links,
+            deletes::delete_many,
+            deletes::reset_data,
+            deletes::cascading_relation_violations,
+        );
+    };
+    ($(#[$attr:meta])+ connector: $connector:expr, migrate: $migrate:expr) => {
+        $crate::conformance_tests!(@all [$(#[$attr])+] $connector, $migrate);
+    };
+    ($connector:expr, $migrate:expr) => {
+        $crate::conformance_tests!(@all [] $connector, $migrate);
+    };
+    ($connector:expr) => {
+        $crate::conformance_tests!($connector, |_, _| ());
+    };
+}
@@ -0,0 +1,329 @@
+//! Nested mutactions, executed with the id of their parent record.
+//!
+//! Nested mutactions check the relation before writing anything, and a failing mutaction leaves
+//! the database as it was.
+use crate::{
+    assert_node_not_found, assert_nodes_not_connected, assert_relation_violation, expect_error, graphql_id_pairs,
+    graphql_ids, ConnectorUnderTest, TestDatabase,
+};
+use connector::{error::ConnectorError, filter::NodeSelector, mutaction::*, DatabaseMutactionExecutor, QueryArguments};
+use prisma_models::*;
+
+fn args(id: &str, values: Vec<(&str, PrismaValue)>) -> PrismaArgs {
+    let mut args = PrismaArgs::new();
+    args.insert("id", GraphqlId::from(id));
+
+    for (name, value) in values {
+        args.insert(name, value);
+    }
+
+    args
+}
+
+fn nested_create<C: ConnectorUnderTest>(
+    db: &TestDatabase<C>,
+    model: &str,
+    field: &str,
+    non_list_args: PrismaArgs,
+    top_is_create: bool,
+) -> NestedCreateNode {
+    NestedCreateNode {
+        relation_field: db.relation_field(model, field),
+        non_list_args,
+        list_args: Vec::new(),
+        top_is_create,
+        nested_mutactions: NestedMutactions::default(),
+    }
+}
+
+fn nested_update<C: ConnectorUnderTest>(
+    db: &TestDatabase<C>,
+    model: &str,
+    field: &str,
+    where_: Option<NodeSelector>,
+    non_list_args: PrismaArgs,
+) -> NestedUpdateNode {
+    NestedUpdateNode {
+        relation_field: db.relation_field(model, field),
+        where_,
+        non_list_args,
+        list_args: Vec::new(),
+        json_updates: Vec::new(),
+        nested_mutactions: NestedMutactions::default(),
+    }
+}
+
+fn posts_of<C: ConnectorUnderTest>(db: &TestDatabase<C>, user_id: &str) -> Vec<(GraphqlId, GraphqlId)> {
+    db.related_ids("User", "posts", &[user_id], QueryArguments::default())
+}
+
+/// Two users, the first one with two posts, the second one with one post.
+fn create_users_with_posts<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    for (id, name) in &[("u1", "Alice"), ("u2", "Bob")] {
+        db.create("User", id, vec![("name", PrismaValue::from(*name))]);
+    }
+
+    for id in &["p1", "p2", "p3"] {
+        db.create("Post", id, vec![("title", PrismaValue::from(*id))]);
+    }
+
+    db.connect("User", "posts", "u1", "p1");
+    db.connect("User", "posts", "u1", "p2");
+    db.connect("User", "posts", "u2", "p3");
+}
+
+pub fn nested_creates<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    let comment = nested_create(
+        db,
+        "Post",
+        "comments",
+        args("c1", vec![("text", "First!".into())]),
+        true,
+    );
+
+    let mut post = nested_create(db, "User", "posts", args("p1", vec![("title", "Hello".into())]), true);
+    post.nested_mutactions.creates.push(comment);
+
+    let mut nested_mutactions = NestedMutactions::default();
+    nested_mutactions.creates.push(post);
+
+    let create = CreateNode {
+        model: db.model("User"),
+        non_list_args: args("u1", vec![("name", "Alice".into())]),
+        list_args: Vec::new(),
+        nested_mutactions,
+    };
+
+    db.execute(TopLevelDatabaseMutaction::CreateNode(create)).unwrap();
+
+    assert_eq!(graphql_id_pairs(&[("u1", "p1")]), posts_of(db, "u1"));
+    assert_eq!(
+        graphql_id_pairs(&[("p1", "c1")]),
+        db.related_ids("Post", "comments", &["p1"], QueryArguments::default())
+    );
+
+    // A profile requires its user, so a user can't get a second one.
+    let profile = |id: &str| {
+        let create = nested_create(db, "User", "profile", args(id, vec![("bio", "Hi".into())]), false);
+        NestedDatabaseMutaction::CreateNode(create)
+    };
+
+    db.execute_nested("u1", profile("pr1")).unwrap();
+    assert_relation_violation("UserProfile", db.execute_nested("u1", profile("pr2")));
+
+    assert!(db.exists("Profile", "pr1"));
+    assert!(!db.exists("Profile", "pr2"));
+}
+
+pub fn nested_connects<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_users_with_posts(db);
+
+    // A post has one author, so connecting it to another user moves it.
+    db.connect("User", "posts", "u2", "p1");
+
+    assert_eq!(graphql_id_pairs(&[("u1", "p2")]), posts_of(db, "u1"));
+    assert_eq!(
+        graphql_id_pairs(&[("p1", "u2")]),
+        db.related_ids("Post", "author", &["p1"], QueryArguments::default())
+    );
+
+    let connect = NestedConnect {
+        relation_field: db.relation_field("User", "posts"),
+        where_: db.selector("Post", "p9"),
+        top_is_create: false,
+    };
+
+    assert_node_not_found(db.execute_nested("u1", NestedDatabaseMutaction::Connect(connect)));
+}
+
+pub fn nested_disconnects<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_users_with_posts(db);
+
+    let disconnect = |model: &str, field: &str, child: Option<(&str, &str)>| {
+        let disconnect = NestedDisconnect {
+            relation_field: db.relation_field(model, field),
+            where_: child.map(|(model, id)| db.selector(model, id)),
+        };
+
+        NestedDatabaseMutaction::Disconnect(disconnect)
+    };
+
+    db.execute_nested("u1", disconnect("User", "posts", Some(("Post", "p1"))))
+        .unwrap();
+
+    assert_eq!(graphql_id_pairs(&[("u1", "p2")]), posts_of(db, "u1"));
+    assert_nodes_not_connected(
+        "UserPosts",
+        db.execute_nested("u1", disconnect("User", "posts", Some(("Post", "p1")))),
+    );
+
+    // Without a selector, the one related record of a to-one relation is disconnected.
+    db.execute_nested("p2", disconnect("Post", "author", None)).unwrap();
+    assert_eq!(graphql_id_pairs(&[]), posts_of(db, "u1"));
+
+    // A comment requires its post.
+    let comment = nested_create(db, "Post", "comments", args("c1", vec![("text", "Hi".into())]), false);
+    db.execute_nested("p3", NestedDatabaseMutaction::CreateNode(comment))
+        .unwrap();
+
+    assert_relation_violation(
+        "PostComments",
+        db.execute_nested("p3", disconnect("Post", "comments", Some(("Comment", "c1")))),
+    );
+}
+
+pub fn nested_sets<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_users_with_posts(db);
+
+    let set = |ids: &[&str]| {
+        let set = NestedSet {
+            relation_field: db.relation_field("User", "posts"),
+            wheres: ids.iter().map(|id| db.selector("Post", id)).collect(),
+        };
+
+        NestedDatabaseMutaction::Set(set)
+    };
+
+    db.create("Post", "p4", vec![("title", "p4".into())]);
+    db.execute_nested("u1", set(&["p2", "p4"])).unwrap();
+
+    assert_eq!(graphql_id_pairs(&[("u1", "p2"), ("u1", "p4")]), posts_of(db, "u1"));
+    assert_eq!(graphql_id_pairs(&[("u2", "p3")]), posts_of(db, "u2"));
+
+    db.execute_nested("u1", set(&[])).unwrap();
+    assert_eq!(graphql_id_pairs(&[]), posts_of(db, "u1"));
+}
+
+pub fn nested_updates<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_users_with_posts(db);
+
+    let title = |title: &str| {
+        let mut values = PrismaArgs::new();
+        values.insert("title", title);
+        values
+    };
+
+    let update = nested_update(db, "User", "posts", Some(db.selector("Post", "p1")), title("Updated"));
+    db.execute_nested("u1", NestedDatabaseMutaction::UpdateNode(update))
+        .unwrap();
+
+    assert_eq!(PrismaValue::from("Updated"), db.value("Post", "p1", "title"));
+
+    let update = nested_update(db, "User", "posts", Some(db.selector("Post", "p3")), title("Updated"));
+    assert_nodes_not_connected(
+        "UserPosts",
+        db.execute_nested("u1", NestedDatabaseMutaction::UpdateNode(update)),
+    );
+
+    assert_eq!(PrismaValue::from("p3"), db.value("Post", "p3", "title"));
+
+    let mut published = PrismaArgs::new();
+    published.insert("published", true);
+
+    let update_many = NestedUpdateNodes {
+        relation_field: db.relation_field("User", "posts"),
+        filter: None,
+        non_list_args: published,
+        list_args: Vec::new(),
+    };
+
+    let mut results = db
+        .execute_nested("u1", NestedDatabaseMutaction::UpdateNodes(update_many))
+        .unwrap();
+
+    assert_eq!(2, results.pop().unwrap().count());
+    assert_eq!(PrismaValue::from(true), db.value("Post", "p2", "published"));
+    assert_eq!(PrismaValue::Null, db.value("Post", "p3", "published"));
+}
+
+pub fn nested_upserts<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_users_with_posts(db);
+
+    let upsert = |id: &str| {
+        let where_ = Some(db.selector("Post", id));
+        let mut title = PrismaArgs::new();
+        title.insert("title", "Upserted");
+
+        let upsert = NestedUpsertNode {
+            relation_field: db.relation_field("User", "posts"),
+            where_: where_.clone(),
+            create: nested_create(db, "User", "posts", args(id, vec![("title", "Created".into())]), false),
+            update: nested_update(db, "User", "posts", where_, title),
+        };
+
+        NestedDatabaseMutaction::UpsertNode(upsert)
+    };
+
+    db.execute_nested("u1", upsert("p1")).unwrap();
+    db.execute_nested("u1", upsert("p9")).unwrap();
+
+    assert_eq!(PrismaValue::from("Upserted"), db.value("Post", "p1", "title"));
+    assert_eq!(PrismaValue::from("Created"), db.value("Post", "p9", "title"));
+    assert_eq!(
+        graphql_id_pairs(&[("u1", "p1"), ("u1", "p2"), ("u1", "p9")]),
+        posts_of(db, "u1")
+    );
+}
+
+pub fn nested_deletes<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_users_with_posts(db);
+
+    let comment = nested_create(db, "Post", "comments", args("c1", vec![("text", "Hi".into())]), false);
+    db.execute_nested("p2", NestedDatabaseMutaction::CreateNode(comment))
+        .unwrap();
+
+    let delete = |id: &str| {
+        let delete = NestedDeleteNode {
+            relation_field: db.relation_field("User", "posts"),
+            where_: Some(db.selector("Post", id)),
+        };
+
+        NestedDatabaseMutaction::DeleteNode(delete)
+    };
+
+    db.execute_nested("u1", delete("p1")).unwrap();
+    assert!(!db.exists("Post", "p1"));
+
+    assert_nodes_not_connected("UserPosts", db.execute_nested("u1", delete("p3")));
+    assert!(db.exists("Post", "p3"));
+
+    // The post still has a comment requiring it.
+    assert_relation_violation("PostComments", db.execute_nested("u1", delete("p2")));
+    assert!(db.exists("Post", "p2"));
+
+    let delete_comments = NestedDeleteNodes {
+        relation_field: db.relation_field("Post", "comments"),
+        filter: None,
+    };
+
+    let mut results = db
+        .execute_nested("p2", NestedDatabaseMutaction::DeleteNodes(delete_comments))
+        .unwrap();
+
+    assert_eq!(1, results.pop().unwrap().count());
+    assert_eq!(0, db.count("Comment"));
+
+    db.execute_nested("u1", delete("p2")).unwrap();
+    assert_eq!(graphql_ids(&["p3"]), db.ids("Post", QueryArguments::default()));
+}
+
+pub fn nested_mutactions_without_parent<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_users_with_posts(db);
+
+    let delete = NestedDeleteNode {
+        relation_field: db.relation_field("User", "posts"),
+        where_: Some(db.selector("Post", "p1")),
+    };
+
+    let mutaction = DatabaseMutaction::Nested(NestedDatabaseMutaction::DeleteNode(delete));
+    let result = db
+        .connector()
+        .execute_with_nested(db.db_name().to_string(), mutaction, None);
+
+    match expect_error(result) {
+        ConnectorError::MissingParentId => (),
+        e => panic!("Expected a missing parent, got: {}", e),
+    }
+
+    assert!(db.exists("Post", "p1"));
+}
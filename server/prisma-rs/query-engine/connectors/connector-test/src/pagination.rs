@@ -0,0 +1,131 @@
+//! Pagination with `first`, `last` and `skip`, on a model and on related records.
+//!
+//! Connectors read one record more than requested with `first` and `last`, to tell if there's
+//! more data, and return the records of `last` in reverse order.
+use crate::{graphql_id_pairs, graphql_ids, ConnectorUnderTest, TestDatabase};
+use connector::{QueryArguments, ScalarCompare};
+use prisma_models::*;
+
+fn create_posts<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    for id in &["p1", "p2", "p3", "p4", "p5", "p6"] {
+        db.create("Post", id, vec![("title", PrismaValue::from(*id))]);
+    }
+}
+
+fn paginated(first: Option<u32>, last: Option<u32>, skip: Option<u32>) -> QueryArguments {
+    QueryArguments {
+        first,
+        last,
+        skip,
+        ..Default::default()
+    }
+}
+
+pub fn pagination<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_posts(db);
+
+    let posts = |query_arguments| db.ids("Post", query_arguments);
+
+    assert_eq!(graphql_ids(&["p1", "p2", "p3"]), posts(paginated(Some(2), None, None)));
+    assert_eq!(
+        graphql_ids(&["p3", "p4", "p5"]),
+        posts(paginated(Some(2), None, Some(2)))
+    );
+    assert_eq!(graphql_ids(&["p6", "p5", "p4"]), posts(paginated(None, Some(2), None)));
+    assert_eq!(
+        graphql_ids(&["p4", "p3", "p2"]),
+        posts(paginated(None, Some(2), Some(2)))
+    );
+    assert_eq!(
+        graphql_ids(&["p2", "p3", "p4", "p5", "p6"]),
+        posts(paginated(None, None, Some(1)))
+    );
+}
+
+pub fn pagination_edge_cases<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_posts(db);
+
+    let posts = |query_arguments| db.ids("Post", query_arguments);
+
+    // Even with nothing requested, the extra record tells there's more.
+    assert_eq!(graphql_ids(&["p1"]), posts(paginated(Some(0), None, None)));
+    assert_eq!(graphql_ids(&["p6"]), posts(paginated(Some(2), None, Some(5))));
+    assert_eq!(graphql_ids(&["p1"]), posts(paginated(None, Some(3), Some(5))));
+    assert_eq!(graphql_ids(&[]), posts(paginated(Some(2), None, Some(6))));
+    assert_eq!(graphql_ids(&[]), posts(paginated(None, None, Some(10))));
+}
+
+pub fn related_pagination<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_posts(db);
+
+    for id in &["u1", "u2", "u3"] {
+        db.create("User", id, vec![("name", PrismaValue::from(*id))]);
+    }
+
+    for post in &["p1", "p2", "p3"] {
+        db.connect("User", "posts", "u1", post);
+    }
+
+    for post in &["p4", "p5"] {
+        db.connect("User", "posts", "u2", post);
+    }
+
+    let parents = &["u1", "u2", "u3"];
+    let all = db.related_ids("User", "posts", parents, QueryArguments::default());
+
+    let expected = graphql_id_pairs(&[("u1", "p1"), ("u1", "p2"), ("u1", "p3"), ("u2", "p4"), ("u2", "p5")]);
+    assert_eq!(expected, all);
+
+    // Pages are per parent. Rows of different parents come in no particular order.
+    let page = |parent: &str, query_arguments| -> Vec<GraphqlId> {
+        db.related_ids("User", "posts", parents, query_arguments)
+            .into_iter()
+            .filter(|(parent_id, _)| parent_id == &GraphqlId::from(parent))
+            .map(|(_, id)| id)
+            .collect()
+    };
+
+    assert_eq!(graphql_ids(&["p1", "p2"]), page("u1", paginated(Some(1), None, None)));
+    assert_eq!(graphql_ids(&["p4", "p5"]), page("u2", paginated(Some(1), None, None)));
+    assert_eq!(
+        graphql_ids(&["p2", "p3"]),
+        page("u1", paginated(Some(1), None, Some(1)))
+    );
+    assert_eq!(graphql_ids(&["p5"]), page("u2", paginated(Some(1), None, Some(1))));
+    assert_eq!(graphql_ids(&["p3", "p2"]), page("u1", paginated(None, Some(1), None)));
+    assert_eq!(graphql_ids(&["p5", "p4"]), page("u2", paginated(None, Some(1), None)));
+    assert_eq!(graphql_ids(&[]), page("u3", paginated(Some(1), None, None)));
+}
+
+/// Connections count the related records of all parents at once, leaving out the pagination.
+pub fn related_counts<C: ConnectorUnderTest>(db: &TestDatabase<C>) {
+    create_posts(db);
+
+    for id in &["u1", "u2", "u3"] {
+        db.create("User", id, vec![("name", PrismaValue::from(*id))]);
+    }
+
+    for post in &["p1", "p2", "p3"] {
+        db.connect("User", "posts", "u1", post);
+    }
+
+    for post in &["p4", "p5"] {
+        db.connect("User", "posts", "u2", post);
+    }
+
+    let parents = &["u1", "u2", "u3"];
+    let counts = |query_arguments| db.related_counts("User", "posts", parents, query_arguments);
+
+    assert_eq!(
+        vec![(GraphqlId::from("u1"), 3), (GraphqlId::from("u2"), 2)],
+        counts(QueryArguments::default())
+    );
+
+    let title = db.scalar_field("Post", "title");
+    let filtered = QueryArguments::from(title.not_equals("p1"));
+
+    assert_eq!(
+        vec![(GraphqlId::from("u1"), 2), (GraphqlId::from("u2"), 2)],
+        counts(filtered)
+    );
+}
@@ -0,0 +1,309 @@
+use crate::fixtures;
+use connector::{error::ConnectorError, filter::NodeSelector, mutaction::*, *};
+use prisma_models::*;
+use std::sync::Arc;
+
+/// A connector the cases can run against.
+pub trait ConnectorUnderTest: DataResolver + DatabaseMutactionExecutor {}
+
+impl<T> ConnectorUnderTest for T where T: DataResolver + DatabaseMutactionExecutor {}
+
+/// A database of the connector under test with the schema of the fixture, and shortcuts to
+/// write and read its records.
+pub struct TestDatabase<'a, C> {
+    connector: &'a C,
+    project: ProjectRef,
+}
+
+impl<'a, C> TestDatabase<'a, C>
+where
+    C: ConnectorUnderTest,
+{
+    /// Runs a case in a database of its own, named after the case, once `migrate` created the
+    /// tables of the fixture in it.
+    pub fn run<M, F>(connector: &'a C, name: &str, migrate: M, case: F)
+    where
+        M: FnOnce(&C, &Schema),
+        F: FnOnce(&TestDatabase<'a, C>),
+    {
+        let db = Self::open(connector, name);
+
+        migrate(connector, db.project.schema());
+        db.reset();
+
+        case(&db);
+    }
+
+    /// Opens the database with the given name, removing all records left from earlier runs.
+    pub fn new(connector: &'a C, db_name: &str) -> Self {
+        let db = Self::open(connector, db_name);
+
+        db.reset();
+        db
+    }
+
+    fn open(connector: &'a C, db_name: &str) -> Self {
+        let template = ProjectTemplate {
+            id: db_name.to_string(),
+            schema: fixtures::blog(),
+            manifestation: ProjectManifestation::default(),
+            revision: Revision::default(),
+        };
+
+        TestDatabase {
+            connector,
+            project: template.into(),
+        }
+    }
+
+    /// Removes all records.
+    pub fn reset(&self) {
+        let project = Arc::clone(&self.project);
+        self.execute(TopLevelDatabaseMutaction::ResetData(ResetData { project }))
+            .unwrap();
+    }
+
+    pub fn connector(&self) -> &C {
+        self.connector
+    }
+
+    pub fn db_name(&self) -> &str {
+        &self.project.schema().db_name
+    }
+
+    pub fn model(&self, name: &str) -> ModelRef {
+        self.project.schema().find_model(name).unwrap()
+    }
+
+    pub fn scalar_field(&self, model: &str, field: &str) -> Arc<ScalarField> {
+        self.model(model).fields().find_from_scalar(field).unwrap()
+    }
+
+    pub fn relation_field(&self, model: &str, field: &str) -> Arc<RelationField> {
+        self.model(model).fields().find_from_relation_fields(field).unwrap()
+    }
+
+    /// Selects the record of the model with the given id.
+    pub fn selector(&self, model: &str, id: &str) -> NodeSelector {
+        NodeSelector::from((self.model(model).fields().id(), GraphqlId::from(id)))
+    }
+
+    /// Executes the mutaction with its nested mutactions, like the query engine does.
+    pub fn execute(&self, mutaction: TopLevelDatabaseMutaction) -> ConnectorResult<DatabaseMutactionResults> {
+        self.connector
+            .execute_with_nested(self.db_name().to_string(), DatabaseMutaction::TopLevel(mutaction), None)
+    }
+
+    pub fn execute_nested(
+        &self,
+        parent_id: &str,
+        mutaction: NestedDatabaseMutaction,
+    ) -> ConnectorResult<DatabaseMutactionResults> {
+        self.connector.execute_with_nested(
+            self.db_name().to_string(),
+            DatabaseMutaction::Nested(mutaction),
+            Some(GraphqlId::from(parent_id)),
+        )
+    }
+
+    /// Creates a record with the given id and scalar values.
+    pub fn create(&self, model: &str, id: &str, values: Vec<(&str, PrismaValue)>) {
+        self.create_with_lists(model, id, values, Vec::new());
+    }
+
+    pub fn create_with_lists(
+        &self,
+        model: &str,
+        id: &str,
+        values: Vec<(&str, PrismaValue)>,
+        lists: Vec<(&str, PrismaListValue)>,
+    ) {
+        let mut non_list_args = PrismaArgs::new();
+        non_list_args.insert("id", GraphqlId::from(id));
+
+        for (name, value) in values {
+            non_list_args.insert(name, value);
+        }
+
+        let create = CreateNode {
+            model: self.model(model),
+            non_list_args,
+            list_args: lists.into_iter().map(|(name, list)| (name.to_string(), list)).collect(),
+            nested_mutactions: NestedMutactions::default(),
+        };
+
+        self.execute(TopLevelDatabaseMutaction::CreateNode(create)).unwrap();
+    }
+
+    /// Connects the child to the parent through the relation field of the parent.
+    pub fn connect(&self, model: &str, field: &str, parent_id: &str, child_id: &str) {
+        let relation_field = self.relation_field(model, field);
+        let where_ = self.selector(&relation_field.related_model().name, child_id);
+
+        let connect = NestedConnect {
+            relation_field,
+            where_,
+            top_is_create: false,
+        };
+
+        self.execute_nested(parent_id, NestedDatabaseMutaction::Connect(connect))
+            .unwrap();
+    }
+
+    /// The ids of the records selected by the arguments, in the order of the connector.
+    pub fn ids(&self, model: &str, query_arguments: QueryArguments) -> Vec<GraphqlId> {
+        let model = self.model(model);
+        let selected_fields = SelectedFields::from(Arc::clone(&model));
+
+        self.connector
+            .get_nodes(Arc::clone(&model), query_arguments, &selected_fields)
+            .unwrap()
+            .get_id_values(model)
+            .unwrap()
+    }
+
+    /// The ids of the records related to the parents through the field, as pairs of parent
+    /// and child ids.
+    pub fn related_ids(
+        &self,
+        model: &str,
+        field: &str,
+        parent_ids: &[&str],
+        query_arguments: QueryArguments,
+    ) -> Vec<(GraphqlId, GraphqlId)> {
+        let from_field = self.relation_field(model, field);
+        let related_model = from_field.related_model();
+        let parent_ids: Vec<GraphqlId> = parent_ids.iter().map(|id| GraphqlId::from(*id)).collect();
+
+        let fields = related_model
+            .fields()
+            .scalar_non_list()
+            .into_iter()
+            .map(SelectedField::from)
+            .collect();
+
+        let selected_fields = SelectedFields::new(fields, Some(Arc::clone(&from_field)));
+
+        let nodes = self
+            .connector
+            .get_related_nodes(from_field, &parent_ids, query_arguments, &selected_fields)
+            .unwrap();
+
+        nodes
+            .nodes
+            .iter()
+            .map(|node| {
+                let id = node
+                    .get_id_value(&nodes.field_names, Arc::clone(&related_model))
+                    .unwrap();
+                (node.parent_id.clone().unwrap(), id.clone())
+            })
+            .collect()
+    }
+
+    /// The number of records related to each of the parents through the field, ordered by parent id.
+    pub fn related_counts(
+        &self,
+        model: &str,
+        field: &str,
+        parent_ids: &[&str],
+        query_arguments: QueryArguments,
+    ) -> Vec<(GraphqlId, usize)> {
+        let parent_ids: Vec<GraphqlId> = parent_ids.iter().map(|id| GraphqlId::from(*id)).collect();
+
+        let mut counts: Vec<(GraphqlId, usize)> = self
+            .connector
+            .count_related_nodes(self.relation_field(model, field), &parent_ids, query_arguments)
+            .unwrap()
+            .into_iter()
+            .map(|related| (related.parent_id, related.count))
+            .collect();
+
+        counts.sort_by(|a, b| format!("{:?}", a.0).cmp(&format!("{:?}", b.0)));
+        counts
+    }
+
+    pub fn count(&self, model: &str) -> usize {
+        self.connector
+            .count_by_model(self.model(model), QueryArguments::default())
+            .unwrap()
+    }
+
+    /// The number of rows in a table, such as the table of a relation or a scalar list.
+    pub fn count_table(&self, table: &str) -> usize {
+        self.connector.count_by_table(self.db_name(), table).unwrap()
+    }
+
+    /// The value of a field of the record with the given id, which must exist.
+    pub fn value(&self, model: &str, id: &str, field: &str) -> PrismaValue {
+        let selected_fields = SelectedFields::from(self.model(model));
+
+        self.connector
+            .get_node_by_where(&self.selector(model, id), &selected_fields)
+            .unwrap()
+            .expect("The record does not exist")
+            .get_field_value(field)
+            .unwrap()
+            .clone()
+    }
+
+    pub fn exists(&self, model: &str, id: &str) -> bool {
+        let selected_fields = SelectedFields::id(self.model(model));
+
+        self.connector
+            .get_node_by_where(&self.selector(model, id), &selected_fields)
+            .unwrap()
+            .is_some()
+    }
+
+    pub fn list_values(&self, model: &str, field: &str, id: &str) -> Vec<PrismaValue> {
+        let values = self
+            .connector
+            .get_scalar_list_values_by_node_ids(self.scalar_field(model, field), vec![GraphqlId::from(id)])
+            .unwrap();
+
+        values.into_iter().next().map(|v| v.values).unwrap_or_default()
+    }
+}
+
+/// The ids with the given values, to compare results to.
+pub fn graphql_ids(ids: &[&str]) -> Vec<GraphqlId> {
+    ids.iter().map(|id| GraphqlId::from(*id)).collect()
+}
+
+/// Pairs of parent and child ids with the given values.
+pub fn graphql_id_pairs(pairs: &[(&str, &str)]) -> Vec<(GraphqlId, GraphqlId)> {
+    pairs
+        .iter()
+        .map(|(parent, child)| (GraphqlId::from(*parent), GraphqlId::from(*child)))
+        .collect()
+}
+
+/// The error of a mutaction or query that is expected to fail.
+pub fn expect_error<T>(result: ConnectorResult<T>) -> ConnectorError {
+    match result {
+        Ok(_) => panic!("Expected an error, but the connector succeeded."),
+        Err(e) => e,
+    }
+}
+
+pub fn assert_relation_violation<T>(relation: &str, result: ConnectorResult<T>) {
+    match expect_error(result) {
+        ConnectorError::RelationViolation { ref relation_name, .. } if relation_name == relation => (),
+        e => panic!("Expected a violation of the relation {}, got: {}", relation, e),
+    }
+}
+
+pub fn assert_nodes_not_connected<T>(relation: &str, result: ConnectorResult<T>) {
+    match expect_error(result) {
+        ConnectorError::NodesNotConnected { ref relation_name, .. } if relation_name == relation => (),
+        e => panic!("Expected unconnected nodes in the relation {}, got: {}", relation, e),
+    }
+}
+
+pub fn assert_node_not_found<T>(result: ConnectorResult<T>) {
+    match expect_error(result) {
+        ConnectorError::NodeNotFoundForWhere(_) => (),
+        e => panic!("Expected a missing node, got: {}", e),
+    }
+}
@@ -0,0 +1,130 @@
+//! Aggregations over the records of a model, optionally grouped by scalar fields
+
+use crate::filter::ScalarCondition;
+use prisma_models::{PrismaValue, ScalarFieldRef, TypeIdentifier};
+
+#[derive(Debug, Clone)]
+pub enum Aggregation {
+    /// The number of records
+    Count,
+    Sum(ScalarFieldRef),
+    Avg(ScalarFieldRef),
+    Min(ScalarFieldRef),
+    Max(ScalarFieldRef),
+}
+
+impl Aggregation {
+    /// The aggregated field, if any.
+    pub fn field(&self) -> Option<&ScalarFieldRef> {
+        match self {
+            Aggregation::Count => None,
+            Aggregation::Sum(field) | Aggregation::Avg(field) | Aggregation::Min(field) | Aggregation::Max(field) => {
+                Some(field)
+            }
+        }
+    }
+
+    /// The type of the aggregated value. Counts are integers, averages floats,
+    /// and the other aggregations keep the type of their field.
+    pub fn type_identifier(&self) -> TypeIdentifier {
+        match self {
+            Aggregation::Count => TypeIdentifier::Int,
+            Aggregation::Avg(_) => TypeIdentifier::Float,
+            Aggregation::Sum(field) | Aggregation::Min(field) | Aggregation::Max(field) => field.type_identifier,
+        }
+    }
+
+    /// Sums and averages are defined for numeric fields, minimums and maximums
+    /// additionally for `DateTime` fields.
+    pub fn is_supported(&self) -> bool {
+        let numeric = |field: &ScalarFieldRef| match field.type_identifier {
+            TypeIdentifier::Int | TypeIdentifier::Float => !field.is_list,
+            _ => false,
+        };
+
+        match self {
+            Aggregation::Count => true,
+            Aggregation::Sum(field) | Aggregation::Avg(field) => numeric(field),
+            Aggregation::Min(field) | Aggregation::Max(field) => {
+                numeric(field) || (field.type_identifier == TypeIdentifier::DateTime && !field.is_list)
+            }
+        }
+    }
+}
+
+impl PartialEq for Aggregation {
+    fn eq(&self, other: &Aggregation) -> bool {
+        let same_field = |a: &ScalarFieldRef, b: &ScalarFieldRef| a.name == b.name;
+
+        match (self, other) {
+            (Aggregation::Count, Aggregation::Count) => true,
+            (Aggregation::Sum(a), Aggregation::Sum(b)) => same_field(a, b),
+            (Aggregation::Avg(a), Aggregation::Avg(b)) => same_field(a, b),
+            (Aggregation::Min(a), Aggregation::Min(b)) => same_field(a, b),
+            (Aggregation::Max(a), Aggregation::Max(b)) => same_field(a, b),
+            _ => false,
+        }
+    }
+}
+
+/// A condition on an aggregation a group has to meet.
+#[derive(Debug, Clone)]
+pub struct AggregationFilter {
+    pub aggregation: Aggregation,
+    pub condition: ScalarCondition,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AggregationSelection {
+    pub aggregations: Vec<Aggregation>,
+
+    /// Records are aggregated per distinct combination of values of these fields,
+    /// or all together if empty
+    pub group_by: Vec<ScalarFieldRef>,
+
+    /// Conditions on the groups, combined with `AND`
+    pub having: Vec<AggregationFilter>,
+}
+
+impl AggregationSelection {
+    /// Selects the aggregation, unless it's selected already, and returns its position in the results.
+    pub fn add(&mut self, aggregation: Aggregation) -> usize {
+        match self.aggregations.iter().position(|selected| selected == &aggregation) {
+            Some(position) => position,
+            None => {
+                self.aggregations.push(aggregation);
+                self.aggregations.len() - 1
+            }
+        }
+    }
+
+    /// All fields that are grouped by or aggregated, including those of the `having` conditions.
+    pub fn fields(&self) -> Vec<ScalarFieldRef> {
+        let aggregated = self
+            .aggregations
+            .iter()
+            .chain(self.having.iter().map(|filter| &filter.aggregation))
+            .filter_map(Aggregation::field);
+
+        self.group_by
+            .iter()
+            .chain(aggregated)
+            .fold(Vec::new(), |mut fields, field| {
+                if fields.iter().all(|f: &ScalarFieldRef| f.name != field.name) {
+                    fields.push(field.clone());
+                }
+
+                fields
+            })
+    }
+}
+
+/// The aggregated values of one group, or of all records if not grouped.
+#[derive(Debug, Clone)]
+pub struct AggregationRow {
+    /// The values of the `group_by` fields, in order
+    pub group: Vec<PrismaValue>,
+
+    /// The values of the aggregations, in order
+    pub values: Vec<PrismaValue>,
+}
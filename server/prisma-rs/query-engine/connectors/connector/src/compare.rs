@@ -50,6 +50,10 @@ pub trait ScalarCompare {
     fn greater_than_or_equals<T>(&self, val: T) -> Filter
     where
         T: Into<PrismaValue>;
+
+    fn search<T>(&self, query: T) -> Filter
+    where
+        T: Into<String>;
 }
 
 pub trait RelationCompare {
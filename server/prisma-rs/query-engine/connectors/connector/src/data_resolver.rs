@@ -1,4 +1,6 @@
-use crate::{filter::NodeSelector, query_arguments::QueryArguments, ConnectorResult};
+use crate::{
+    filter::NodeSelector, query_arguments::QueryArguments, AggregationRow, AggregationSelection, ConnectorResult,
+};
 use prisma_models::prelude::*;
 use prisma_models::ScalarFieldRef;
 
@@ -31,10 +33,34 @@ pub trait DataResolver {
     ) -> ConnectorResult<Vec<ScalarListValues>>;
 
     fn count_by_model(&self, model: ModelRef, query_arguments: QueryArguments) -> ConnectorResult<usize>;
+
+    /// Counts the records related to each of the parents in one query, leaving out the parents
+    /// without related records.
+    fn count_related_nodes(
+        &self,
+        from_field: RelationFieldRef,
+        from_node_ids: &[GraphqlId],
+        query_arguments: QueryArguments,
+    ) -> ConnectorResult<Vec<RelatedNodesCount>>;
+
     fn count_by_table(&self, database: &str, table: &str) -> ConnectorResult<usize>;
+
+    /// Aggregates the records matching the arguments, one row per group,
+    /// or a single row if the selection doesn't group.
+    fn aggregate(
+        &self,
+        model: ModelRef,
+        query_arguments: QueryArguments,
+        selection: AggregationSelection,
+    ) -> ConnectorResult<Vec<AggregationRow>>;
 }
 
 pub struct ScalarListValues {
     pub node_id: GraphqlId,
     pub values: Vec<PrismaValue>,
 }
+
+pub struct RelatedNodesCount {
+    pub parent_id: GraphqlId,
+    pub count: usize,
+}
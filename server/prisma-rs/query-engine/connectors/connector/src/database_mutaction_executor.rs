@@ -8,10 +8,21 @@ use serde_json::Value;
 pub trait DatabaseMutactionExecutor {
     fn execute_raw(&self, _query: String) -> ConnectorResult<Value>;
 
+    /// Executes the mutaction, leaving out the nested mutactions it carries. Callers running the
+    /// nested mutactions themselves, such as the native bridge, use this.
     fn execute(
         &self,
         db_name: String,
         mutaction: DatabaseMutaction,
         parent_id: Option<GraphqlId>, // TODO: we don't need this when we handle the whole mutaction in here.
     ) -> ConnectorResult<DatabaseMutactionResults>;
+
+    /// Executes the mutaction and all of its nested mutactions, recursively and in one
+    /// transaction. The result of the mutaction is the last one of the returned results.
+    fn execute_with_nested(
+        &self,
+        db_name: String,
+        mutaction: DatabaseMutaction,
+        parent_id: Option<GraphqlId>,
+    ) -> ConnectorResult<DatabaseMutactionResults>;
 }
@@ -71,6 +71,9 @@ pub enum ConnectorError {
     #[fail(display = "Node not found: {}", _0)]
     NodeNotFoundForWhere(NodeSelectorInfo),
 
+    #[fail(display = "A nested mutaction has no parent to be executed with.")]
+    MissingParentId,
+
     #[fail(
         display = "Violating a relation {} between {} and {}",
         relation_name, model_a_name, model_b_name
@@ -146,6 +149,72 @@ impl From<rusqlite::Error> for ConnectorError {
     }
 }
 
+#[cfg(feature = "postgresql")]
+impl From<postgres::Error> for ConnectorError {
+    fn from(e: postgres::Error) -> ConnectorError {
+        use postgres::error::UNIQUE_VIOLATION;
+
+        // The detail of a unique violation reads `Key (field)=(value) already exists.`
+        let unique_field = e
+            .as_db()
+            .filter(|db_error| db_error.code == UNIQUE_VIOLATION)
+            .and_then(|db_error| db_error.detail.as_ref())
+            .and_then(|detail| {
+                let start = detail.find("Key (")? + "Key (".len();
+                let end = detail[start..].find(")=")? + start;
+
+                Some(detail[start..end].trim_matches('"').to_string())
+            });
+
+        match unique_field {
+            Some(field_name) => ConnectorError::UniqueConstraintViolation { field_name },
+            None => ConnectorError::QueryError(e.into()),
+        }
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl From<mysql::Error> for ConnectorError {
+    fn from(e: mysql::Error) -> ConnectorError {
+        const DUPLICATE_ENTRY: u16 = 1062;
+        const PARENT_ROW_REFERENCED: u16 = 1451;
+        const NO_PARENT_ROW: u16 = 1452;
+
+        // The quoted identifiers of the message, like the key in
+        // ``Duplicate entry 'a' for key 'User.email_UNIQUE'`` or the tables and constraint in
+        // ``a foreign key constraint fails (`db`.`Post`, CONSTRAINT `fk` FOREIGN KEY ...)``.
+        fn quoted(message: &str, quote: char) -> Vec<&str> {
+            message.split(quote).skip(1).step_by(2).collect()
+        }
+
+        match e {
+            mysql::Error::MySqlError(ref error) if error.code == DUPLICATE_ENTRY => {
+                let key = quoted(&error.message, '\'').pop().unwrap_or("");
+                let key = key.rsplit('.').next().unwrap_or(key);
+
+                ConnectorError::UniqueConstraintViolation {
+                    field_name: key.trim_end_matches("_UNIQUE").to_string(),
+                }
+            }
+            // MySQL only knows the constraint and the tables, which the connector resolves to the
+            // relation of the schema.
+            mysql::Error::MySqlError(ref error)
+                if error.code == PARENT_ROW_REFERENCED || error.code == NO_PARENT_ROW =>
+            {
+                let identifiers = quoted(&error.message, '`');
+                let identifier = |i: usize| identifiers.get(i).map(|s| s.to_string()).unwrap_or_default();
+
+                ConnectorError::RelationViolation {
+                    relation_name: identifier(2),
+                    model_a_name: identifier(1),
+                    model_b_name: identifier(4),
+                }
+            }
+            e => ConnectorError::QueryError(e.into()),
+        }
+    }
+}
+
 impl From<uuid::parser::ParseError> for ConnectorError {
     fn from(e: uuid::parser::ParseError) -> ConnectorError {
         ConnectorError::ColumnReadFailure(e.into())
@@ -0,0 +1,24 @@
+use prisma_models::ScalarField;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// A condition on the value at a path in a JSON field.
+#[derive(Debug, Clone)]
+pub struct JsonFilter {
+    pub field: Arc<ScalarField>,
+
+    /// Object keys and array indices leading to the value, the whole document if empty
+    pub path: Vec<String>,
+    pub condition: JsonCondition,
+}
+
+#[derive(Debug, Clone)]
+pub enum JsonCondition {
+    Equals(Value),
+
+    /// The value is an array with the given element
+    ArrayContains(Value),
+
+    /// The value is a string containing the given string
+    StringContains(String),
+}
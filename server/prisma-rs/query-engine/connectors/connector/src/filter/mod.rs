@@ -4,10 +4,12 @@
 //! [ScalarCompare](/connector/trait.ScalarCompare.html) and
 //! [RelationCompare](/connector/trait.RelationCompare.html).
 
+mod json;
 mod node_selector;
 mod relation;
 mod scalar;
 
+pub use json::*;
 pub use node_selector::*;
 pub use relation::*;
 pub use scalar::*;
@@ -21,6 +23,7 @@ pub enum Filter {
     ScalarList(ScalarListFilter),
     OneRelationIsNull(OneRelationIsNullFilter),
     Relation(RelationFilter),
+    Json(JsonFilter),
     NodeSubscription,
     BoolFilter(bool),
 }
@@ -63,6 +66,12 @@ impl From<RelationFilter> for Filter {
     }
 }
 
+impl From<JsonFilter> for Filter {
+    fn from(jf: JsonFilter) -> Self {
+        Filter::Json(jf)
+    }
+}
+
 impl From<bool> for Filter {
     fn from(b: bool) -> Self {
         Filter::BoolFilter(b)
@@ -74,6 +83,7 @@ impl From<NodeSelector> for Filter {
         Filter::Scalar(ScalarFilter {
             field: node_selector.field,
             condition: ScalarCondition::Equals(node_selector.value),
+            mode: StringMode::Default,
         })
     }
 }
@@ -7,6 +7,22 @@ use std::sync::Arc;
 pub struct ScalarFilter {
     pub field: Arc<ScalarField>,
     pub condition: ScalarCondition,
+    pub mode: StringMode,
+}
+
+/// How string values are compared in a `ScalarFilter`. Ignored on non-string fields.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StringMode {
+    Default,
+
+    /// Compares strings ignoring their case
+    Insensitive,
+}
+
+impl Default for StringMode {
+    fn default() -> Self {
+        StringMode::Default
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -31,6 +47,9 @@ pub enum ScalarCondition {
     GreaterThanOrEquals(PrismaValue),
     In(Vec<PrismaValue>),
     NotIn(Vec<PrismaValue>),
+
+    /// Full-text search for records matching all words of the query
+    Search(String),
 }
 
 #[derive(Debug, Clone)]
@@ -57,7 +76,7 @@ impl ScalarCompare for Arc<ScalarField> {
     /// let filter = field.equals("foo");
     ///
     /// match filter {
-    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::Equals(val) }) => {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::Equals(val), .. }) => {
     ///         assert_eq!(PrismaValue::from("foo"), val);
     ///         assert_eq!(String::from("name"), field.name);
     ///     }
@@ -71,6 +90,7 @@ impl ScalarCompare for Arc<ScalarField> {
         Filter::from(ScalarFilter {
             field: Arc::clone(self),
             condition: ScalarCondition::Equals(val.into()),
+            mode: StringMode::Default,
         })
     }
 
@@ -90,7 +110,7 @@ impl ScalarCompare for Arc<ScalarField> {
     /// let filter = field.not_equals(false);
     ///
     /// match filter {
-    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::NotEquals(val) }) => {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::NotEquals(val), .. }) => {
     ///         assert_eq!(PrismaValue::from(false), val);
     ///         assert_eq!(String::from("name"), field.name);
     ///     }
@@ -104,6 +124,7 @@ impl ScalarCompare for Arc<ScalarField> {
         Filter::from(ScalarFilter {
             field: Arc::clone(self),
             condition: ScalarCondition::NotEquals(val.into()),
+            mode: StringMode::Default,
         })
     }
 
@@ -123,7 +144,7 @@ impl ScalarCompare for Arc<ScalarField> {
     /// let filter = field.contains("asdf");
     ///
     /// match filter {
-    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::Contains(val) }) => {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::Contains(val), .. }) => {
     ///         assert_eq!(PrismaValue::from("asdf"), val);
     ///         assert_eq!(String::from("name"), field.name);
     ///     }
@@ -137,6 +158,7 @@ impl ScalarCompare for Arc<ScalarField> {
         Filter::from(ScalarFilter {
             field: Arc::clone(self),
             condition: ScalarCondition::Contains(val.into()),
+            mode: StringMode::Default,
         })
     }
 
@@ -156,7 +178,7 @@ impl ScalarCompare for Arc<ScalarField> {
     /// let filter = field.not_contains("asdf");
     ///
     /// match filter {
-    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::NotContains(val) }) => {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::NotContains(val), .. }) => {
     ///         assert_eq!(PrismaValue::from("asdf"), val);
     ///         assert_eq!(String::from("name"), field.name);
     ///     }
@@ -170,6 +192,7 @@ impl ScalarCompare for Arc<ScalarField> {
         Filter::from(ScalarFilter {
             field: Arc::clone(self),
             condition: ScalarCondition::NotContains(val.into()),
+            mode: StringMode::Default,
         })
     }
 
@@ -189,7 +212,7 @@ impl ScalarCompare for Arc<ScalarField> {
     /// let filter = field.starts_with("qwert");
     ///
     /// match filter {
-    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::StartsWith(val) }) => {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::StartsWith(val), .. }) => {
     ///         assert_eq!(PrismaValue::from("qwert"), val);
     ///         assert_eq!(String::from("name"), field.name);
     ///     }
@@ -203,6 +226,7 @@ impl ScalarCompare for Arc<ScalarField> {
         Filter::from(ScalarFilter {
             field: Arc::clone(self),
             condition: ScalarCondition::StartsWith(val.into()),
+            mode: StringMode::Default,
         })
     }
 
@@ -222,7 +246,7 @@ impl ScalarCompare for Arc<ScalarField> {
     /// let filter = field.not_starts_with("qwert");
     ///
     /// match filter {
-    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::NotStartsWith(val) }) => {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::NotStartsWith(val), .. }) => {
     ///         assert_eq!(PrismaValue::from("qwert"), val);
     ///         assert_eq!(String::from("name"), field.name);
     ///     }
@@ -236,6 +260,7 @@ impl ScalarCompare for Arc<ScalarField> {
         Filter::from(ScalarFilter {
             field: Arc::clone(self),
             condition: ScalarCondition::NotStartsWith(val.into()),
+            mode: StringMode::Default,
         })
     }
 
@@ -255,7 +280,7 @@ impl ScalarCompare for Arc<ScalarField> {
     /// let filter = field.ends_with("musti");
     ///
     /// match filter {
-    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::EndsWith(val) }) => {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::EndsWith(val), .. }) => {
     ///         assert_eq!(PrismaValue::from("musti"), val);
     ///         assert_eq!(String::from("name"), field.name);
     ///     }
@@ -269,6 +294,7 @@ impl ScalarCompare for Arc<ScalarField> {
         Filter::from(ScalarFilter {
             field: Arc::clone(self),
             condition: ScalarCondition::EndsWith(val.into()),
+            mode: StringMode::Default,
         })
     }
 
@@ -288,7 +314,7 @@ impl ScalarCompare for Arc<ScalarField> {
     /// let filter = field.not_ends_with("naukio");
     ///
     /// match filter {
-    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::NotEndsWith(val) }) => {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::NotEndsWith(val), .. }) => {
     ///         assert_eq!(PrismaValue::from("naukio"), val);
     ///         assert_eq!(String::from("name"), field.name);
     ///     }
@@ -302,6 +328,7 @@ impl ScalarCompare for Arc<ScalarField> {
         Filter::from(ScalarFilter {
             field: Arc::clone(self),
             condition: ScalarCondition::NotEndsWith(val.into()),
+            mode: StringMode::Default,
         })
     }
 
@@ -321,7 +348,7 @@ impl ScalarCompare for Arc<ScalarField> {
     /// let filter = field.less_than(10);
     ///
     /// match filter {
-    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::LessThan(val) }) => {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::LessThan(val), .. }) => {
     ///         assert_eq!(PrismaValue::from(10), val);
     ///         assert_eq!(String::from("id"), field.name);
     ///     }
@@ -335,6 +362,7 @@ impl ScalarCompare for Arc<ScalarField> {
         Filter::from(ScalarFilter {
             field: Arc::clone(self),
             condition: ScalarCondition::LessThan(val.into()),
+            mode: StringMode::Default,
         })
     }
 
@@ -354,7 +382,7 @@ impl ScalarCompare for Arc<ScalarField> {
     /// let filter = field.less_than_or_equals(10);
     ///
     /// match filter {
-    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::LessThanOrEquals(val) }) => {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::LessThanOrEquals(val), .. }) => {
     ///         assert_eq!(PrismaValue::from(10), val);
     ///         assert_eq!(String::from("id"), field.name);
     ///     }
@@ -368,6 +396,7 @@ impl ScalarCompare for Arc<ScalarField> {
         Filter::from(ScalarFilter {
             field: Arc::clone(self),
             condition: ScalarCondition::LessThanOrEquals(val.into()),
+            mode: StringMode::Default,
         })
     }
 
@@ -387,7 +416,7 @@ impl ScalarCompare for Arc<ScalarField> {
     /// let filter = field.greater_than(10);
     ///
     /// match filter {
-    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::GreaterThan(val) }) => {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::GreaterThan(val), .. }) => {
     ///         assert_eq!(PrismaValue::from(10), val);
     ///         assert_eq!(String::from("id"), field.name);
     ///     }
@@ -401,6 +430,7 @@ impl ScalarCompare for Arc<ScalarField> {
         Filter::from(ScalarFilter {
             field: Arc::clone(self),
             condition: ScalarCondition::GreaterThan(val.into()),
+            mode: StringMode::Default,
         })
     }
 
@@ -420,7 +450,7 @@ impl ScalarCompare for Arc<ScalarField> {
     /// let filter = field.greater_than_or_equals(10);
     ///
     /// match filter {
-    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::GreaterThanOrEquals(val) }) => {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::GreaterThanOrEquals(val), .. }) => {
     ///         assert_eq!(PrismaValue::from(10), val);
     ///         assert_eq!(String::from("id"), field.name);
     ///     }
@@ -434,6 +464,41 @@ impl ScalarCompare for Arc<ScalarField> {
         Filter::from(ScalarFilter {
             field: Arc::clone(self),
             condition: ScalarCondition::GreaterThanOrEquals(val.into()),
+            mode: StringMode::Default,
+        })
+    }
+
+    /// Field matches the full-text search query.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("User").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("name").unwrap();
+    /// let filter = field.search("foo bar");
+    ///
+    /// match filter {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::Search(query), .. }) => {
+    ///         assert_eq!(String::from("foo bar"), query);
+    ///         assert_eq!(String::from("name"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn search<T>(&self, query: T) -> Filter
+    where
+        T: Into<String>,
+    {
+        Filter::from(ScalarFilter {
+            field: Arc::clone(self),
+            condition: ScalarCondition::Search(query.into()),
+            mode: StringMode::Default,
         })
     }
 }
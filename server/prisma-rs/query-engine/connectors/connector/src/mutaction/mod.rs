@@ -73,6 +73,40 @@ pub struct NestedMutactions {
     pub delete_manys: Vec<NestedDeleteNodes>,
 }
 
+impl NestedMutactions {
+    /// All nested mutactions in the order they should be executed.
+    pub fn mutactions(&self) -> Vec<NestedDatabaseMutaction> {
+        let mut result: Vec<NestedDatabaseMutaction> = vec![];
+
+        result.extend(self.creates.iter().cloned().map(NestedDatabaseMutaction::CreateNode));
+        result.extend(self.updates.iter().cloned().map(NestedDatabaseMutaction::UpdateNode));
+        result.extend(self.upserts.iter().cloned().map(NestedDatabaseMutaction::UpsertNode));
+        result.extend(self.deletes.iter().cloned().map(NestedDatabaseMutaction::DeleteNode));
+        result.extend(self.connects.iter().cloned().map(NestedDatabaseMutaction::Connect));
+        result.extend(
+            self.disconnects
+                .iter()
+                .cloned()
+                .map(NestedDatabaseMutaction::Disconnect),
+        );
+        result.extend(self.sets.iter().cloned().map(NestedDatabaseMutaction::Set));
+        result.extend(
+            self.update_manys
+                .iter()
+                .cloned()
+                .map(NestedDatabaseMutaction::UpdateNodes),
+        );
+        result.extend(
+            self.delete_manys
+                .iter()
+                .cloned()
+                .map(NestedDatabaseMutaction::DeleteNodes),
+        );
+
+        result
+    }
+}
+
 // SET
 
 #[derive(Debug, Clone)]
@@ -1,4 +1,7 @@
-use super::{DatabaseMutaction, DatabaseMutactionResultType};
+use super::{
+    DatabaseMutaction, DatabaseMutactionResultType, NestedDatabaseMutaction, NestedMutactions,
+    TopLevelDatabaseMutaction,
+};
 use prisma_models::prelude::{GraphqlId, SingleNode};
 
 #[derive(Default)]
@@ -42,6 +45,30 @@ impl DatabaseMutactionResult {
             _ => panic!("No node defined in DatabaseMutactionResult"),
         }
     }
+
+    /// The nested mutactions to execute with the id of this result as parent.
+    /// For upserts, the branch depends on whether a node was created or updated.
+    pub fn nested_mutactions(&self) -> Option<&NestedMutactions> {
+        match (&self.mutaction, self.typ) {
+            (DatabaseMutaction::TopLevel(TopLevelDatabaseMutaction::CreateNode(m)), _) => Some(&m.nested_mutactions),
+            (DatabaseMutaction::TopLevel(TopLevelDatabaseMutaction::UpdateNode(m)), _) => Some(&m.nested_mutactions),
+            (
+                DatabaseMutaction::TopLevel(TopLevelDatabaseMutaction::UpsertNode(m)),
+                DatabaseMutactionResultType::Create,
+            ) => Some(&m.create.nested_mutactions),
+            (DatabaseMutaction::TopLevel(TopLevelDatabaseMutaction::UpsertNode(m)), _) => {
+                Some(&m.update.nested_mutactions)
+            }
+            (DatabaseMutaction::Nested(NestedDatabaseMutaction::CreateNode(m)), _) => Some(&m.nested_mutactions),
+            (DatabaseMutaction::Nested(NestedDatabaseMutaction::UpdateNode(m)), _) => Some(&m.nested_mutactions),
+            (
+                DatabaseMutaction::Nested(NestedDatabaseMutaction::UpsertNode(m)),
+                DatabaseMutactionResultType::Create,
+            ) => Some(&m.create.nested_mutactions),
+            (DatabaseMutaction::Nested(NestedDatabaseMutaction::UpsertNode(m)), _) => Some(&m.update.nested_mutactions),
+            _ => None,
+        }
+    }
 }
 
 impl DatabaseMutactionResults {
@@ -65,3 +92,13 @@ impl DatabaseMutactionResults {
         }
     }
 }
+
+impl IntoIterator for DatabaseMutactionResults {
+    type Item = DatabaseMutactionResult;
+    type IntoIter = std::vec::IntoIter<DatabaseMutactionResult>;
+
+    /// The results in the order they were pushed.
+    fn into_iter(self) -> Self::IntoIter {
+        self.results.into_iter()
+    }
+}
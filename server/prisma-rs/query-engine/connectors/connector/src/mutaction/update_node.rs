@@ -6,14 +6,35 @@ pub trait SharedUpdateLogic {
     fn model(&self) -> ModelRef;
     fn non_list_args(&self) -> &PrismaArgs;
     fn list_args(&self) -> &[(String, PrismaListValue)];
+    fn json_updates(&self) -> &[JsonUpdate];
     fn nested_mutactions(&self) -> &NestedMutactions;
 }
 
+/// A partial update of a JSON field, applied after the scalar arguments.
+#[derive(Debug, Clone)]
+pub struct JsonUpdate {
+    pub field: Arc<ScalarField>,
+    pub operation: JsonOperation,
+}
+
+#[derive(Debug, Clone)]
+pub enum JsonOperation {
+    /// Sets the value at the path, adding missing object keys on the way
+    SetPath {
+        path: Vec<String>,
+        value: serde_json::Value,
+    },
+
+    /// Merges the object into the document as a JSON merge patch
+    Merge(serde_json::Value),
+}
+
 #[derive(Debug, Clone)]
 pub struct UpdateNode {
     pub where_: NodeSelector,
     pub non_list_args: PrismaArgs,
     pub list_args: Vec<(String, PrismaListValue)>,
+    pub json_updates: Vec<JsonUpdate>,
     pub nested_mutactions: NestedMutactions,
 }
 
@@ -30,6 +51,10 @@ impl SharedUpdateLogic for UpdateNode {
         self.list_args.as_slice()
     }
 
+    fn json_updates(&self) -> &[JsonUpdate] {
+        self.json_updates.as_slice()
+    }
+
     fn nested_mutactions(&self) -> &NestedMutactions {
         &self.nested_mutactions
     }
@@ -41,6 +66,7 @@ pub struct NestedUpdateNode {
     pub where_: Option<NodeSelector>,
     pub non_list_args: PrismaArgs,
     pub list_args: Vec<(String, PrismaListValue)>,
+    pub json_updates: Vec<JsonUpdate>,
     pub nested_mutactions: NestedMutactions,
 }
 
@@ -57,6 +83,10 @@ impl SharedUpdateLogic for NestedUpdateNode {
         self.list_args.as_slice()
     }
 
+    fn json_updates(&self) -> &[JsonUpdate] {
+        self.json_updates.as_slice()
+    }
+
     fn nested_mutactions(&self) -> &NestedMutactions {
         &self.nested_mutactions
     }
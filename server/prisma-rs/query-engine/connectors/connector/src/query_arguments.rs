@@ -9,7 +9,10 @@ pub struct QueryArguments {
     pub before: Option<GraphqlId>,
     pub last: Option<u32>,
     pub filter: Option<Filter>,
-    pub order_by: Option<OrderBy>,
+    /// Sort keys in order of precedence, with the id as implicit last key
+    pub order_by: Vec<OrderBy>,
+    /// Only the first record in order is returned of records with equal values in these fields
+    pub distinct: Vec<ScalarFieldRef>,
 }
 
 impl QueryArguments {
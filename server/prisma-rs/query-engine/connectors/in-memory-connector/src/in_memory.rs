@@ -0,0 +1,74 @@
+mod aggregation;
+mod database;
+mod filter;
+mod json;
+mod mutaction_executor;
+mod nested_actions;
+mod query;
+mod read;
+mod resolver;
+mod search;
+mod value;
+mod write;
+
+use connector::ConnectorResult;
+use database::Database;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+
+/// Holds the data of every Prisma database in memory, until the connector is dropped.
+///
+/// ```rust
+/// # use connector::*;
+/// # use in_memory_connector::*;
+/// # use prisma_models::*;
+/// # use std::fs::File;
+/// #
+/// # let template: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+/// let schema = template.build(String::from("test"));
+/// let model = schema.find_model("User").unwrap();
+///
+/// let in_memory = InMemory::new();
+/// let count = in_memory.count_by_model(model, QueryArguments::default()).unwrap();
+///
+/// assert_eq!(0, count);
+/// ```
+#[derive(Default)]
+pub struct InMemory {
+    databases: RwLock<HashMap<String, Database>>,
+}
+
+impl InMemory {
+    pub fn new() -> InMemory {
+        InMemory::default()
+    }
+
+    /// Reads from the given database, which is empty until written to.
+    pub(crate) fn with_database<F, T>(&self, db_name: &str, f: F) -> ConnectorResult<T>
+    where
+        F: FnOnce(&Database) -> ConnectorResult<T>,
+    {
+        let databases = self.databases.read();
+
+        match databases.get(db_name) {
+            Some(database) => f(database),
+            None => f(&Database::default()),
+        }
+    }
+
+    /// Writes to a copy of the given database, which replaces the database only if `F` was
+    /// successful. Writes are serialized, and a failing mutaction leaves no trace, like a rolled
+    /// back transaction.
+    pub(crate) fn with_transaction<F, T>(&self, db_name: &str, f: F) -> ConnectorResult<T>
+    where
+        F: FnOnce(&mut Database) -> ConnectorResult<T>,
+    {
+        let mut databases = self.databases.write();
+        let mut database = databases.get(db_name).cloned().unwrap_or_default();
+
+        let result = f(&mut database)?;
+        databases.insert(db_name.to_string(), database);
+
+        Ok(result)
+    }
+}
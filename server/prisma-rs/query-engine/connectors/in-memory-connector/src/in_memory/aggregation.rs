@@ -0,0 +1,122 @@
+use super::{
+    database::{Database, Record},
+    filter::{self, field_value},
+    value::Key,
+};
+use connector::{filter::StringMode, Aggregation, AggregationRow, AggregationSelection, QueryArguments};
+use prisma_models::prelude::*;
+use std::cmp::Ordering;
+
+impl Database {
+    /// Aggregates the records of the page the arguments select, per group of records with
+    /// equal values in the `group_by` fields. Groups come in ascending order of these values,
+    /// and without grouping there's a single row, even if there are no records.
+    pub(crate) fn aggregate(
+        &self,
+        model: &ModelRef,
+        query_arguments: &QueryArguments,
+        selection: &AggregationSelection,
+    ) -> Vec<AggregationRow> {
+        let records: Vec<&Record> = self
+            .query_page(model, query_arguments, 0)
+            .iter()
+            .filter_map(|id| self.record(model, id))
+            .collect();
+
+        let mut groups: Vec<(Vec<PrismaValue>, Vec<&Record>)> = Vec::new();
+
+        if selection.group_by.is_empty() {
+            groups.push((Vec::new(), records));
+        } else {
+            for record in records {
+                let group: Vec<PrismaValue> = selection
+                    .group_by
+                    .iter()
+                    .map(|field| field_value(record, field).clone())
+                    .collect();
+
+                match groups
+                    .iter_mut()
+                    .find(|(other, _)| compare_groups(other, &group) == Ordering::Equal)
+                {
+                    Some((_, members)) => members.push(record),
+                    None => groups.push((group, vec![record])),
+                }
+            }
+
+            groups.sort_by(|(a, _), (b, _)| compare_groups(a, b));
+        }
+
+        groups
+            .into_iter()
+            .filter(|(_, members)| {
+                selection.having.iter().all(|having| {
+                    let value = aggregated(&having.aggregation, members);
+                    filter::scalar_condition(&value, &having.condition, StringMode::Default) == Some(true)
+                })
+            })
+            .map(|(group, members)| AggregationRow {
+                group,
+                values: selection
+                    .aggregations
+                    .iter()
+                    .map(|aggregation| aggregated(aggregation, &members))
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+/// Groups take `NULL` values as equal, like `GROUP BY`.
+fn compare_groups(a: &[PrismaValue], b: &[PrismaValue]) -> Ordering {
+    a.iter().zip(b.iter()).fold(Ordering::Equal, |acc, (a, b)| {
+        acc.then_with(|| Key::from(a).order(&Key::from(b)))
+    })
+}
+
+/// The aggregated value of the records. Aggregations other than the count ignore `NULL` values,
+/// and are `NULL` if there are no other values.
+fn aggregated(aggregation: &Aggregation, records: &[&Record]) -> PrismaValue {
+    let field = match aggregation.field() {
+        Some(field) => field,
+        None => return PrismaValue::Int(records.len() as i32),
+    };
+
+    let values: Vec<&PrismaValue> = records
+        .iter()
+        .map(|record| field_value(record, field))
+        .filter(|value| !value.is_null())
+        .collect();
+
+    if values.is_empty() {
+        return PrismaValue::Null;
+    }
+
+    let number = |value: &PrismaValue| match Key::from(value) {
+        Key::Number(n) => n,
+        _ => 0.0,
+    };
+
+    match aggregation {
+        Aggregation::Sum(field) if field.type_identifier == TypeIdentifier::Int => {
+            let sum: i64 = values.iter().map(|value| number(value) as i64).sum();
+            PrismaValue::Int(sum as i32)
+        }
+        Aggregation::Sum(_) => PrismaValue::Float(values.iter().map(|value| number(value)).sum()),
+        Aggregation::Avg(_) => {
+            let sum: f64 = values.iter().map(|value| number(value)).sum();
+            PrismaValue::Float(sum / values.len() as f64)
+        }
+        Aggregation::Min(_) => values
+            .into_iter()
+            .min_by(|a, b| Key::from(*a).order(&Key::from(*b)))
+            .cloned()
+            .unwrap_or(PrismaValue::Null),
+        Aggregation::Max(_) => values
+            .into_iter()
+            .max_by(|a, b| Key::from(*a).order(&Key::from(*b)))
+            .cloned()
+            .unwrap_or(PrismaValue::Null),
+        Aggregation::Count => PrismaValue::Int(records.len() as i32),
+    }
+}
@@ -0,0 +1,299 @@
+use super::value::{self, Key};
+use connector::{error::ConnectorError, ConnectorResult};
+use prisma_models::prelude::*;
+use std::{collections::HashMap, sync::Arc};
+
+/// The values of a record by field name. Relations are held as links, apart from the records.
+pub(crate) type Record = HashMap<String, PrismaValue>;
+
+/// A link between two records, the id of the record of model A first.
+type Link = (GraphqlId, GraphqlId);
+
+/// The data of one Prisma database.
+#[derive(Default, Clone)]
+pub(crate) struct Database {
+    /// Records by model name and id
+    records: HashMap<String, HashMap<GraphqlId, Record>>,
+
+    /// Links by relation name, in the order they were made
+    links: HashMap<String, Vec<Link>>,
+
+    /// Values of scalar list fields by model name and field name, and then by record id
+    lists: HashMap<(String, String), HashMap<GraphqlId, Vec<PrismaValue>>>,
+
+    /// The last generated integer id by model name
+    sequences: HashMap<String, usize>,
+
+    /// The schema of the written records, to find tables by name
+    schema: Option<SchemaRef>,
+}
+
+impl Database {
+    pub(crate) fn schema(&self) -> Option<&SchemaRef> {
+        self.schema.as_ref()
+    }
+
+    pub(crate) fn ids(&self, model: &ModelRef) -> Vec<GraphqlId> {
+        self.records
+            .get(&model.name)
+            .map(|records| records.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn record(&self, model: &ModelRef, id: &GraphqlId) -> Option<&Record> {
+        self.records.get(&model.name).and_then(|records| records.get(id))
+    }
+
+    pub(crate) fn count(&self, model: &ModelRef) -> usize {
+        self.records.get(&model.name).map(|records| records.len()).unwrap_or(0)
+    }
+
+    /// The next id of a model with ids generated by the database, counting from 1.
+    pub(crate) fn next_id(&mut self, model: &ModelRef) -> GraphqlId {
+        let sequence = self.sequences.entry(model.name.clone()).or_insert(0);
+        *sequence += 1;
+
+        GraphqlId::Int(*sequence)
+    }
+
+    /// Inserts a record with the given scalar values, which are converted to the types of their
+    /// fields, and returns its id the way it's stored.
+    pub(crate) fn insert(&mut self, model: &ModelRef, id: GraphqlId, values: Record) -> ConnectorResult<GraphqlId> {
+        let id = value::id(id);
+
+        if self.record(model, &id).is_some() {
+            return Err(Self::unique_violation(model, &model.fields().id()));
+        }
+
+        let mut record = Record::new();
+
+        for field in model.fields().scalar_non_list() {
+            let value = match values.get(&field.name) {
+                _ if field.is_id() => PrismaValue::GraphqlId(id.clone()),
+                Some(value) => value::stored(field.type_identifier, value.clone()),
+                None => PrismaValue::Null,
+            };
+
+            record.insert(field.name.clone(), value);
+        }
+
+        self.check_unique(model, &id, &record)?;
+
+        if self.schema.is_none() {
+            self.schema = Some(model.schema());
+        }
+
+        self.records
+            .entry(model.name.clone())
+            .or_default()
+            .insert(id.clone(), record);
+
+        Ok(id)
+    }
+
+    /// Sets scalar values of an existing record.
+    pub(crate) fn update(&mut self, model: &ModelRef, id: &GraphqlId, values: Record) -> ConnectorResult<()> {
+        let mut record = match self.record(model, id) {
+            Some(record) => record.clone(),
+            None => return Err(ConnectorError::NodeDoesNotExist),
+        };
+
+        for (name, value) in values {
+            let field = model.fields().find_from_scalar(&name)?;
+            record.insert(name, value::stored(field.type_identifier, value));
+        }
+
+        self.check_unique(model, id, &record)?;
+        self.records
+            .entry(model.name.clone())
+            .or_default()
+            .insert(id.clone(), record);
+
+        Ok(())
+    }
+
+    /// Removes the records with their list values and links.
+    pub(crate) fn remove(&mut self, model: &ModelRef, ids: &[&GraphqlId]) {
+        if let Some(records) = self.records.get_mut(&model.name) {
+            for id in ids {
+                records.remove(id);
+            }
+        }
+
+        for field in model.fields().scalar_list() {
+            if let Some(lists) = self.lists.get_mut(&(model.name.clone(), field.name.clone())) {
+                for id in ids {
+                    lists.remove(id);
+                }
+            }
+        }
+
+        for field in model.fields().relation() {
+            if let Some(links) = self.links.get_mut(&field.relation_name) {
+                links.retain(|link| !ids.contains(&Self::side(link, field.relation_side)));
+            }
+        }
+    }
+
+    /// The ids of the records linked to the given record through the field.
+    pub(crate) fn related_ids(&self, field: &RelationField, id: &GraphqlId) -> Vec<GraphqlId> {
+        self.links(field)
+            .iter()
+            .filter(|link| Self::side(link, field.relation_side) == id)
+            .map(|link| Self::side(link, field.relation_side.opposite()).clone())
+            .collect()
+    }
+
+    /// Whether the record has any link through the field.
+    pub(crate) fn is_linked(&self, field: &RelationField, id: &GraphqlId) -> bool {
+        self.links(field)
+            .iter()
+            .any(|link| Self::side(link, field.relation_side) == id)
+    }
+
+    /// Links a child to a parent through the field of the parent. A record holding the column of
+    /// an inline relation loses its previous link, the same way the column is overwritten.
+    pub(crate) fn link(&mut self, field: &RelationField, parent_id: &GraphqlId, child_id: &GraphqlId) {
+        let link = match field.relation_side {
+            RelationSide::A => (parent_id.clone(), child_id.clone()),
+            RelationSide::B => (child_id.clone(), parent_id.clone()),
+        };
+
+        let relation = field.relation();
+        let links = self.links.entry(relation.name.clone()).or_default();
+
+        if let Some(mani) = relation.inline_manifestation() {
+            let holder = if mani.in_table_of_model_name == relation.model_b().name {
+                RelationSide::B
+            } else {
+                RelationSide::A
+            };
+
+            let holder_id = Self::side(&link, holder).clone();
+            links.retain(|existing| Self::side(existing, holder) != &holder_id);
+        }
+
+        if !links.contains(&link) {
+            links.push(link);
+        }
+    }
+
+    /// Removes the links of the parent through its field.
+    pub(crate) fn unlink_parent(&mut self, field: &RelationField, parent_id: &GraphqlId) {
+        self.unlink_where(field, |link| Self::side(link, field.relation_side) == parent_id);
+    }
+
+    /// Removes the links of the child to any parent through the field of the parent.
+    pub(crate) fn unlink_child(&mut self, field: &RelationField, child_id: &GraphqlId) {
+        self.unlink_where(field, |link| {
+            Self::side(link, field.relation_side.opposite()) == child_id
+        });
+    }
+
+    pub(crate) fn unlink(&mut self, field: &RelationField, parent_id: &GraphqlId, child_id: &GraphqlId) {
+        self.unlink_where(field, |link| {
+            Self::side(link, field.relation_side) == parent_id
+                && Self::side(link, field.relation_side.opposite()) == child_id
+        });
+    }
+
+    /// The number of links of the relation.
+    pub(crate) fn link_count(&self, relation: &Relation) -> usize {
+        self.links.get(&relation.name).map(|links| links.len()).unwrap_or(0)
+    }
+
+    pub(crate) fn list_values(&self, field: &ScalarField, id: &GraphqlId) -> &[PrismaValue] {
+        self.lists
+            .get(&(field.model().name.clone(), field.name.clone()))
+            .and_then(|lists| lists.get(id))
+            .map(|values| values.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Replaces the values of a list field, removing them if the list is empty.
+    pub(crate) fn set_list_values(&mut self, field: &ScalarField, id: &GraphqlId, values: &PrismaListValue) {
+        let lists = self
+            .lists
+            .entry((field.model().name.clone(), field.name.clone()))
+            .or_default();
+
+        if values.is_empty() {
+            lists.remove(id);
+        } else {
+            let values = values
+                .iter()
+                .map(|value| value::stored(field.type_identifier, value.clone()))
+                .collect();
+
+            lists.insert(id.clone(), values);
+        }
+    }
+
+    /// The number of values of a list field in all records.
+    pub(crate) fn list_value_count(&self, field: &ScalarField) -> usize {
+        self.lists
+            .get(&(field.model().name.clone(), field.name.clone()))
+            .map(|lists| lists.values().map(|values| values.len()).sum())
+            .unwrap_or(0)
+    }
+
+    fn links(&self, field: &RelationField) -> &[Link] {
+        self.links
+            .get(&field.relation_name)
+            .map(|links| links.as_slice())
+            .unwrap_or(&[])
+    }
+
+    fn unlink_where<F>(&mut self, field: &RelationField, f: F)
+    where
+        F: Fn(&Link) -> bool,
+    {
+        if let Some(links) = self.links.get_mut(&field.relation_name) {
+            links.retain(|link| !f(link));
+        }
+    }
+
+    fn side(link: &Link, side: RelationSide) -> &GraphqlId {
+        match side {
+            RelationSide::A => &link.0,
+            RelationSide::B => &link.1,
+        }
+    }
+
+    /// Unique fields can't have the same value in two records, unless it's `NULL`.
+    fn check_unique(&self, model: &ModelRef, id: &GraphqlId, record: &Record) -> ConnectorResult<()> {
+        let records = match self.records.get(&model.name) {
+            Some(records) => records,
+            None => return Ok(()),
+        };
+
+        for field in model.fields().scalar_non_list() {
+            if !field.is_unique || field.is_id() {
+                continue;
+            }
+
+            let key = record.get(&field.name).map(Key::from).unwrap_or(Key::Null);
+
+            if key.is_null() {
+                continue;
+            }
+
+            let is_taken = records.iter().any(|(other_id, other)| {
+                other_id != id && other.get(&field.name).map(Key::from).unwrap_or(Key::Null) == key
+            });
+
+            if is_taken {
+                return Err(Self::unique_violation(model, &field));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Named after the column like in the message of SQLite.
+    fn unique_violation(model: &ModelRef, field: &Arc<ScalarField>) -> ConnectorError {
+        ConnectorError::UniqueConstraintViolation {
+            field_name: format!("{}.{}", model.db_name(), field.db_name()),
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use super::{
+    database::{Database, Record},
+    json, search,
+    value::Key,
+};
+use connector::filter::*;
+use prisma_models::prelude::*;
+use std::cmp::Ordering;
+
+/// The truth value of a condition in SQL, which is unknown (`None`) for comparisons with `NULL`.
+/// Unknown conditions exclude a record like false ones, but stay unknown when negated.
+pub(crate) type Truth = Option<bool>;
+
+pub(crate) fn and(left: Truth, right: Truth) -> Truth {
+    match (left, right) {
+        (Some(false), _) | (_, Some(false)) => Some(false),
+        (Some(true), Some(true)) => Some(true),
+        _ => None,
+    }
+}
+
+pub(crate) fn or(left: Truth, right: Truth) -> Truth {
+    match (left, right) {
+        (Some(true), _) | (_, Some(true)) => Some(true),
+        (Some(false), Some(false)) => Some(false),
+        _ => None,
+    }
+}
+
+pub(crate) fn not(truth: Truth) -> Truth {
+    truth.map(|t| !t)
+}
+
+impl Database {
+    /// Whether the record of the model with the given id matches the filter.
+    pub(crate) fn matches(&self, model: &ModelRef, id: &GraphqlId, filter: &Filter) -> Truth {
+        let record = match self.record(model, id) {
+            Some(record) => record,
+            None => return Some(false),
+        };
+
+        self.evaluate(id, record, filter)
+    }
+
+    fn evaluate(&self, id: &GraphqlId, record: &Record, filter: &Filter) -> Truth {
+        match filter {
+            // Empty lists of filters are no condition at all, even when negated.
+            Filter::And(filters) => filters
+                .iter()
+                .fold(Some(true), |acc, filter| and(acc, self.evaluate(id, record, filter))),
+            Filter::Or(filters) if filters.is_empty() => Some(true),
+            Filter::Or(filters) => filters
+                .iter()
+                .fold(Some(false), |acc, filter| or(acc, self.evaluate(id, record, filter))),
+            Filter::Not(filters) => filters.iter().fold(Some(true), |acc, filter| {
+                and(acc, not(self.evaluate(id, record, filter)))
+            }),
+            Filter::Scalar(filter) => evaluate_scalar(record, filter),
+            Filter::ScalarList(filter) => Some(self.evaluate_scalar_list(id, filter)),
+            Filter::OneRelationIsNull(filter) => Some(!self.is_linked(&filter.field, id)),
+            Filter::Relation(filter) => Some(self.evaluate_relation(id, filter)),
+            Filter::Json(filter) => {
+                json::condition(field_value(record, &filter.field), &filter.path, &filter.condition)
+            }
+            Filter::BoolFilter(b) => Some(*b),
+            // Subscriptions already select the node in question.
+            Filter::NodeSubscription => Some(true),
+        }
+    }
+
+    /// The values of a list field are a set of values to look in, like the rows of its table.
+    fn evaluate_scalar_list(&self, id: &GraphqlId, filter: &ScalarListFilter) -> bool {
+        let values: Vec<Key> = self.list_values(&filter.field, id).iter().map(Key::from).collect();
+        let contains = |value: &PrismaValue| {
+            let key = Key::from(value);
+            values.iter().any(|v| v.compare(&key) == Some(Ordering::Equal))
+        };
+
+        match filter.condition {
+            ScalarListCondition::Contains(ref value) => contains(value),
+            ScalarListCondition::ContainsEvery(ref values) => values.iter().all(contains),
+            ScalarListCondition::ContainsSome(ref values) => values.iter().any(contains),
+        }
+    }
+
+    /// Related records are selected by the nested filter like in a subselect, so a related record
+    /// for which the filter is unknown neither matches nor fails to match.
+    fn evaluate_relation(&self, id: &GraphqlId, filter: &RelationFilter) -> bool {
+        let related_model = filter.field.related_model();
+        let related = self.related_ids(&filter.field, id);
+        let related_matching = |expected: bool| {
+            related
+                .iter()
+                .any(|related_id| self.matches(&related_model, related_id, &filter.nested_filter) == Some(expected))
+        };
+
+        match filter.condition {
+            RelationCondition::EveryRelatedNode => !related_matching(false),
+            RelationCondition::NoRelatedNode => !related_matching(true),
+            RelationCondition::AtLeastOneRelatedNode | RelationCondition::ToOneRelatedNode => related_matching(true),
+        }
+    }
+}
+
+fn evaluate_scalar(record: &Record, filter: &ScalarFilter) -> Truth {
+    // Only strings have a case to ignore.
+    let mode = match filter.field.type_identifier {
+        TypeIdentifier::String => filter.mode,
+        _ => StringMode::Default,
+    };
+
+    scalar_condition(field_value(record, &filter.field), &filter.condition, mode)
+}
+
+/// The condition on a value, shared by scalar filters and conditions on aggregated values.
+///
+/// Substrings are matched literally and case-sensitively, like in the SQL connectors. Insensitive
+/// conditions only fold the case of ASCII characters, like SQLite.
+pub(crate) fn scalar_condition(value: &PrismaValue, condition: &ScalarCondition, mode: StringMode) -> Truth {
+    let fold = |key: Key| match mode {
+        StringMode::Default => key,
+        StringMode::Insensitive => key.lowercase(),
+    };
+
+    let column = fold(Key::from(value));
+    let key = |value: &PrismaValue| match (mode, value) {
+        (StringMode::Insensitive, PrismaValue::String(s)) => Key::Text(s.to_ascii_lowercase()),
+        (_, value) => Key::from(value),
+    };
+
+    let compare = |value: &PrismaValue| column.compare(&key(value));
+    let text = |value: &PrismaValue| match (column.text(), key(value).text()) {
+        (Some(column), Some(value)) => Some((column, value)),
+        _ => None,
+    };
+
+    let is_in = |values: &[PrismaValue]| {
+        values.iter().fold(Some(false), |acc, value| {
+            or(acc, compare(value).map(|ordering| ordering == Ordering::Equal))
+        })
+    };
+
+    match condition {
+        ScalarCondition::Equals(PrismaValue::Null) => Some(column.is_null()),
+        ScalarCondition::NotEquals(PrismaValue::Null) => Some(!column.is_null()),
+        ScalarCondition::Equals(value) => compare(value).map(|ordering| ordering == Ordering::Equal),
+        ScalarCondition::NotEquals(value) => compare(value).map(|ordering| ordering != Ordering::Equal),
+        ScalarCondition::Contains(value) => text(value).map(|(column, value)| column.contains(&value)),
+        ScalarCondition::NotContains(value) => text(value).map(|(column, value)| !column.contains(&value)),
+        ScalarCondition::StartsWith(value) => text(value).map(|(column, value)| column.starts_with(&value)),
+        ScalarCondition::NotStartsWith(value) => text(value).map(|(column, value)| !column.starts_with(&value)),
+        ScalarCondition::EndsWith(value) => text(value).map(|(column, value)| column.ends_with(&value)),
+        ScalarCondition::NotEndsWith(value) => text(value).map(|(column, value)| !column.ends_with(&value)),
+        ScalarCondition::LessThan(value) => compare(value).map(|ordering| ordering == Ordering::Less),
+        ScalarCondition::LessThanOrEquals(value) => compare(value).map(|ordering| ordering != Ordering::Greater),
+        ScalarCondition::GreaterThan(value) => compare(value).map(|ordering| ordering == Ordering::Greater),
+        ScalarCondition::GreaterThanOrEquals(value) => compare(value).map(|ordering| ordering != Ordering::Less),
+        ScalarCondition::In(values) => match values.split_first() {
+            Some((PrismaValue::Null, tail)) if tail.is_empty() => Some(column.is_null()),
+            _ => is_in(values),
+        },
+        ScalarCondition::NotIn(values) => match values.split_first() {
+            Some((PrismaValue::Null, tail)) if tail.is_empty() => Some(!column.is_null()),
+            _ => not(is_in(values)),
+        },
+        ScalarCondition::Search(query) => Some(search::matches(value, query)),
+    }
+}
+
+/// The value of a scalar field of the record, `NULL` if it was never set.
+pub(crate) fn field_value<'a>(record: &'a Record, field: &ScalarField) -> &'a PrismaValue {
+    record.get(&field.name).unwrap_or(&PrismaValue::Null)
+}
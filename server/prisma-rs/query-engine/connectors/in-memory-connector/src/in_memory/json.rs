@@ -0,0 +1,137 @@
+use super::{filter, filter::Truth, value::Key};
+use connector::{error::ConnectorError, filter::JsonCondition, mutaction::JsonOperation, ConnectorResult};
+use prisma_models::prelude::*;
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
+
+/// Whether the value at the path in the document of a JSON column meets the condition. Like the
+/// functions of the SQLite JSON1 extension, a missing document or path makes it unknown.
+pub(crate) fn condition(column: &PrismaValue, path: &[String], condition: &JsonCondition) -> Truth {
+    let document = match column {
+        PrismaValue::Json(s) | PrismaValue::String(s) => serde_json::from_str::<Value>(s).ok()?,
+        _ => return None,
+    };
+
+    let value = extract(&document, path)?;
+    let equals = |a: &Value, b: &Value| extracted(a).compare(&extracted(b)).map(|o| o == Ordering::Equal);
+
+    match condition {
+        JsonCondition::Equals(Value::Null) => Some(value.is_null()),
+        JsonCondition::Equals(expected) => equals(value, expected),
+        JsonCondition::ArrayContains(expected) => match value {
+            Value::Array(elements) => elements
+                .iter()
+                .fold(Some(false), |acc, element| filter::or(acc, equals(element, expected))),
+            _ => Some(false),
+        },
+        JsonCondition::StringContains(expected) => match value {
+            Value::String(s) => Some(s.contains(expected.as_str())),
+            _ => Some(false),
+        },
+    }
+}
+
+/// The document in the column after the operation. Columns without a document are taken as an
+/// empty object.
+pub(crate) fn update(column: &PrismaValue, operation: &JsonOperation) -> ConnectorResult<PrismaValue> {
+    let mut document = match column {
+        PrismaValue::Json(s) | PrismaValue::String(s) => {
+            serde_json::from_str(s).map_err(|e| ConnectorError::QueryError(e.into()))?
+        }
+        _ => Value::Object(Map::new()),
+    };
+
+    match operation {
+        JsonOperation::SetPath { path, value } => set_path(&mut document, path, value.clone()),
+        JsonOperation::Merge(patch) => merge(&mut document, patch),
+    }
+
+    Ok(PrismaValue::Json(document.to_string()))
+}
+
+/// Segments that parse as numbers are array indices, all others object keys.
+fn extract<'a>(document: &'a Value, path: &[String]) -> Option<&'a Value> {
+    path.iter()
+        .try_fold(document, |value, segment| match segment.parse::<usize>() {
+            Ok(index) => value.as_array().and_then(|elements| elements.get(index)),
+            Err(_) => value.as_object().and_then(|object| object.get(segment)),
+        })
+}
+
+/// A JSON value the way `json_extract` reads it, as an SQL value for scalars and as minified text
+/// for arrays and objects.
+fn extracted(value: &Value) -> Key {
+    match value {
+        Value::Null => Key::Null,
+        Value::Bool(b) => Key::Number(if *b { 1.0 } else { 0.0 }),
+        Value::Number(n) => Key::Number(n.as_f64().unwrap_or(0.0)),
+        Value::String(s) => Key::Text(s.clone()),
+        Value::Array(_) | Value::Object(_) => Key::Text(value.to_string()),
+    }
+}
+
+/// Replaces or adds the value at the path like `json_set`. Missing object keys on the way are
+/// added as empty objects, while paths through other values or past the end of arrays change
+/// nothing.
+fn set_path(document: &mut Value, path: &[String], value: Value) {
+    let (last, init) = match path.split_last() {
+        Some(split) => split,
+        None => {
+            *document = value;
+            return;
+        }
+    };
+
+    let mut current = document;
+
+    for segment in init {
+        current = match (segment.parse::<usize>(), current) {
+            (Ok(index), Value::Array(elements)) => match elements.get_mut(index) {
+                Some(element) => element,
+                None => return,
+            },
+            (Err(_), Value::Object(object)) => object
+                .entry(segment.clone())
+                .or_insert_with(|| Value::Object(Map::new())),
+            _ => return,
+        };
+    }
+
+    match (last.parse::<usize>(), current) {
+        (Ok(index), Value::Array(elements)) => {
+            if let Some(element) = elements.get_mut(index) {
+                *element = value;
+            }
+        }
+        (Err(_), Value::Object(object)) => {
+            object.insert(last.clone(), value);
+        }
+        _ => (),
+    }
+}
+
+/// Applies a JSON merge patch (RFC 7396) like `json_patch`: objects are merged key by key, `null`
+/// removes a key, and any other value replaces the document.
+fn merge(document: &mut Value, patch: &Value) {
+    let patch = match patch {
+        Value::Object(patch) => patch,
+        patch => {
+            *document = patch.clone();
+            return;
+        }
+    };
+
+    if !document.is_object() {
+        *document = Value::Object(Map::new());
+    }
+
+    if let Value::Object(object) = document {
+        for (key, value) in patch {
+            if value.is_null() {
+                object.remove(key);
+            } else {
+                merge(object.entry(key.clone()).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use super::database::Database;
+use crate::InMemory;
+use connector::{error::ConnectorError, mutaction::*, ConnectorResult, DatabaseMutactionExecutor};
+use prisma_models::*;
+use serde_json::Value;
+
+impl DatabaseMutactionExecutor for InMemory {
+    fn execute_raw(&self, _query: String) -> ConnectorResult<Value> {
+        // There's no query language to run raw queries in.
+        Ok(Value::String("hello world!".to_string()))
+    }
+
+    fn execute(
+        &self,
+        db_name: String,
+        mutaction: DatabaseMutaction,
+        parent_id: Option<GraphqlId>, // TODO: we don't need this when we handle the whole mutaction in here.
+    ) -> ConnectorResult<DatabaseMutactionResults> {
+        self.with_transaction(&db_name, |db| match mutaction {
+            DatabaseMutaction::TopLevel(tlm) => Self::execute_toplevel(db, tlm),
+            DatabaseMutaction::Nested(nm) => {
+                Self::execute_nested(db, nm, parent_id.ok_or(ConnectorError::MissingParentId)?)
+            }
+        })
+    }
+
+    fn execute_with_nested(
+        &self,
+        db_name: String,
+        mutaction: DatabaseMutaction,
+        parent_id: Option<GraphqlId>,
+    ) -> ConnectorResult<DatabaseMutactionResults> {
+        self.with_transaction(&db_name, |db| {
+            let own_results = match mutaction {
+                DatabaseMutaction::TopLevel(tlm) => Self::execute_toplevel(db, tlm)?,
+                DatabaseMutaction::Nested(nm) => {
+                    Self::execute_nested(db, nm, parent_id.ok_or(ConnectorError::MissingParentId)?)?
+                }
+            };
+
+            Self::execute_nested_tree(db, own_results)
+        })
+    }
+}
+
+impl InMemory {
+    /// Executes the nested mutactions of every result recursively in the same transaction.
+    /// Results are handled in the order they were returned, and the result of a parent is pushed
+    /// after the results of its children, keeping `pop` on the returned results pointing to the
+    /// mutaction originally requested.
+    fn execute_nested_tree(
+        db: &mut Database,
+        own_results: DatabaseMutactionResults,
+    ) -> ConnectorResult<DatabaseMutactionResults> {
+        let mut results = DatabaseMutactionResults::default();
+
+        for result in own_results {
+            if let Some(nested) = result.nested_mutactions() {
+                for mutaction in nested.mutactions() {
+                    let nested_results = Self::execute_nested(db, mutaction, result.id().clone())?;
+                    results.merge(Self::execute_nested_tree(db, nested_results)?);
+                }
+            }
+
+            results.push(result);
+        }
+
+        Ok(results)
+    }
+}
@@ -0,0 +1,218 @@
+use super::database::Database;
+use connector::{error::*, filter::NodeSelector, mutaction::*, ConnectorResult, QueryArguments};
+use prisma_models::*;
+use std::sync::Arc;
+
+/// The relation checks and link removals of a nested mutaction, decided by the arity of the
+/// relation fields on both sides the same way as in the SQL connectors.
+pub(crate) trait NestedActions {
+    fn relation_field(&self) -> RelationFieldRef;
+
+    /// Fails if the mutaction would violate the relation, before anything is written.
+    fn required_check(&self, db: &Database, parent_id: &GraphqlId) -> ConnectorResult<()>;
+
+    /// Whether the parent loses its links before the child is linked.
+    fn parent_removal(&self) -> bool;
+
+    /// Whether the child loses its links before it's linked to the parent.
+    fn child_removal(&self) -> bool;
+
+    fn relation(&self) -> RelationRef {
+        self.relation_field().relation()
+    }
+
+    fn relation_violation(&self) -> ConnectorError {
+        let relation = self.relation();
+
+        ConnectorError::RelationViolation {
+            relation_name: relation.name.clone(),
+            model_a_name: relation.model_a().name.clone(),
+            model_b_name: relation.model_b().name.clone(),
+        }
+    }
+
+    fn nodes_not_connected(&self, parent_id: Option<GraphqlId>, child_id: Option<GraphqlId>) -> ConnectorError {
+        let rf = self.relation_field();
+
+        let parent_where = parent_id.map(|parent_id| NodeSelectorInfo::for_id(rf.model(), &parent_id));
+        let child_where = child_id.map(|child_id| NodeSelectorInfo::for_id(rf.related_model(), &child_id));
+
+        ConnectorError::NodesNotConnected {
+            relation_name: rf.relation().name.clone(),
+            parent_name: rf.model().name.clone(),
+            parent_where,
+            child_name: rf.related_model().name.clone(),
+            child_where,
+        }
+    }
+
+    /// The parent must not have a child yet.
+    fn check_for_old_child(&self, db: &Database, parent_id: &GraphqlId) -> ConnectorResult<()> {
+        if db.is_linked(&self.relation_field(), parent_id) {
+            Err(self.relation_violation())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The children selected by the node selector must not have a parent yet.
+    fn check_for_old_parent_by_child(&self, db: &Database, node_selector: &NodeSelector) -> ConnectorResult<()> {
+        let rf = self.relation_field().related_field();
+        let children = db.query(&rf.model(), &QueryArguments::from(node_selector.clone()));
+
+        if children.iter().any(|child_id| db.is_linked(&rf, child_id)) {
+            Err(self.relation_violation())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn ensure_parent_is_connected(&self, db: &Database, parent_id: &GraphqlId) -> ConnectorResult<()> {
+        if db.is_linked(&self.relation_field(), parent_id) {
+            Ok(())
+        } else {
+            Err(self.nodes_not_connected(Some(parent_id.clone()), None))
+        }
+    }
+
+    fn ensure_connected(&self, db: &Database, parent_id: &GraphqlId, child_id: &GraphqlId) -> ConnectorResult<()> {
+        if db.related_ids(&self.relation_field(), parent_id).contains(child_id) {
+            Ok(())
+        } else {
+            Err(self.nodes_not_connected(Some(parent_id.clone()), Some(child_id.clone())))
+        }
+    }
+}
+
+impl NestedActions for NestedCreateNode {
+    fn relation_field(&self) -> RelationFieldRef {
+        self.relation_field.clone()
+    }
+
+    fn required_check(&self, db: &Database, parent_id: &GraphqlId) -> ConnectorResult<()> {
+        if self.top_is_create {
+            return Ok(());
+        }
+
+        let p = Arc::clone(&self.relation_field);
+        let c = p.related_field();
+
+        match (p.is_list, p.is_required, c.is_list, c.is_required) {
+            (false, true, false, true) => Err(self.relation_violation()),
+            (false, false, false, true) => self.check_for_old_child(db, parent_id),
+            _ => Ok(()),
+        }
+    }
+
+    fn parent_removal(&self) -> bool {
+        !self.top_is_create && !self.relation_field.is_list
+    }
+
+    fn child_removal(&self) -> bool {
+        false
+    }
+}
+
+impl NestedActions for NestedConnect {
+    fn relation_field(&self) -> RelationFieldRef {
+        self.relation_field.clone()
+    }
+
+    fn required_check(&self, db: &Database, parent_id: &GraphqlId) -> ConnectorResult<()> {
+        let p = Arc::clone(&self.relation_field);
+        let c = p.related_field();
+
+        match (p.is_list, p.is_required, c.is_list, c.is_required) {
+            (false, true, false, true) => Err(self.relation_violation()),
+            (false, true, false, false) => self.check_for_old_parent_by_child(db, &self.where_),
+            (false, false, false, true) if !self.top_is_create => self.check_for_old_child(db, parent_id),
+            _ => Ok(()),
+        }
+    }
+
+    fn parent_removal(&self) -> bool {
+        let p = &self.relation_field;
+        let c = p.related_field();
+
+        match (p.is_list, c.is_list) {
+            (false, false) => true,
+            (false, true) => !self.top_is_create,
+            _ => false,
+        }
+    }
+
+    fn child_removal(&self) -> bool {
+        let p = &self.relation_field;
+        let c = p.related_field();
+
+        match (p.is_list, c.is_list) {
+            (false, false) => !self.top_is_create,
+            (true, false) => true,
+            _ => false,
+        }
+    }
+}
+
+impl NestedActions for NestedDisconnect {
+    fn relation_field(&self) -> RelationFieldRef {
+        self.relation_field.clone()
+    }
+
+    fn required_check(&self, _: &Database, _: &GraphqlId) -> ConnectorResult<()> {
+        let p = Arc::clone(&self.relation_field);
+        let c = p.related_field();
+
+        match (p.is_list, p.is_required, c.is_list, c.is_required) {
+            (false, true, false, true)
+            | (false, true, false, false)
+            | (false, false, false, true)
+            | (true, false, false, true)
+            | (false, true, true, false) => Err(self.relation_violation()),
+            _ => Ok(()),
+        }
+    }
+
+    fn parent_removal(&self) -> bool {
+        false
+    }
+
+    fn child_removal(&self) -> bool {
+        false
+    }
+}
+
+impl NestedActions for NestedSet {
+    fn relation_field(&self) -> RelationFieldRef {
+        self.relation_field.clone()
+    }
+
+    fn required_check(&self, _: &Database, _: &GraphqlId) -> ConnectorResult<()> {
+        Ok(())
+    }
+
+    fn parent_removal(&self) -> bool {
+        false
+    }
+
+    fn child_removal(&self) -> bool {
+        false
+    }
+}
+
+impl NestedActions for NestedDeleteNode {
+    fn relation_field(&self) -> RelationFieldRef {
+        self.relation_field.clone()
+    }
+
+    fn required_check(&self, _: &Database, _: &GraphqlId) -> ConnectorResult<()> {
+        Ok(())
+    }
+
+    fn parent_removal(&self) -> bool {
+        false
+    }
+
+    fn child_removal(&self) -> bool {
+        false
+    }
+}
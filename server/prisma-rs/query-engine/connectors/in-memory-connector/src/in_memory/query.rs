@@ -0,0 +1,352 @@
+use super::{
+    database::Database,
+    filter::{self, field_value, Truth},
+    search,
+    value::Key,
+};
+use connector::{QueryArguments, RelatedNodesCount};
+use prisma_models::prelude::*;
+use std::cmp::Ordering;
+
+#[derive(Clone, Copy)]
+enum CursorType {
+    Before,
+    After,
+}
+
+/// A selected record with the values of its sort keys, in the order of `order_by`.
+struct Row {
+    id: GraphqlId,
+    keys: Vec<Key>,
+}
+
+impl Database {
+    /// The ids of the records of the model selected by the arguments, in order.
+    ///
+    /// Like in the SQL connectors, records are filtered first, then reduced to the distinct
+    /// records, then restricted to the records past the cursors, and finally ordered and sliced,
+    /// taking one record more than requested to see if there's more data.
+    pub(crate) fn query(&self, model: &ModelRef, query_arguments: &QueryArguments) -> Vec<GraphqlId> {
+        self.query_page(model, query_arguments, 1)
+    }
+
+    /// The ids of the records of the page the arguments select, followed by up to `lookahead`
+    /// records past it.
+    pub(crate) fn query_page(
+        &self,
+        model: &ModelRef,
+        query_arguments: &QueryArguments,
+        lookahead: usize,
+    ) -> Vec<GraphqlId> {
+        let mut rows = self.select(model, self.ids(model), query_arguments);
+        let reverse = query_arguments.last.is_some();
+
+        rows.sort_by(|a, b| compare_rows(model, &query_arguments.order_by, reverse, a, b));
+
+        let skip = query_arguments.skip.unwrap_or(0) as usize;
+        let limit = match query_arguments.last.or(query_arguments.first) {
+            Some(count) => count as usize + lookahead,
+            None => usize::max_value(),
+        };
+
+        rows.into_iter().skip(skip).take(limit).map(|row| row.id).collect()
+    }
+
+    /// The records related to the parents through the field, as pairs of parent and child ids.
+    ///
+    /// Records are distinct and paginated per parent. Without pagination, the related records
+    /// of all parents are ordered together, with the child id as tie-breaker. With pagination,
+    /// they are ordered per parent and come grouped by parent, exactly as many as requested.
+    pub(crate) fn query_related(
+        &self,
+        from_field: &RelationFieldRef,
+        from_node_ids: &[GraphqlId],
+        query_arguments: &QueryArguments,
+    ) -> Vec<(GraphqlId, GraphqlId)> {
+        let model = from_field.related_model();
+        let order_by = &query_arguments.order_by;
+        let reverse = query_arguments.last.is_some();
+
+        let mut parent_ids: Vec<&GraphqlId> = Vec::new();
+        for id in from_node_ids {
+            if !parent_ids.contains(&id) {
+                parent_ids.push(id);
+            }
+        }
+
+        let mut rows: Vec<(GraphqlId, Row)> = parent_ids
+            .into_iter()
+            .flat_map(|parent_id| {
+                self.select(&model, self.related_ids(from_field, parent_id), query_arguments)
+                    .into_iter()
+                    .map(move |row| (parent_id.clone(), row))
+            })
+            .collect();
+
+        if !query_arguments.is_with_pagination() {
+            rows.sort_by(|(_, a), (_, b)| compare_rows(&model, order_by, reverse, a, b));
+
+            return rows.into_iter().map(|(parent_id, row)| (parent_id, row.id)).collect();
+        }
+
+        rows.sort_by(|(parent_a, a), (parent_b, b)| {
+            Key::from(parent_a)
+                .order(&Key::from(parent_b))
+                .then_with(|| compare_rows(&model, order_by, reverse, a, b))
+        });
+
+        let (start, end) = query_arguments.window_limits();
+        let mut row_number = 0;
+        let mut previous_parent: Option<GraphqlId> = None;
+        let mut result = Vec::new();
+
+        for (parent_id, row) in rows {
+            if previous_parent.as_ref() != Some(&parent_id) {
+                row_number = 0;
+                previous_parent = Some(parent_id.clone());
+            }
+
+            row_number += 1;
+
+            if row_number >= start && row_number <= end {
+                result.push((parent_id, row.id));
+            }
+        }
+
+        result
+    }
+
+    /// The number of records related to each parent, filtered and distinct like in `query_related`,
+    /// leaving out parents without related records.
+    pub(crate) fn count_related(
+        &self,
+        from_field: &RelationFieldRef,
+        from_node_ids: &[GraphqlId],
+        query_arguments: &QueryArguments,
+    ) -> Vec<RelatedNodesCount> {
+        let model = from_field.related_model();
+        let mut counts: Vec<RelatedNodesCount> = Vec::new();
+
+        for parent_id in from_node_ids {
+            if counts.iter().any(|c| &c.parent_id == parent_id) {
+                continue;
+            }
+
+            let count = self
+                .select(&model, self.related_ids(from_field, parent_id), query_arguments)
+                .len();
+
+            if count > 0 {
+                counts.push(RelatedNodesCount {
+                    parent_id: parent_id.clone(),
+                    count,
+                });
+            }
+        }
+
+        counts
+    }
+
+    /// The values of the selected fields of a record, the way the SQL connectors read a row:
+    /// scalar fields first, then the ids of the records related through inlined relations.
+    pub(crate) fn read_node(&self, model: &ModelRef, id: &GraphqlId, selected_fields: &SelectedFields) -> Node {
+        let record = self.record(model, id);
+
+        let mut values: Vec<PrismaValue> = selected_fields
+            .scalar
+            .iter()
+            .filter(|sf| !sf.field.is_list)
+            .map(|sf| match record {
+                Some(record) => field_value(record, &sf.field).clone(),
+                None => PrismaValue::Null,
+            })
+            .collect();
+
+        for rf in selected_fields.relation_inlined() {
+            let related_id = self.related_ids(&rf, id).into_iter().next();
+            values.push(related_id.map(PrismaValue::GraphqlId).unwrap_or(PrismaValue::Null));
+        }
+
+        Node::new(values)
+    }
+
+    /// The candidates matching the filter, distinct and past the cursors, in no particular order.
+    fn select(&self, model: &ModelRef, candidates: Vec<GraphqlId>, query_arguments: &QueryArguments) -> Vec<Row> {
+        let rows: Vec<Row> = candidates
+            .into_iter()
+            .filter(|id| match query_arguments.filter {
+                Some(ref filter) => self.matches(model, id, filter) == Some(true),
+                None => true,
+            })
+            .map(|id| {
+                let keys = self.sort_keys(model, &id, &query_arguments.order_by);
+                Row { id, keys }
+            })
+            .collect();
+
+        let rows = self.distinct(model, rows, query_arguments);
+
+        let after = query_arguments.after.as_ref().map(|cursor| {
+            (
+                CursorType::After,
+                self.sort_keys(model, cursor, &query_arguments.order_by),
+                cursor,
+            )
+        });
+
+        let before = query_arguments.before.as_ref().map(|cursor| {
+            (
+                CursorType::Before,
+                self.sort_keys(model, cursor, &query_arguments.order_by),
+                cursor,
+            )
+        });
+
+        rows.into_iter()
+            .filter(|row| {
+                let past = |cursor: Option<&(CursorType, Vec<Key>, &GraphqlId)>| match cursor {
+                    Some((cursor_type, keys, id)) => past_cursor(model, query_arguments, row, *cursor_type, keys, id),
+                    None => Some(true),
+                };
+
+                filter::and(past(after.as_ref()), past(before.as_ref())) == Some(true)
+            })
+            .collect()
+    }
+
+    /// Only the first record in the order of the query among the records with equal values in
+    /// the `distinct` fields, which takes `NULL` values as equal.
+    fn distinct(&self, model: &ModelRef, rows: Vec<Row>, query_arguments: &QueryArguments) -> Vec<Row> {
+        if query_arguments.distinct.is_empty() {
+            return rows;
+        }
+
+        let mut rows = rows;
+        rows.sort_by(|a, b| compare_rows(model, &query_arguments.order_by, false, a, b));
+
+        let mut seen: Vec<Vec<Key>> = Vec::new();
+
+        rows.into_iter()
+            .filter(|row| {
+                let values: Vec<Key> = query_arguments
+                    .distinct
+                    .iter()
+                    .map(|field| match self.record(model, &row.id) {
+                        Some(record) => Key::from(field_value(record, field)),
+                        None => Key::Null,
+                    })
+                    .collect();
+
+                let is_seen = seen.iter().any(|other| {
+                    other
+                        .iter()
+                        .zip(values.iter())
+                        .all(|(a, b)| a.order(b) == Ordering::Equal)
+                });
+
+                if !is_seen {
+                    seen.push(values);
+                }
+
+                !is_seen
+            })
+            .collect()
+    }
+
+    /// The values of the sort keys of a record. Keys on related models are read through the
+    /// to-one relations of their path, and are `NULL` if a relation is not set. Ranks in a
+    /// full-text search are `NULL` for records not matching the search.
+    fn sort_keys(&self, model: &ModelRef, id: &GraphqlId, order_by: &[OrderBy]) -> Vec<Key> {
+        order_by
+            .iter()
+            .map(|oby| {
+                let mut current = (model.clone(), id.clone());
+
+                for field in oby.path.iter() {
+                    match self.related_ids(field, &current.1).into_iter().next() {
+                        Some(related_id) => current = (field.related_model(), related_id),
+                        None => return Key::Null,
+                    }
+                }
+
+                let value = match self.record(&current.0, &current.1) {
+                    Some(record) => field_value(record, &oby.field),
+                    None => return Key::Null,
+                };
+
+                match oby.search {
+                    Some(ref search) => search::rank(value, search).map(Key::Number).unwrap_or(Key::Null),
+                    None => Key::from(value),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Whether the sort key is the id of the model itself.
+fn is_id(model: &ModelRef, order_by: &OrderBy) -> bool {
+    order_by.path.is_empty() && order_by.search.is_none() && order_by.field.name == model.fields().id().name
+}
+
+/// Compares two records by their sort keys, followed by their ids as an ascending tie-breaker,
+/// unless the id is one of the keys. `NULL` values come first in ascending order.
+fn compare_rows(model: &ModelRef, order_by: &[OrderBy], reverse: bool, a: &Row, b: &Row) -> Ordering {
+    let directed = |ordering: Ordering, sort_order: SortOrder| match (sort_order, reverse) {
+        (SortOrder::Ascending, false) | (SortOrder::Descending, true) => ordering,
+        (SortOrder::Ascending, true) | (SortOrder::Descending, false) => ordering.reverse(),
+    };
+
+    let by_keys = order_by
+        .iter()
+        .zip(a.keys.iter().zip(b.keys.iter()))
+        .fold(Ordering::Equal, |acc, (oby, (a, b))| {
+            acc.then_with(|| directed(a.order(b), oby.sort_order))
+        });
+
+    if order_by.iter().any(|oby| is_id(model, oby)) {
+        by_keys
+    } else {
+        by_keys.then_with(|| directed(Key::from(&a.id).order(&Key::from(&b.id)), SortOrder::Ascending))
+    }
+}
+
+/// Whether the record comes before or after the cursor record in the order of all sort keys.
+///
+/// The keys are compared lexicographically: a record comes after the cursor if its first key is
+/// past the cursor's, or if it's equal and the remaining keys decide. The id is the last key,
+/// and keys after it never decide, as ids are unique.
+fn past_cursor(
+    model: &ModelRef,
+    query_arguments: &QueryArguments,
+    row: &Row,
+    cursor_type: CursorType,
+    cursor_keys: &[Key],
+    cursor_id: &GraphqlId,
+) -> Truth {
+    let order_by = &query_arguments.order_by;
+
+    let (key_count, id_order) = match order_by.iter().position(|oby| is_id(model, oby)) {
+        Some(position) => (position, order_by[position].sort_order),
+        None => (order_by.len(), SortOrder::Ascending),
+    };
+
+    let compare = |value: &Key, cursor: &Key, sort_order: SortOrder| {
+        value.compare(cursor).map(|ordering| match (cursor_type, sort_order) {
+            (CursorType::Before, SortOrder::Ascending) | (CursorType::After, SortOrder::Descending) => {
+                ordering == Ordering::Less
+            }
+            (CursorType::Before, SortOrder::Descending) | (CursorType::After, SortOrder::Ascending) => {
+                ordering == Ordering::Greater
+            }
+        })
+    };
+
+    let id_condition = compare(&Key::from(&row.id), &Key::from(cursor_id), id_order);
+
+    (0..key_count).rev().fold(id_condition, |acc, index| {
+        let (value, cursor) = (&row.keys[index], &cursor_keys[index]);
+        let tie = filter::and(value.compare(cursor).map(|ordering| ordering == Ordering::Equal), acc);
+
+        filter::or(tie, compare(value, cursor, order_by[index].sort_order))
+    })
+}
@@ -0,0 +1,87 @@
+use super::database::Database;
+use crate::InMemory;
+use connector::{
+    error::*,
+    filter::{Filter, NodeSelector},
+    ConnectorResult, QueryArguments,
+};
+use prisma_models::*;
+use std::sync::Arc;
+
+impl InMemory {
+    pub(crate) fn ids_for<T>(db: &Database, model: ModelRef, query_arguments: T) -> Vec<GraphqlId>
+    where
+        T: Into<QueryArguments>,
+    {
+        db.query(&model, &query_arguments.into())
+    }
+
+    pub(crate) fn id_for(db: &Database, node_selector: &NodeSelector) -> ConnectorResult<GraphqlId> {
+        let model = node_selector.field.model();
+        let opt_id = Self::ids_for(db, model, node_selector.clone()).into_iter().next();
+
+        opt_id.ok_or_else(|| ConnectorError::NodeNotFoundForWhere(NodeSelectorInfo::from(node_selector)))
+    }
+
+    pub(crate) fn find_node(db: &Database, node_selector: &NodeSelector) -> ConnectorResult<SingleNode> {
+        let model = node_selector.field.model();
+        let selected_fields = SelectedFields::from(Arc::clone(&model));
+        let id = Self::id_for(db, node_selector)?;
+
+        let node = db.read_node(&model, &id, &selected_fields);
+
+        Ok(SingleNode::new(node, selected_fields.names()))
+    }
+
+    pub(crate) fn get_id_by_parent(
+        db: &Database,
+        parent_field: RelationFieldRef,
+        parent_id: &GraphqlId,
+        selector: &Option<NodeSelector>,
+    ) -> ConnectorResult<GraphqlId> {
+        let ids = Self::get_ids_by_parents(db, Arc::clone(&parent_field), vec![parent_id], selector.clone());
+
+        let id = ids
+            .into_iter()
+            .next()
+            .ok_or_else(|| ConnectorError::NodesNotConnected {
+                relation_name: parent_field.relation().name.clone(),
+                parent_name: parent_field.model().name.clone(),
+                parent_where: None,
+                child_name: parent_field.related_model().name.clone(),
+                child_where: selector.as_ref().map(NodeSelectorInfo::from),
+            })?;
+
+        Ok(id)
+    }
+
+    /// The ids of the children of the parents matching the optional filter, each child once.
+    pub(crate) fn get_ids_by_parents<T>(
+        db: &Database,
+        parent_field: RelationFieldRef,
+        parent_ids: Vec<&GraphqlId>,
+        selector: Option<T>,
+    ) -> Vec<GraphqlId>
+    where
+        T: Into<Filter>,
+    {
+        let related_model = parent_field.related_model();
+        let filter: Option<Filter> = selector.map(Into::into);
+        let mut ids: Vec<GraphqlId> = Vec::new();
+
+        for parent_id in parent_ids {
+            for child_id in db.related_ids(&parent_field, parent_id) {
+                let matches = match filter {
+                    Some(ref filter) => db.matches(&related_model, &child_id, filter) == Some(true),
+                    None => db.record(&related_model, &child_id).is_some(),
+                };
+
+                if matches && !ids.contains(&child_id) {
+                    ids.push(child_id);
+                }
+            }
+        }
+
+        ids
+    }
+}
@@ -0,0 +1,168 @@
+use crate::InMemory;
+use connector::{filter::NodeSelector, *};
+use prisma_models::*;
+
+impl DataResolver for InMemory {
+    fn get_node_by_where(
+        &self,
+        node_selector: &NodeSelector,
+        selected_fields: &SelectedFields,
+    ) -> ConnectorResult<Option<SingleNode>> {
+        let model = node_selector.field.model();
+        let db_name = &model.schema().db_name;
+        let field_names = selected_fields.names();
+
+        let node = self.with_database(db_name, |db| {
+            let id = Self::ids_for(db, model.clone(), node_selector.clone())
+                .into_iter()
+                .next();
+            Ok(id.map(|id| db.read_node(&model, &id, selected_fields)))
+        })?;
+
+        Ok(node.map(|node| SingleNode { node, field_names }))
+    }
+
+    fn get_nodes(
+        &self,
+        model: ModelRef,
+        query_arguments: QueryArguments,
+        selected_fields: &SelectedFields,
+    ) -> ConnectorResult<ManyNodes> {
+        let db_name = &model.schema().db_name;
+        let field_names = selected_fields.names();
+
+        let nodes = self.with_database(db_name, |db| {
+            let nodes = db
+                .query(&model, &query_arguments)
+                .iter()
+                .map(|id| db.read_node(&model, id, selected_fields))
+                .collect();
+
+            Ok(nodes)
+        })?;
+
+        Ok(ManyNodes { nodes, field_names })
+    }
+
+    fn get_related_nodes(
+        &self,
+        from_field: RelationFieldRef,
+        from_node_ids: &[GraphqlId],
+        query_arguments: QueryArguments,
+        selected_fields: &SelectedFields,
+    ) -> ConnectorResult<ManyNodes> {
+        let db_name = &from_field.model().schema().db_name;
+        let related_model = from_field.related_model();
+        let field_names = selected_fields.names();
+
+        let nodes = self.with_database(db_name, |db| {
+            let nodes = db
+                .query_related(&from_field, from_node_ids, &query_arguments)
+                .into_iter()
+                .map(|(parent_id, id)| {
+                    let mut node = db.read_node(&related_model, &id, selected_fields);
+                    node.add_parent_id(parent_id);
+
+                    node
+                })
+                .collect();
+
+            Ok(nodes)
+        })?;
+
+        Ok(ManyNodes { nodes, field_names })
+    }
+
+    fn count_by_model(&self, model: ModelRef, query_arguments: QueryArguments) -> ConnectorResult<usize> {
+        let db_name = &model.schema().db_name;
+        self.with_database(db_name, |db| Ok(db.query_page(&model, &query_arguments, 0).len()))
+    }
+
+    fn count_related_nodes(
+        &self,
+        from_field: RelationFieldRef,
+        from_node_ids: &[GraphqlId],
+        query_arguments: QueryArguments,
+    ) -> ConnectorResult<Vec<RelatedNodesCount>> {
+        let db_name = &from_field.model().schema().db_name;
+
+        self.with_database(db_name, |db| {
+            Ok(db.count_related(&from_field, from_node_ids, &query_arguments))
+        })
+    }
+
+    /// Tables are found by name in the schema of the database: the tables of models, relation
+    /// tables, the tables of scalar lists, and the models holding inline relations, which count
+    /// their links.
+    fn count_by_table(&self, database: &str, table: &str) -> ConnectorResult<usize> {
+        self.with_database(database, |db| {
+            let schema = match db.schema() {
+                Some(schema) => schema,
+                None => return Ok(0),
+            };
+
+            for relation in schema.relations().iter() {
+                let relation_table = match relation.manifestation {
+                    Some(RelationLinkManifestation::RelationTable(ref m)) => m.table.clone(),
+                    Some(RelationLinkManifestation::Inline(_)) => continue,
+                    None => format!("_{}", relation.name),
+                };
+
+                if relation_table == table {
+                    return Ok(db.link_count(relation));
+                }
+            }
+
+            for model in schema.models().iter() {
+                if model.db_name() == table {
+                    return Ok(db.count(model));
+                }
+
+                for field in model.fields().scalar_list() {
+                    if format!("{}_{}", model.db_name(), field.name) == table {
+                        return Ok(db.list_value_count(&field));
+                    }
+                }
+            }
+
+            Ok(0)
+        })
+    }
+
+    fn aggregate(
+        &self,
+        model: ModelRef,
+        query_arguments: QueryArguments,
+        selection: AggregationSelection,
+    ) -> ConnectorResult<Vec<AggregationRow>> {
+        let db_name = &model.schema().db_name;
+        self.with_database(db_name, |db| Ok(db.aggregate(&model, &query_arguments, &selection)))
+    }
+
+    fn get_scalar_list_values_by_node_ids(
+        &self,
+        list_field: ScalarFieldRef,
+        node_ids: Vec<GraphqlId>,
+    ) -> ConnectorResult<Vec<ScalarListValues>> {
+        let db_name = &list_field.model().schema().db_name;
+
+        self.with_database(db_name, |db| {
+            let mut list_values: Vec<ScalarListValues> = Vec::new();
+
+            for node_id in node_ids {
+                let values = db.list_values(&list_field, &node_id);
+
+                if values.is_empty() || list_values.iter().any(|lv| lv.node_id == node_id) {
+                    continue;
+                }
+
+                list_values.push(ScalarListValues {
+                    node_id,
+                    values: values.to_vec(),
+                });
+            }
+
+            Ok(list_values)
+        })
+    }
+}
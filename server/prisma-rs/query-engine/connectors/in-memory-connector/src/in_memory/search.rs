@@ -0,0 +1,55 @@
+use prisma_models::prelude::*;
+
+/// Whether the text of the value matches all words of the search. Every word is a phrase of the
+/// tokens in it, like a quoted string in an FTS5 query, and a search without words matches
+/// nothing.
+pub(crate) fn matches(value: &PrismaValue, search: &str) -> bool {
+    rank(value, search).is_some()
+}
+
+/// The relevance of the value in the search, lower is better like the `bm25` rank of FTS5.
+/// Values not matching the search have no rank.
+///
+/// The rank is the negative number of phrase occurrences per token of the value, which orders
+/// matches the same way as `bm25` for records of one field.
+pub(crate) fn rank(value: &PrismaValue, search: &str) -> Option<f64> {
+    let text = match value {
+        PrismaValue::String(s) | PrismaValue::Enum(s) | PrismaValue::Json(s) => s,
+        _ => return None,
+    };
+
+    let phrases: Vec<Vec<String>> = search.split_whitespace().map(tokens).collect();
+
+    if phrases.is_empty() {
+        return None;
+    }
+
+    let tokens = tokens(text);
+    let mut occurrences = 0;
+
+    for phrase in phrases.iter() {
+        if phrase.is_empty() {
+            return None;
+        }
+
+        match tokens
+            .windows(phrase.len())
+            .filter(|window| *window == &phrase[..])
+            .count()
+        {
+            0 => return None,
+            count => occurrences += count,
+        }
+    }
+
+    Some(-(occurrences as f64) / tokens.len() as f64)
+}
+
+/// The tokens of the text like the `unicode61` tokenizer makes them: runs of alphanumeric
+/// characters, folded to lower case.
+fn tokens(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(|token| token.to_lowercase())
+        .collect()
+}
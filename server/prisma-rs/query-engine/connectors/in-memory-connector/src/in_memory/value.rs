@@ -0,0 +1,122 @@
+use chrono::{TimeZone, Utc};
+use prisma_models::prelude::*;
+use std::cmp::Ordering;
+use uuid::Uuid;
+
+/// A value the way SQLite stores and compares it. Booleans and dates are numbers, while ids,
+/// UUIDs, enums and JSON documents are text. `NULL` sorts before numbers, and numbers before
+/// text.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Key {
+    Null,
+    Number(f64),
+    Text(String),
+}
+
+impl Key {
+    pub(crate) fn is_null(&self) -> bool {
+        *self == Key::Null
+    }
+
+    /// The order of `ORDER BY`, which takes `NULL` values as equal.
+    pub(crate) fn order(&self, other: &Key) -> Ordering {
+        match (self, other) {
+            (Key::Null, Key::Null) => Ordering::Equal,
+            (Key::Null, _) => Ordering::Less,
+            (_, Key::Null) => Ordering::Greater,
+            (Key::Number(a), Key::Number(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+            (Key::Number(_), Key::Text(_)) => Ordering::Less,
+            (Key::Text(_), Key::Number(_)) => Ordering::Greater,
+            (Key::Text(a), Key::Text(b)) => a.cmp(b),
+        }
+    }
+
+    /// The comparison in a condition, which is unknown if either value is `NULL`.
+    pub(crate) fn compare(&self, other: &Key) -> Option<Ordering> {
+        if self.is_null() || other.is_null() {
+            None
+        } else {
+            Some(self.order(other))
+        }
+    }
+
+    /// The text string functions like `instr` read from the value.
+    pub(crate) fn text(&self) -> Option<String> {
+        match self {
+            Key::Null => None,
+            Key::Number(n) if n.fract() == 0.0 && n.abs() < 1e15 => Some(format!("{}", *n as i64)),
+            Key::Number(n) => Some(format!("{}", n)),
+            Key::Text(s) => Some(s.clone()),
+        }
+    }
+
+    /// Folds the case of ASCII characters like SQLite's `lower`.
+    pub(crate) fn lowercase(self) -> Key {
+        match self.text() {
+            Some(text) => Key::Text(text.to_ascii_lowercase()),
+            None => Key::Null,
+        }
+    }
+}
+
+impl From<&PrismaValue> for Key {
+    fn from(value: &PrismaValue) -> Key {
+        match value {
+            PrismaValue::String(s) | PrismaValue::Enum(s) | PrismaValue::Json(s) => Key::Text(s.clone()),
+            PrismaValue::Float(f) => Key::Number(*f),
+            PrismaValue::Boolean(b) => Key::Number(if *b { 1.0 } else { 0.0 }),
+            PrismaValue::DateTime(d) => Key::Number(d.timestamp_millis() as f64),
+            PrismaValue::Int(i) => Key::Number(f64::from(*i)),
+            PrismaValue::Relation(i) => Key::Number(*i as f64),
+            PrismaValue::Uuid(u) => Key::Text(u.to_hyphenated_ref().to_string()),
+            PrismaValue::GraphqlId(id) => Key::from(id),
+            PrismaValue::Null | PrismaValue::List(_) => Key::Null,
+        }
+    }
+}
+
+impl From<&GraphqlId> for Key {
+    fn from(id: &GraphqlId) -> Key {
+        match id {
+            GraphqlId::String(s) => Key::Text(s.clone()),
+            GraphqlId::Int(i) => Key::Number(*i as f64),
+            GraphqlId::UUID(u) => Key::Text(u.to_hyphenated_ref().to_string()),
+        }
+    }
+}
+
+/// Ids the way the SQL connectors read them back, with UUIDs as strings.
+pub(crate) fn id(id: GraphqlId) -> GraphqlId {
+    match id {
+        GraphqlId::UUID(u) => GraphqlId::String(u.to_hyphenated_ref().to_string()),
+        id => id,
+    }
+}
+
+/// The value of a field the way the SQL connectors read it back after writing it: ids as
+/// `GraphqlId`s, UUIDs as `Uuid`s and dates with millisecond precision.
+pub(crate) fn stored(type_identifier: TypeIdentifier, value: PrismaValue) -> PrismaValue {
+    match (type_identifier, value) {
+        (_, PrismaValue::Null) => PrismaValue::Null,
+        (TypeIdentifier::GraphQLID, value) | (TypeIdentifier::Relation, value) => match value {
+            PrismaValue::String(s) => PrismaValue::GraphqlId(GraphqlId::String(s)),
+            PrismaValue::Int(i) => PrismaValue::GraphqlId(GraphqlId::Int(i as usize)),
+            PrismaValue::Uuid(u) => PrismaValue::GraphqlId(id(GraphqlId::UUID(u))),
+            PrismaValue::GraphqlId(gid) => PrismaValue::GraphqlId(id(gid)),
+            value => value,
+        },
+        (TypeIdentifier::UUID, PrismaValue::String(s))
+        | (TypeIdentifier::UUID, PrismaValue::GraphqlId(GraphqlId::String(s))) => match Uuid::parse_str(&s) {
+            Ok(uuid) => PrismaValue::Uuid(uuid),
+            Err(_) => PrismaValue::String(s),
+        },
+        (TypeIdentifier::UUID, PrismaValue::GraphqlId(GraphqlId::UUID(u))) => PrismaValue::Uuid(u),
+        (TypeIdentifier::Enum, PrismaValue::String(s)) => PrismaValue::Enum(s),
+        (TypeIdentifier::Json, PrismaValue::String(s)) => PrismaValue::Json(s),
+        (TypeIdentifier::Float, PrismaValue::Int(i)) => PrismaValue::Float(f64::from(i)),
+        (TypeIdentifier::DateTime, PrismaValue::DateTime(d)) => {
+            PrismaValue::DateTime(Utc.timestamp_millis(d.timestamp_millis()))
+        }
+        (_, value) => value,
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{
+    in_memory::{
+        database::{Database, Record},
+        nested_actions::NestedActions,
+        value,
+    },
+    InMemory,
+};
+use connector::ConnectorResult;
+use prisma_models::{GraphqlId, ModelRef, PrismaArgs, PrismaListValue, PrismaValue, RelationFieldRef, TypeIdentifier};
+
+impl InMemory {
+    /// Creates a record with the id from the arguments, or a generated one. Ids of models with
+    /// ids generated by the database count up from 1.
+    pub(crate) fn execute_create<T>(
+        db: &mut Database,
+        model: ModelRef,
+        non_list_args: &PrismaArgs,
+        list_args: &[(T, PrismaListValue)],
+    ) -> ConnectorResult<GraphqlId>
+    where
+        T: AsRef<str>,
+    {
+        let model_id = model.fields().id();
+
+        let id = match non_list_args.get_field_value(&model_id.name).cloned() {
+            Some(PrismaValue::Null) | None if model_id.is_auto_generated => db.next_id(&model),
+            Some(PrismaValue::Null) | None => model.generate_id(),
+            Some(value) => match value::stored(TypeIdentifier::GraphQLID, value) {
+                PrismaValue::GraphqlId(id) => id,
+                _ => model.generate_id(),
+            },
+        };
+
+        let values: Record = non_list_args
+            .args
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+
+        let id = db.insert(&model, id, values)?;
+
+        // Arguments for relation fields set the column of an inline relation.
+        for rf in model.fields().relation() {
+            if let Some(value) = non_list_args.get_field_value(&rf.name) {
+                if let PrismaValue::GraphqlId(related_id) = value::stored(TypeIdentifier::GraphQLID, value.clone()) {
+                    db.link(&rf, &id, &related_id);
+                }
+            }
+        }
+
+        for (field_name, list_value) in list_args {
+            let field = model.fields().find_from_scalar(field_name.as_ref()).unwrap();
+            db.set_list_values(&field, &id, list_value);
+        }
+
+        Ok(id)
+    }
+
+    pub(crate) fn execute_nested_create<T>(
+        db: &mut Database,
+        parent_id: &GraphqlId,
+        actions: &NestedActions,
+        relation_field: RelationFieldRef,
+        non_list_args: &PrismaArgs,
+        list_args: &[(T, PrismaListValue)],
+    ) -> ConnectorResult<GraphqlId>
+    where
+        T: AsRef<str>,
+    {
+        actions.required_check(db, parent_id)?;
+
+        if actions.parent_removal() {
+            db.unlink_parent(&relation_field, parent_id);
+        }
+
+        let id = Self::execute_create(db, relation_field.related_model(), non_list_args, list_args)?;
+        db.link(&relation_field, parent_id, &id);
+
+        Ok(id)
+    }
+}
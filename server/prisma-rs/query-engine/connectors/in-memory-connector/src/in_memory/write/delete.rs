@@ -0,0 +1,122 @@
+use crate::{
+    in_memory::{database::Database, nested_actions::NestedActions},
+    InMemory,
+};
+use connector::{
+    error::{ConnectorError, NodeSelectorInfo},
+    filter::{Filter, NodeSelector},
+    ConnectorResult,
+};
+use prisma_models::{GraphqlId, ModelRef, RelationFieldRef, SingleNode};
+use std::sync::Arc;
+
+impl InMemory {
+    pub(crate) fn execute_delete(db: &mut Database, node_selector: &NodeSelector) -> ConnectorResult<SingleNode> {
+        let model = node_selector.field.model();
+        let node = Self::find_node(db, node_selector)?;
+
+        let id = node.get_id_value(Arc::clone(&model)).unwrap().clone();
+
+        Self::check_relation_violations(db, Arc::clone(&model), &[&id])?;
+        db.remove(&model, &[&id]);
+
+        Ok(node)
+    }
+
+    pub(crate) fn execute_delete_many(db: &mut Database, model: ModelRef, filter: &Filter) -> ConnectorResult<usize> {
+        let ids = Self::ids_for(db, Arc::clone(&model), filter.clone());
+        let ids: Vec<&GraphqlId> = ids.iter().map(|id| &*id).collect();
+        let count = ids.len();
+
+        Self::check_relation_violations(db, Arc::clone(&model), ids.as_slice())?;
+        db.remove(&model, ids.as_slice());
+
+        Ok(count)
+    }
+
+    pub(crate) fn execute_nested_delete(
+        db: &mut Database,
+        parent_id: &GraphqlId,
+        actions: &NestedActions,
+        node_selector: &Option<NodeSelector>,
+        relation_field: RelationFieldRef,
+    ) -> ConnectorResult<()> {
+        if let Some(ref node_selector) = node_selector {
+            Self::id_for(db, node_selector)?;
+        };
+
+        let child_id =
+            Self::get_id_by_parent(db, Arc::clone(&relation_field), parent_id, node_selector).map_err(|e| match e {
+                ConnectorError::NodesNotConnected {
+                    relation_name,
+                    parent_name,
+                    parent_where: _,
+                    child_name,
+                    child_where,
+                } => {
+                    let model = Arc::clone(&relation_field.model());
+
+                    ConnectorError::NodesNotConnected {
+                        relation_name: relation_name,
+                        parent_name: parent_name,
+                        parent_where: Some(NodeSelectorInfo::for_id(model, parent_id)),
+                        child_name: child_name,
+                        child_where: child_where,
+                    }
+                }
+                e => e,
+            })?;
+
+        actions.ensure_connected(db, parent_id, &child_id)?;
+
+        let related_model = relation_field.related_model();
+
+        Self::check_relation_violations(db, Arc::clone(&related_model), &[&child_id])?;
+        db.remove(&related_model, &[&child_id]);
+
+        Ok(())
+    }
+
+    /// Deletes the matching children of the parent, checking the relations of the children.
+    pub(crate) fn execute_nested_delete_many(
+        db: &mut Database,
+        parent_id: &GraphqlId,
+        filter: &Option<Filter>,
+        relation_field: RelationFieldRef,
+    ) -> ConnectorResult<usize> {
+        let ids = Self::get_ids_by_parents(db, Arc::clone(&relation_field), vec![parent_id], filter.clone());
+        let count = ids.len();
+
+        let ids: Vec<&GraphqlId> = ids.iter().map(|id| &*id).collect();
+        let related_model = relation_field.related_model();
+
+        Self::check_relation_violations(db, Arc::clone(&related_model), ids.as_slice())?;
+        db.remove(&related_model, ids.as_slice());
+
+        Ok(count)
+    }
+
+    /// Empties the database, including the sequences of generated ids.
+    pub(crate) fn execute_reset_data(db: &mut Database) {
+        *db = Database::default();
+    }
+
+    /// A record can't be deleted while a record of a model requiring it is linked to it.
+    fn check_relation_violations(db: &Database, model: ModelRef, ids: &[&GraphqlId]) -> ConnectorResult<()> {
+        for rf in model.schema().fields_requiring_model(Arc::clone(&model)) {
+            let related_field = rf.related_field();
+
+            if ids.iter().any(|id| db.is_linked(&related_field, id)) {
+                let relation = rf.relation();
+
+                return Err(ConnectorError::RelationViolation {
+                    relation_name: relation.name.clone(),
+                    model_a_name: relation.model_a().name.clone(),
+                    model_b_name: relation.model_b().name.clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{
+    in_memory::{database::Database, nested_actions::NestedActions},
+    InMemory,
+};
+use connector::{filter::NodeSelector, ConnectorResult};
+use prisma_models::{GraphqlId, RelationFieldRef};
+
+impl InMemory {
+    pub(crate) fn execute_connect(
+        db: &mut Database,
+        parent_id: &GraphqlId,
+        actions: &NestedActions,
+        node_selector: &NodeSelector,
+        relation_field: RelationFieldRef,
+    ) -> ConnectorResult<()> {
+        actions.required_check(db, parent_id)?;
+
+        let child_id = Self::id_for(db, node_selector)?;
+
+        if actions.parent_removal() {
+            db.unlink_parent(&relation_field, parent_id);
+        }
+
+        if actions.child_removal() {
+            db.unlink_child(&relation_field, &child_id);
+        }
+
+        db.link(&relation_field, parent_id, &child_id);
+
+        Ok(())
+    }
+
+    pub(crate) fn execute_disconnect(
+        db: &mut Database,
+        parent_id: &GraphqlId,
+        actions: &NestedActions,
+        node_selector: &Option<NodeSelector>,
+    ) -> ConnectorResult<()> {
+        actions.required_check(db, parent_id)?;
+
+        let relation_field = actions.relation_field();
+
+        match node_selector {
+            None => {
+                actions.ensure_parent_is_connected(db, parent_id)?;
+                db.unlink_parent(&relation_field, parent_id);
+            }
+            Some(ref selector) => {
+                let child_id = Self::id_for(db, selector)?;
+
+                actions.ensure_connected(db, parent_id, &child_id)?;
+                db.unlink(&relation_field, parent_id, &child_id);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn execute_set(
+        db: &mut Database,
+        parent_id: &GraphqlId,
+        actions: &NestedActions,
+        node_selectors: &Vec<NodeSelector>,
+        relation_field: RelationFieldRef,
+    ) -> ConnectorResult<()> {
+        actions.required_check(db, parent_id)?;
+
+        db.unlink_parent(&relation_field, parent_id);
+
+        for selector in node_selectors {
+            let child_id = Self::id_for(db, selector)?;
+
+            if !relation_field.is_list {
+                db.unlink_child(&relation_field, &child_id);
+            }
+
+            db.link(&relation_field, parent_id, &child_id);
+        }
+
+        Ok(())
+    }
+}
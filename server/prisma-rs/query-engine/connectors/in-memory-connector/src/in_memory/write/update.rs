@@ -0,0 +1,162 @@
+use crate::{
+    in_memory::{
+        database::{Database, Record},
+        json,
+    },
+    InMemory,
+};
+use connector::{
+    error::ConnectorError,
+    filter::{Filter, NodeSelector},
+    mutaction::JsonUpdate,
+    ConnectorResult,
+};
+use prisma_models::{GraphqlId, ModelRef, PrismaArgs, PrismaListValue, PrismaValue, RelationFieldRef};
+use std::sync::Arc;
+
+impl InMemory {
+    pub(crate) fn execute_update<T>(
+        db: &mut Database,
+        node_selector: &NodeSelector,
+        non_list_args: &PrismaArgs,
+        list_args: &[(T, PrismaListValue)],
+        json_updates: &[JsonUpdate],
+    ) -> ConnectorResult<GraphqlId>
+    where
+        T: AsRef<str>,
+    {
+        let model = node_selector.field.model();
+        let id = Self::id_for(db, node_selector)?;
+
+        Self::update_nodes(db, &model, &[id.clone()], non_list_args, json_updates)?;
+        Self::update_list_args(db, &[id.clone()], Arc::clone(&model), list_args);
+
+        Ok(id)
+    }
+
+    pub(crate) fn execute_update_many<T>(
+        db: &mut Database,
+        model: ModelRef,
+        filter: &Filter,
+        non_list_args: &PrismaArgs,
+        list_args: &[(T, PrismaListValue)],
+    ) -> ConnectorResult<usize>
+    where
+        T: AsRef<str>,
+    {
+        let ids = Self::ids_for(db, Arc::clone(&model), filter.clone());
+        let count = ids.len();
+
+        Self::update_nodes(db, &model, ids.as_slice(), non_list_args, &[])?;
+        Self::update_list_args(db, ids.as_slice(), Arc::clone(&model), list_args);
+
+        Ok(count)
+    }
+
+    pub(crate) fn execute_nested_update<T>(
+        db: &mut Database,
+        parent_id: &GraphqlId,
+        node_selector: &Option<NodeSelector>,
+        relation_field: RelationFieldRef,
+        non_list_args: &PrismaArgs,
+        list_args: &[(T, PrismaListValue)],
+        json_updates: &[JsonUpdate],
+    ) -> ConnectorResult<GraphqlId>
+    where
+        T: AsRef<str>,
+    {
+        if let Some(ref node_selector) = node_selector {
+            Self::id_for(db, node_selector)?;
+        };
+
+        let id = Self::get_id_by_parent(db, Arc::clone(&relation_field), parent_id, node_selector)?;
+
+        let node_selector = NodeSelector::from((relation_field.related_model().fields().id(), id));
+        Self::execute_update(db, &node_selector, non_list_args, list_args, json_updates)
+    }
+
+    pub(crate) fn execute_nested_update_many<T>(
+        db: &mut Database,
+        parent_id: &GraphqlId,
+        filter: &Option<Filter>,
+        relation_field: RelationFieldRef,
+        non_list_args: &PrismaArgs,
+        list_args: &[(T, PrismaListValue)],
+    ) -> ConnectorResult<usize>
+    where
+        T: AsRef<str>,
+    {
+        let related_model = relation_field.related_model();
+        let ids = Self::get_ids_by_parents(db, Arc::clone(&relation_field), vec![parent_id], filter.clone());
+        let count = ids.len();
+
+        Self::update_nodes(db, &related_model, ids.as_slice(), non_list_args, &[])?;
+        Self::update_list_args(db, ids.as_slice(), related_model, list_args);
+
+        Ok(count)
+    }
+
+    /// Replaces the values of the list fields in the arguments for all records.
+    pub(crate) fn update_list_args<T>(
+        db: &mut Database,
+        ids: &[GraphqlId],
+        model: ModelRef,
+        list_args: &[(T, PrismaListValue)],
+    ) where
+        T: AsRef<str>,
+    {
+        for (field_name, list_value) in list_args {
+            let field = model.fields().find_from_scalar(field_name.as_ref()).unwrap();
+
+            for id in ids {
+                db.set_list_values(&field, id, list_value);
+            }
+        }
+    }
+
+    /// Sets the scalar arguments and applies the JSON updates. Like in an SQL `UPDATE`, every
+    /// JSON update reads the document from before the update.
+    fn update_nodes(
+        db: &mut Database,
+        model: &ModelRef,
+        ids: &[GraphqlId],
+        args: &PrismaArgs,
+        json_updates: &[JsonUpdate],
+    ) -> ConnectorResult<()> {
+        if (args.args.is_empty() && json_updates.is_empty()) || ids.is_empty() {
+            return Ok(());
+        }
+
+        for (name, value) in args.args.iter() {
+            let field = model.fields().find_from_scalar(&name)?;
+
+            if field.is_required && value.is_null() {
+                return Err(ConnectorError::FieldCannotBeNull {
+                    field: field.name.clone(),
+                });
+            }
+        }
+
+        for id in ids {
+            let mut values: Record = args
+                .args
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect();
+
+            for update in json_updates {
+                let column = db
+                    .record(model, id)
+                    .and_then(|record| record.get(&update.field.name))
+                    .cloned()
+                    .unwrap_or(PrismaValue::Null);
+
+                values.insert(update.field.name.clone(), json::update(&column, &update.operation)?);
+            }
+
+            db.update(model, id, values)?;
+        }
+
+        Ok(())
+    }
+}
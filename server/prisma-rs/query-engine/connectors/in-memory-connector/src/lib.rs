@@ -0,0 +1,17 @@
+//! # The in-memory Connector interface
+//!
+//! Implements [DataResolver](../connector/trait.DataResolver.html) and
+//! [DatabaseMutactionExecutor](../connector/trait.DatabaseMutactionExecutor.html) over records
+//! held in Rust data structures, see [InMemory](struct.InMemory.html).
+//!
+//! Filters, pagination, orderings and the relation checks of nested mutactions are evaluated
+//! directly on the records, with the semantics of the [SQLite connector](../sqlite_connector/index.html):
+//! values compare like in SQLite, conditions on `NULL` are unknown instead of false, and the
+//! results come in the same order. Nothing touches the disk, which makes the connector fast to
+//! set up in tests, and a reference to compare the other connectors to.
+//!
+//! The server and the native bridge only offer it as the `in-memory` connector when built with
+//! their `in-memory` feature.
+mod in_memory;
+
+pub use in_memory::*;
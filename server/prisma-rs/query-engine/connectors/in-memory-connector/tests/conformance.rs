@@ -0,0 +1,3 @@
+use in_memory_connector::InMemory;
+
+connector_test::conformance_tests!(InMemory::new());
@@ -0,0 +1,19 @@
+//! # The MySQL Connector interface
+//!
+//! Implements [DataResolver](../connector/trait.DataResolver.html) and
+//! [DatabaseMutactionExecutor](../connector/trait.DatabaseMutactionExecutor.html) for
+//! [MySQL](struct.Mysql.html).
+//!
+//! The queries are built with the same builders as the
+//! [SQLite connector](../sqlite_connector/index.html), which also reads and writes the records
+//! for every [SqlDatabase](../sqlite_connector/trait.SqlDatabase.html). A
+//! [MysqlTransaction](struct.MysqlTransaction.html) renders them with the MySQL visitor of
+//! `prisma_query`. Every Prisma database is a MySQL database of the same name. Generated ids are
+//! read from the id of the last inserted row, as MySQL has no `RETURNING`.
+//!
+//! MySQL 5.7 has no window functions, so paginated related nodes are read with a query per
+//! parent, and queries with `distinct` are rejected. Full-text search and the JSON filters and updates
+//! depend on SQLite functions, and are not supported yet.
+mod mysql;
+
+pub use crate::mysql::*;
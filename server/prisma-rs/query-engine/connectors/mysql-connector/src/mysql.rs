@@ -0,0 +1,240 @@
+mod mutaction_executor;
+mod read;
+mod resolver;
+mod transaction;
+
+pub use transaction::*;
+
+use chrono::{DateTime, Utc};
+use connector::{error::ConnectorError, ConnectorResult, QueryArguments};
+use failure::err_msg;
+use mysql_client::{prelude::FromValue, OptsBuilder, Params, Row, Value};
+use prisma_common::config::{ConnectionLimit, ExplicitConfig};
+use prisma_models::prelude::*;
+use prisma_query::ast::ParameterizedValue;
+use r2d2_mysql::MysqlConnectionManager;
+use sqlite_connector::SqlDatabase;
+use std::cell::RefCell;
+use uuid::Uuid;
+
+type Pool = r2d2::Pool<MysqlConnectionManager>;
+pub type Connection = r2d2::PooledConnection<MysqlConnectionManager>;
+
+/// Statements of the driver need a mutable transaction, while the shared write logic passes it
+/// around immutably, like the transactions of the other connectors.
+pub type Transaction<'a> = RefCell<mysql_client::Transaction<'a>>;
+
+pub struct Mysql {
+    pool: Pool,
+
+    /// `ROW_NUMBER()` is only available from MySQL 8 and MariaDB 10.2 on.
+    supports_window_functions: bool,
+}
+
+impl SqlDatabase for Mysql {}
+
+impl Mysql {
+    /// Creates a pool of at most `ConnectionLimit::limit()` connections to the server of the
+    /// config.
+    ///
+    /// ```rust,no_run
+    /// # use mysql_connector::*;
+    /// # use prisma_common::config::ExplicitConfig;
+    /// let config: ExplicitConfig = serde_yaml::from_str(
+    ///     "connector: mysql-native\nhost: localhost\nport: 3306\nuser: root\npassword: prisma",
+    /// ).unwrap();
+    ///
+    /// let mysql = Mysql::new(&config).unwrap();
+    /// let databases = mysql.with_connection("mysql", |conn| Ok(conn.query("SHOW DATABASES")?.count()));
+    ///
+    /// assert!(databases.unwrap() > 0);
+    /// ```
+    pub fn new(config: &ExplicitConfig) -> ConnectorResult<Mysql> {
+        if config.ssl == Some(true) {
+            return Err(ConnectorError::InvalidConnectionArguments);
+        }
+
+        let mut opts = OptsBuilder::new();
+
+        opts.ip_or_hostname(Some(config.host.as_str()))
+            .tcp_port(config.port)
+            .user(Some(config.user.as_str()))
+            .pass(config.password.as_ref().map(String::as_str));
+
+        let manager = MysqlConnectionManager::new(opts);
+        let pool = r2d2::Pool::builder().max_size(config.limit()).build(manager)?;
+
+        let version: String = {
+            let mut conn = pool.get()?;
+            let row = conn.query("SELECT VERSION()")?.next();
+
+            match row {
+                Some(row) => Self::get(&row?, 0)?.unwrap_or_default(),
+                None => String::new(),
+            }
+        };
+
+        Ok(Mysql {
+            pool,
+            supports_window_functions: Self::supports_window_functions(&version),
+        })
+    }
+
+    /// Versions look like `5.7.26-log` or `10.3.15-MariaDB`.
+    fn supports_window_functions(version: &str) -> bool {
+        let mut numbers = version
+            .split(|c: char| !c.is_ascii_digit())
+            .map(|number| number.parse::<u32>().unwrap_or(0));
+
+        let major = numbers.next().unwrap_or(0);
+        let minor = numbers.next().unwrap_or(0);
+
+        if version.contains("MariaDB") {
+            (major, minor) >= (10, 2)
+        } else {
+            major >= 8
+        }
+    }
+
+    /// Takes a connection from the pool, resolving unqualified tables, like the relation tables,
+    /// in the given database.
+    pub fn with_connection<F, T>(&self, db_name: &str, f: F) -> ConnectorResult<T>
+    where
+        F: FnOnce(&mut Connection) -> ConnectorResult<T>,
+    {
+        let mut conn = self.pool.get()?;
+        conn.query(format!("USE {}", Self::quote(db_name)))?;
+
+        f(&mut conn)
+    }
+
+    /// Takes a connection and starts a transaction, that is committed if the given `F` was
+    /// successful and rolled back otherwise.
+    pub fn with_transaction<F, T>(&self, db_name: &str, f: F) -> ConnectorResult<T>
+    where
+        F: FnOnce(&MysqlTransaction) -> ConnectorResult<T>,
+    {
+        self.with_connection(db_name, |conn| {
+            let tx = MysqlTransaction::new(conn.start_transaction(false, None, None)?);
+            let result = f(&tx);
+
+            if result.is_ok() {
+                tx.commit()?;
+            }
+
+            result
+        })
+    }
+
+    /// Runs `F` without foreign key checks, which are turned on again even if `F` fails, as the
+    /// connection goes back to the pool.
+    pub(crate) fn without_foreign_key_checks<F, T>(conn: &Transaction, f: F) -> ConnectorResult<T>
+    where
+        F: FnOnce() -> ConnectorResult<T>,
+    {
+        conn.borrow_mut().query("SET FOREIGN_KEY_CHECKS = 0")?;
+        let result = f();
+        conn.borrow_mut().query("SET FOREIGN_KEY_CHECKS = 1")?;
+
+        result
+    }
+
+    /// `distinct` picks the first record of every group with `ROW_NUMBER()`, so it can't be
+    /// read from servers without window functions.
+    pub(crate) fn check_distinct(&self, query_arguments: &QueryArguments) -> ConnectorResult<()> {
+        if query_arguments.distinct.is_empty() || self.supports_window_functions {
+            Ok(())
+        } else {
+            Err(ConnectorError::QueryError(err_msg(
+                "`distinct` is only supported from MySQL 8 and MariaDB 10.2 on",
+            )))
+        }
+    }
+
+    /// Quotes an identifier, like a database or column name.
+    pub fn quote(identifier: &str) -> String {
+        format!("`{}`", identifier.replace('`', "``"))
+    }
+
+    fn parameters(params: Vec<ParameterizedValue>) -> Params {
+        Params::Positional(params.into_iter().map(Value::from).collect())
+    }
+
+    /// An integer, which can be any integer column or an aggregation.
+    pub fn fetch_int(row: &Row, i: usize) -> ConnectorResult<Option<i64>> {
+        Self::get(row, i)
+    }
+
+    pub fn fetch_id(row: &Row) -> ConnectorResult<GraphqlId> {
+        Self::fetch_id_at(row, 0)
+    }
+
+    pub fn fetch_id_at(row: &Row, i: usize) -> ConnectorResult<GraphqlId> {
+        Self::fetch_optional_id(row, i)?.ok_or_else(|| ConnectorError::ColumnReadFailure(err_msg("Id column is null")))
+    }
+
+    /// Ids are either strings or generated integers.
+    fn fetch_optional_id(row: &Row, i: usize) -> ConnectorResult<Option<GraphqlId>> {
+        match row.as_ref(i) {
+            Some(Value::Int(id)) => Ok(Some(GraphqlId::Int(*id as usize))),
+            Some(Value::UInt(id)) => Ok(Some(GraphqlId::Int(*id as usize))),
+            _ => Ok(Self::get::<String>(row, i)?.map(GraphqlId::String)),
+        }
+    }
+
+    /// Read and cast a `Row` into a `Record`, casting the columns from the
+    /// `DataModel` definitions.
+    pub fn read_row(row: &Row, selected_fields: &SelectedFields) -> ConnectorResult<Node> {
+        let mut fields = Vec::new();
+
+        for (i, typid) in selected_fields.type_identifiers().iter().enumerate() {
+            fields.push(Self::fetch_value(*typid, &row, i)?);
+        }
+
+        Ok(Node::new(fields))
+    }
+
+    /// Reads a column into a `PrismaValue`. Values are written the same way as in SQLite, so
+    /// dates are milliseconds and UUIDs are strings.
+    pub fn fetch_value(typ: TypeIdentifier, row: &Row, i: usize) -> ConnectorResult<PrismaValue> {
+        let value = match typ {
+            TypeIdentifier::String => Self::get(row, i)?.map(PrismaValue::String),
+            TypeIdentifier::GraphQLID | TypeIdentifier::Relation => {
+                Self::fetch_optional_id(row, i)?.map(PrismaValue::GraphqlId)
+            }
+            TypeIdentifier::UUID => match Self::get::<String>(row, i)? {
+                Some(val) => Some(PrismaValue::Uuid(Uuid::parse_str(val.as_ref())?)),
+                None => None,
+            },
+            TypeIdentifier::Int => Self::fetch_int(row, i)?.map(|val| PrismaValue::Int(val as i32)),
+            TypeIdentifier::Boolean => Self::get(row, i)?.map(PrismaValue::Boolean),
+            TypeIdentifier::Enum => Self::get(row, i)?.map(PrismaValue::Enum),
+            TypeIdentifier::Json => Self::get(row, i)?.map(PrismaValue::Json),
+            TypeIdentifier::DateTime => Self::fetch_int(row, i)?.map(|ts| {
+                let nsecs = ((ts % 1000) * 1_000_000) as u32;
+                let secs = (ts / 1000) as i64;
+                let naive = chrono::NaiveDateTime::from_timestamp(secs, nsecs);
+                let datetime: DateTime<Utc> = DateTime::from_utc(naive, Utc);
+
+                PrismaValue::DateTime(datetime)
+            }),
+            TypeIdentifier::Float => Self::get(row, i)?.map(PrismaValue::Float),
+        };
+
+        Ok(value.unwrap_or(PrismaValue::Null))
+    }
+
+    fn get<T>(row: &Row, i: usize) -> ConnectorResult<Option<T>>
+    where
+        T: FromValue,
+    {
+        match row.get_opt::<Option<T>, usize>(i) {
+            Some(Ok(value)) => Ok(value),
+            Some(Err(e)) => Err(ConnectorError::ColumnReadFailure(err_msg(format!(
+                "Unexpected value {:?} in column {}",
+                e.0, i
+            )))),
+            None => Err(ConnectorError::ColumnReadFailure(err_msg("Column index out of bounds"))),
+        }
+    }
+}
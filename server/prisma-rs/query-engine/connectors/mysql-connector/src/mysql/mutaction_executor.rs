@@ -0,0 +1,143 @@
+use crate::Mysql;
+use connector::{error::ConnectorError, mutaction::*, ConnectorResult, DatabaseMutactionExecutor};
+use prisma_models::*;
+use serde_json::Value;
+use sqlite_connector::DatabaseWrite;
+use std::sync::Arc;
+
+impl DatabaseMutactionExecutor for Mysql {
+    fn execute_raw(&self, _query: String) -> ConnectorResult<Value> {
+        // Raw access isn't supported by the SQLite connector either.
+        Ok(Value::String("hello world!".to_string()))
+    }
+
+    fn execute(
+        &self,
+        db_name: String,
+        mutaction: DatabaseMutaction,
+        parent_id: Option<GraphqlId>, // TODO: we don't need this when we handle the whole mutaction in here.
+    ) -> ConnectorResult<DatabaseMutactionResults> {
+        let schema = Self::schema(&mutaction);
+
+        self.with_transaction(&db_name, |conn| Self::execute_mutaction(conn, mutaction, parent_id))
+            .map_err(|e| Self::resolve_relation_violation(&schema, e))
+    }
+
+    fn execute_with_nested(
+        &self,
+        db_name: String,
+        mutaction: DatabaseMutaction,
+        parent_id: Option<GraphqlId>,
+    ) -> ConnectorResult<DatabaseMutactionResults> {
+        let schema = Self::schema(&mutaction);
+
+        self.with_transaction(&db_name, |conn| {
+            let own_results = Self::execute_mutaction(conn, mutaction, parent_id)?;
+            Self::execute_nested_tree(conn, own_results)
+        })
+        .map_err(|e| Self::resolve_relation_violation(&schema, e))
+    }
+}
+
+impl Mysql {
+    /// A failing foreign key only tells the constraint, the table holding the key and the table
+    /// it references. Unless the constraint is named after a relation, the relation is the one
+    /// linking the records in the table holding the key, or the one inlined in it that references
+    /// the other table. Errors not matching a relation of the schema are returned as they are.
+    ///
+    /// ```rust
+    /// # use connector::error::ConnectorError;
+    /// # use mysql_connector::Mysql;
+    /// # let schema = connector_test::fixtures::blog().build(String::from("blog"));
+    /// let error = ConnectorError::RelationViolation {
+    ///     relation_name: String::from("_PostComments_ibfk_2"),
+    ///     model_a_name: String::from("_PostComments"),
+    ///     model_b_name: String::from("Post"),
+    /// };
+    ///
+    /// match Mysql::resolve_relation_violation(&schema, error) {
+    ///     ConnectorError::RelationViolation { relation_name, model_a_name, model_b_name } => {
+    ///         assert_eq!("PostComments", relation_name);
+    ///         assert_eq!("Comment", model_a_name);
+    ///         assert_eq!("Post", model_b_name);
+    ///     }
+    ///     e => panic!("Unexpected error {}", e),
+    /// }
+    /// ```
+    pub fn resolve_relation_violation(schema: &Schema, error: ConnectorError) -> ConnectorError {
+        let (constraint, table, referenced_table) = match error {
+            ConnectorError::RelationViolation {
+                relation_name,
+                model_a_name,
+                model_b_name,
+            } => (relation_name, model_a_name, model_b_name),
+            e => return e,
+        };
+
+        let holds_key = |relation: &RelationRef| match relation.inline_manifestation() {
+            Some(inline) => {
+                let (model_a, model_b) = (relation.model_a(), relation.model_b());
+
+                let (holding, referenced) = if model_a.name == inline.in_table_of_model_name {
+                    (model_a, model_b)
+                } else {
+                    (model_b, model_a)
+                };
+
+                holding.db_name() == table && referenced.db_name() == referenced_table
+            }
+            None => relation.relation_table_name().as_ref() == Some(&table),
+        };
+
+        let relation = schema
+            .relations()
+            .iter()
+            .find(|relation| relation.name == constraint)
+            .or_else(|| schema.relations().iter().find(|relation| holds_key(*relation)));
+
+        match relation {
+            Some(relation) => ConnectorError::RelationViolation {
+                relation_name: relation.name.clone(),
+                model_a_name: relation.model_a().name.clone(),
+                model_b_name: relation.model_b().name.clone(),
+            },
+            None => ConnectorError::RelationViolation {
+                relation_name: constraint,
+                model_a_name: table,
+                model_b_name: referenced_table,
+            },
+        }
+    }
+
+    /// The schema the models of the mutaction belong to.
+    fn schema(mutaction: &DatabaseMutaction) -> SchemaRef {
+        match mutaction {
+            DatabaseMutaction::TopLevel(tlm) => match tlm {
+                TopLevelDatabaseMutaction::CreateNode(cn) => cn.model.schema(),
+                TopLevelDatabaseMutaction::UpdateNode(un) => un.where_.field.schema(),
+                TopLevelDatabaseMutaction::DeleteNode(dn) => dn.where_.field.schema(),
+                TopLevelDatabaseMutaction::UpsertNode(ups) => ups.where_.field.schema(),
+                TopLevelDatabaseMutaction::UpdateNodes(uns) => uns.model.schema(),
+                TopLevelDatabaseMutaction::DeleteNodes(dns) => dns.model.schema(),
+                TopLevelDatabaseMutaction::ResetData(rd) => {
+                    Arc::clone(rd.project.schema.get().expect("Project has no schema set!"))
+                }
+            },
+            DatabaseMutaction::Nested(nm) => {
+                let relation_field = match nm {
+                    NestedDatabaseMutaction::CreateNode(cn) => &cn.relation_field,
+                    NestedDatabaseMutaction::UpdateNode(un) => &un.relation_field,
+                    NestedDatabaseMutaction::UpsertNode(ups) => &ups.relation_field,
+                    NestedDatabaseMutaction::DeleteNode(dn) => &dn.relation_field,
+                    NestedDatabaseMutaction::Connect(c) => &c.relation_field,
+                    NestedDatabaseMutaction::Disconnect(d) => &d.relation_field,
+                    NestedDatabaseMutaction::Set(s) => &s.relation_field,
+                    NestedDatabaseMutaction::UpdateNodes(uns) => &uns.relation_field,
+                    NestedDatabaseMutaction::DeleteNodes(dns) => &dns.relation_field,
+                };
+
+                relation_field.model().schema()
+            }
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::mysql::{Mysql, Transaction};
+use connector::ConnectorResult;
+use mysql_client::Row;
+use prisma_query::{
+    ast::*,
+    visitor::{self, *},
+};
+
+impl Mysql {
+    pub(crate) fn query<F, T, S>(conn: &Transaction, query: S, mut f: F) -> ConnectorResult<Vec<T>>
+    where
+        F: FnMut(&Row) -> ConnectorResult<T>,
+        S: Into<Select>,
+    {
+        let (query_sql, params) = visitor::Mysql::build(query.into());
+        let mut conn = conn.borrow_mut();
+
+        let rows: ConnectorResult<Vec<T>> = conn
+            .prep_exec(query_sql, Self::parameters(params))?
+            .map(|row| f(&row?))
+            .collect();
+
+        rows
+    }
+
+    /// Runs a query selecting a single count, like `count_by_model`.
+    pub(crate) fn count<S>(conn: &Transaction, query: S) -> ConnectorResult<usize>
+    where
+        S: Into<Select>,
+    {
+        let counts = Self::query(conn, query, |row| Self::fetch_int(row, 0))?;
+        let count = counts.into_iter().next().and_then(|count| count).unwrap_or(0);
+
+        Ok(count as usize)
+    }
+}
@@ -0,0 +1,203 @@
+use crate::Mysql;
+use connector::{filter::NodeSelector, *};
+use itertools::Itertools;
+use prisma_models::*;
+use sqlite_connector::QueryBuilder;
+use std::sync::Arc;
+
+impl DataResolver for Mysql {
+    fn get_node_by_where(
+        &self,
+        node_selector: &NodeSelector,
+        selected_fields: &SelectedFields,
+    ) -> ConnectorResult<Option<SingleNode>> {
+        let db_name = &node_selector.field.model().schema().db_name;
+        let query = QueryBuilder::get_nodes(node_selector.field.model(), selected_fields, node_selector);
+        let field_names = selected_fields.names();
+
+        let nodes = self.with_transaction(db_name, |conn| {
+            Self::query(conn, query, |row| Self::read_row(row, selected_fields))
+        })?;
+
+        let result = nodes.into_iter().next().map(|node| SingleNode { node, field_names });
+
+        Ok(result)
+    }
+
+    fn get_nodes(
+        &self,
+        model: ModelRef,
+        query_arguments: QueryArguments,
+        selected_fields: &SelectedFields,
+    ) -> ConnectorResult<ManyNodes> {
+        self.check_distinct(&query_arguments)?;
+
+        let db_name = &model.schema().db_name;
+        let field_names = selected_fields.names();
+        let query = QueryBuilder::get_nodes(model, selected_fields, query_arguments);
+
+        let nodes = self.with_transaction(db_name, |conn| {
+            Self::query(conn, query, |row| Self::read_row(row, selected_fields))
+        })?;
+
+        Ok(ManyNodes { nodes, field_names })
+    }
+
+    fn get_related_nodes(
+        &self,
+        from_field: RelationFieldRef,
+        from_node_ids: &[GraphqlId],
+        query_arguments: QueryArguments,
+        selected_fields: &SelectedFields,
+    ) -> ConnectorResult<ManyNodes> {
+        self.check_distinct(&query_arguments)?;
+
+        let db_name = &from_field.model().schema().db_name;
+        let field_names = selected_fields.names();
+
+        // Without `ROW_NUMBER()` every parent is paginated in a query of its own.
+        let queries = if self.supports_window_functions || !query_arguments.is_with_pagination() {
+            vec![QueryBuilder::get_related_nodes(
+                from_field,
+                from_node_ids,
+                query_arguments,
+                selected_fields,
+            )]
+        } else {
+            from_node_ids
+                .iter()
+                .map(|id| {
+                    QueryBuilder::get_related_nodes_of_parent(
+                        Arc::clone(&from_field),
+                        id,
+                        query_arguments.clone(),
+                        selected_fields,
+                    )
+                })
+                .collect()
+        };
+
+        let nodes = self.with_transaction(db_name, |conn| {
+            let mut nodes = Vec::new();
+
+            for query in queries {
+                nodes.append(&mut Self::query(conn, query, |row| {
+                    let position = field_names.len();
+
+                    let mut node = Self::read_row(row, &selected_fields)?;
+                    node.add_parent_id(Self::fetch_id_at(row, position - 1)?);
+
+                    Ok(node)
+                })?);
+            }
+
+            Ok(nodes)
+        })?;
+
+        Ok(ManyNodes { nodes, field_names })
+    }
+
+    fn count_by_model(&self, model: ModelRef, query_arguments: QueryArguments) -> ConnectorResult<usize> {
+        self.check_distinct(&query_arguments)?;
+
+        let db_name = &model.schema().db_name;
+        let query = QueryBuilder::count_by_model(model, query_arguments);
+
+        self.with_transaction(db_name, |conn| Self::count(conn, query))
+    }
+
+    fn count_related_nodes(
+        &self,
+        from_field: RelationFieldRef,
+        from_node_ids: &[GraphqlId],
+        query_arguments: QueryArguments,
+    ) -> ConnectorResult<Vec<RelatedNodesCount>> {
+        self.check_distinct(&query_arguments)?;
+
+        let db_name = &from_field.model().schema().db_name;
+        let query = QueryBuilder::count_related_nodes(from_field, from_node_ids, query_arguments);
+
+        self.with_transaction(db_name, |conn| {
+            Self::query(conn, query, |row| {
+                Ok(RelatedNodesCount {
+                    parent_id: Self::fetch_id_at(row, 0)?,
+                    count: Self::fetch_int(row, 1)?.unwrap_or(0) as usize,
+                })
+            })
+        })
+    }
+
+    fn count_by_table(&self, database: &str, table: &str) -> ConnectorResult<usize> {
+        let query = QueryBuilder::count_by_table(database, table);
+        self.with_transaction(database, |conn| Self::count(conn, query))
+    }
+
+    fn aggregate(
+        &self,
+        model: ModelRef,
+        query_arguments: QueryArguments,
+        selection: AggregationSelection,
+    ) -> ConnectorResult<Vec<AggregationRow>> {
+        self.check_distinct(&query_arguments)?;
+
+        let db_name = &model.schema().db_name;
+        let query = QueryBuilder::aggregate(Arc::clone(&model), query_arguments, &selection);
+
+        self.with_transaction(db_name, |conn| {
+            Self::query(conn, query, |row| {
+                let group = selection
+                    .group_by
+                    .iter()
+                    .enumerate()
+                    .map(|(i, field)| Self::fetch_value(field.type_identifier, row, i))
+                    .collect::<ConnectorResult<Vec<PrismaValue>>>()?;
+
+                let offset = group.len();
+                let values = selection
+                    .aggregations
+                    .iter()
+                    .enumerate()
+                    .map(|(i, aggregation)| Self::fetch_value(aggregation.type_identifier(), row, offset + i))
+                    .collect::<ConnectorResult<Vec<PrismaValue>>>()?;
+
+                Ok(AggregationRow { group, values })
+            })
+        })
+    }
+
+    fn get_scalar_list_values_by_node_ids(
+        &self,
+        list_field: ScalarFieldRef,
+        node_ids: Vec<GraphqlId>,
+    ) -> ConnectorResult<Vec<ScalarListValues>> {
+        let db_name = &list_field.model().schema().db_name;
+        let type_identifier = list_field.type_identifier;
+        let query = QueryBuilder::get_scalar_list_values_by_node_ids(list_field, node_ids);
+
+        let results = self.with_transaction(db_name, |conn| {
+            Self::query(conn, query, |row| {
+                let node_id = Self::fetch_id(row)?;
+                let value: PrismaValue = Self::fetch_value(type_identifier, row, 2)?;
+
+                Ok(ScalarListElement { node_id, value })
+            })
+        })?;
+
+        let mut list_values = Vec::new();
+
+        for (node_id, elements) in &results.into_iter().group_by(|ele| ele.node_id.clone()) {
+            let values = ScalarListValues {
+                node_id,
+                values: elements.into_iter().map(|e| e.value).collect(),
+            };
+            list_values.push(values);
+        }
+
+        Ok(list_values)
+    }
+}
+
+struct ScalarListElement {
+    node_id: GraphqlId,
+    value: PrismaValue,
+}
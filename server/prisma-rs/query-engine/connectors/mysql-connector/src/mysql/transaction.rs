@@ -0,0 +1,71 @@
+use crate::mysql::{Mysql, Transaction};
+use connector::ConnectorResult;
+use prisma_models::*;
+use prisma_query::{
+    ast::*,
+    visitor::{self, *},
+};
+use sqlite_connector::{MutationBuilder, SqlTransaction};
+use std::{cell::RefCell, ops::Deref};
+
+/// A transaction executing the shared queries of the SQL connectors. Statements of the driver
+/// need a mutable transaction, so it is borrowed for every statement.
+pub struct MysqlTransaction<'a> {
+    inner: Transaction<'a>,
+}
+
+impl<'a> MysqlTransaction<'a> {
+    pub(crate) fn new(inner: mysql_client::Transaction<'a>) -> Self {
+        Self {
+            inner: RefCell::new(inner),
+        }
+    }
+
+    pub(crate) fn commit(self) -> ConnectorResult<()> {
+        Ok(self.inner.into_inner().commit()?)
+    }
+}
+
+impl<'a> Deref for MysqlTransaction<'a> {
+    type Target = Transaction<'a>;
+
+    fn deref(&self) -> &Transaction<'a> {
+        &self.inner
+    }
+}
+
+impl SqlTransaction for MysqlTransaction<'_> {
+    fn write(&self, query: Query) -> ConnectorResult<()> {
+        let (sql, params) = visitor::Mysql::build(query);
+        self.borrow_mut().prep_exec(sql, Mysql::parameters(params))?;
+
+        Ok(())
+    }
+
+    /// MySQL has no `RETURNING`, but reports the id of the inserted row.
+    fn insert_returning_id(&self, insert: Insert, _: &ModelRef) -> ConnectorResult<GraphqlId> {
+        let (sql, params) = visitor::Mysql::build(insert);
+        let mut conn = self.borrow_mut();
+        let result = conn.prep_exec(sql, Mysql::parameters(params))?;
+
+        Ok(GraphqlId::Int(result.last_insert_id() as usize))
+    }
+
+    fn read_ids(&self, select: Select) -> ConnectorResult<Vec<GraphqlId>> {
+        Mysql::query(self, select, Mysql::fetch_id)
+    }
+
+    fn read_nodes(&self, select: Select, selected_fields: &SelectedFields) -> ConnectorResult<Vec<Node>> {
+        Mysql::query(self, select, |row| Mysql::read_row(row, selected_fields))
+    }
+
+    fn truncate(&self, project: ProjectRef) -> ConnectorResult<()> {
+        Mysql::without_foreign_key_checks(self, || {
+            for delete in MutationBuilder::truncate_tables(project) {
+                self.write(delete.into())?;
+            }
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,156 @@
+use mysql_connector::Mysql;
+use prisma_common::config::ExplicitConfig;
+use prisma_models::*;
+use std::env;
+
+// The cases need running servers, configured in `MYSQL_TEST_CONFIG` and `MYSQL_5_7_TEST_CONFIG`
+// like a database of the Prisma config, and run with `cargo test -- --ignored`. MySQL 5.7 has
+// no window functions, paginating the related nodes of every parent in a query of its own.
+mod mysql_8 {
+    use super::*;
+
+    connector_test::conformance_tests!(#[ignore] connector: Mysql::new(&config("MYSQL_TEST_CONFIG", 3306)).unwrap(), migrate: migrate);
+}
+
+mod mysql_5_7 {
+    use super::*;
+
+    connector_test::conformance_tests!(#[ignore] connector: Mysql::new(&config("MYSQL_5_7_TEST_CONFIG", 3307)).unwrap(), migrate: migrate);
+}
+
+fn config(variable: &str, default_port: u16) -> ExplicitConfig {
+    let config = env::var(variable).unwrap_or_else(|_| {
+        format!(
+            "connector: mysql-native\nhost: localhost\nport: {}\nuser: root\npassword: prisma",
+            default_port
+        )
+    });
+
+    serde_yaml::from_str(&config).unwrap()
+}
+
+/// Creates the tables of the schema in a new database of the same name: a table per model, a
+/// table per scalar list and a table per relation. Removing a record removes its list values and
+/// links through the foreign keys.
+fn migrate(mysql: &Mysql, schema: &Schema) {
+    let database = Mysql::quote(&schema.db_name);
+
+    mysql
+        .with_connection("mysql", |conn| {
+            conn.query(format!("DROP DATABASE IF EXISTS {}", database))?;
+            conn.query(format!("CREATE DATABASE {}", database))?;
+
+            for statement in create_tables(schema) {
+                conn.query(statement)?;
+            }
+
+            Ok(())
+        })
+        .unwrap();
+}
+
+fn create_tables(schema: &Schema) -> Vec<String> {
+    let database = Mysql::quote(&schema.db_name);
+    let quote = Mysql::quote;
+    let mut statements = Vec::new();
+
+    for model in schema.models() {
+        let table = quote(model.db_name());
+        let id = model.fields().id();
+
+        let columns: Vec<String> = model
+            .fields()
+            .scalar_non_list()
+            .iter()
+            .map(|field| {
+                let mut column = format!("{} {}", quote(field.db_name()), column_type(field.type_identifier));
+
+                if field.name == id.name {
+                    column.push_str(" NOT NULL PRIMARY KEY");
+                } else if field.is_required {
+                    column.push_str(" NOT NULL");
+                }
+
+                if field.is_unique && field.name != id.name {
+                    column.push_str(" UNIQUE");
+                }
+
+                column
+            })
+            .collect();
+
+        statements.push(format!("CREATE TABLE {}.{} ({})", database, table, columns.join(", ")));
+
+        for field in model.fields().scalar_list() {
+            let list_table = quote(field.scalar_list_table().table_name());
+            let node_id = quote(ScalarListTable::NODE_ID_FIELD_NAME);
+            let position = quote(ScalarListTable::POSITION_FIELD_NAME);
+
+            let columns = vec![
+                format!("{} VARCHAR(191) NOT NULL", node_id),
+                format!("{} INTEGER NOT NULL", position),
+                format!(
+                    "{} {} NOT NULL",
+                    quote(ScalarListTable::VALUE_FIELD_NAME),
+                    column_type(field.type_identifier)
+                ),
+                format!("PRIMARY KEY ({}, {})", node_id, position),
+                format!(
+                    "FOREIGN KEY ({}) REFERENCES {}.{}({}) ON DELETE CASCADE",
+                    node_id,
+                    database,
+                    table,
+                    quote(id.db_name())
+                ),
+            ];
+
+            statements.push(format!(
+                "CREATE TABLE {}.{} ({})",
+                database,
+                list_table,
+                columns.join(", ")
+            ));
+        }
+    }
+
+    for relation in schema.relations() {
+        let references = |column: &str, model: ModelRef| {
+            format!(
+                "FOREIGN KEY ({}) REFERENCES {}.{}({}) ON DELETE CASCADE",
+                column,
+                database,
+                quote(model.db_name()),
+                quote(model.fields().id().db_name())
+            )
+        };
+
+        let model_a = quote(Relation::MODEL_A_DEFAULT_COLUMN);
+        let model_b = quote(Relation::MODEL_B_DEFAULT_COLUMN);
+
+        let columns = vec![
+            String::from("`id` VARCHAR(191) NOT NULL PRIMARY KEY"),
+            format!("{} VARCHAR(191) NOT NULL", model_a),
+            format!("{} VARCHAR(191) NOT NULL", model_b),
+            format!("UNIQUE ({}, {})", model_a, model_b),
+            references(&model_a, relation.model_a()),
+            references(&model_b, relation.model_b()),
+        ];
+
+        let table = quote(&format!("_{}", relation.name));
+        statements.push(format!("CREATE TABLE {}.{} ({})", database, table, columns.join(", ")));
+    }
+
+    statements
+}
+
+/// Dates are written as milliseconds, like in SQLite. Strings are limited to the length of an
+/// index on `utf8mb4` columns in MySQL 5.7, as ids and unique fields are indexed.
+fn column_type(type_identifier: TypeIdentifier) -> &'static str {
+    match type_identifier {
+        TypeIdentifier::Int => "INTEGER",
+        TypeIdentifier::Float => "DOUBLE",
+        TypeIdentifier::Boolean => "BOOLEAN",
+        TypeIdentifier::DateTime => "BIGINT",
+        _ => "VARCHAR(191)",
+    }
+}
@@ -0,0 +1,79 @@
+use connector::error::ConnectorError;
+use connector_test::fixtures;
+use mysql_client::error::{Error, MySqlError};
+use mysql_connector::Mysql;
+
+fn foreign_key_error(code: u16, message: &str) -> ConnectorError {
+    let schema = fixtures::blog().build(String::from("relation_violations"));
+
+    let error = Error::MySqlError(MySqlError {
+        state: String::from("23000"),
+        message: message.to_string(),
+        code,
+    });
+
+    Mysql::resolve_relation_violation(&schema, ConnectorError::from(error))
+}
+
+fn assert_relation_violation(error: ConnectorError, relation: &str, model_a: &str, model_b: &str) {
+    match error {
+        ConnectorError::RelationViolation {
+            relation_name,
+            model_a_name,
+            model_b_name,
+        } => {
+            assert_eq!(relation, relation_name);
+            assert_eq!(model_a, model_a_name);
+            assert_eq!(model_b, model_b_name);
+        }
+        e => panic!("Expected a relation violation, got {}", e),
+    }
+}
+
+#[test]
+fn deleting_a_referenced_record() {
+    let error = foreign_key_error(
+        1451,
+        "Cannot delete or update a parent row: a foreign key constraint fails \
+         (`relation_violations`.`_PostComments`, CONSTRAINT `_PostComments_ibfk_2` \
+         FOREIGN KEY (`B`) REFERENCES `Post` (`id`))",
+    );
+
+    assert_relation_violation(error, "PostComments", "Comment", "Post");
+}
+
+#[test]
+fn linking_a_missing_record() {
+    let error = foreign_key_error(
+        1452,
+        "Cannot add or update a child row: a foreign key constraint fails \
+         (`relation_violations`.`_UserPosts`, CONSTRAINT `_UserPosts_ibfk_1` \
+         FOREIGN KEY (`A`) REFERENCES `Post` (`id`))",
+    );
+
+    assert_relation_violation(error, "UserPosts", "Post", "User");
+}
+
+#[test]
+fn constraints_named_after_the_relation() {
+    let error = foreign_key_error(
+        1452,
+        "Cannot add or update a child row: a foreign key constraint fails \
+         (`relation_violations`.`Profile`, CONSTRAINT `UserProfile` \
+         FOREIGN KEY (`user`) REFERENCES `User` (`id`))",
+    );
+
+    assert_relation_violation(error, "UserProfile", "Profile", "User");
+}
+
+#[test]
+fn unknown_constraints() {
+    let error = foreign_key_error(
+        1451,
+        "Cannot delete or update a parent row: a foreign key constraint fails \
+         (`relation_violations`.`Audit`, CONSTRAINT `Audit_ibfk_1` \
+         FOREIGN KEY (`post`) REFERENCES `Post` (`id`))",
+    );
+
+    assert_relation_violation(error, "Audit_ibfk_1", "Audit", "Post");
+}
@@ -0,0 +1,18 @@
+//! # The PostgreSQL Connector interface
+//!
+//! Implements [DataResolver](../connector/trait.DataResolver.html) and
+//! [DatabaseMutactionExecutor](../connector/trait.DatabaseMutactionExecutor.html) for
+//! [PostgreSQL](struct.PostgreSql.html).
+//!
+//! The queries are built with the same builders as the
+//! [SQLite connector](../sqlite_connector/index.html), which also reads and writes the records
+//! for every [SqlDatabase](../sqlite_connector/trait.SqlDatabase.html). A
+//! [PostgreSqlTransaction](struct.PostgreSqlTransaction.html) renders them with the Postgres
+//! visitor of `prisma_query`. Every Prisma database is a schema of the same name, taking the
+//! place of the attached databases in SQLite. Generated ids are read back with `RETURNING`.
+//!
+//! Full-text search and the JSON filters and updates depend on SQLite functions, and are not
+//! supported yet.
+mod postgresql;
+
+pub use postgresql::*;
@@ -0,0 +1,227 @@
+mod mutaction_executor;
+mod read;
+mod resolver;
+mod transaction;
+
+pub use transaction::*;
+
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use connector::{error::ConnectorError, ConnectorResult};
+use failure::err_msg;
+use num_traits::ToPrimitive;
+use postgres::{
+    params::{ConnectParams, Host},
+    rows::Row,
+    stmt::Statement,
+    transaction::Transaction,
+    types::{FromSql, ToSql, TIMESTAMP, TIMESTAMPTZ},
+    TlsMode,
+};
+use prisma_common::config::{ConnectionLimit, ExplicitConfig};
+use prisma_models::prelude::*;
+use prisma_query::ast::ParameterizedValue;
+use r2d2_postgres::PostgresConnectionManager;
+use rust_decimal::Decimal;
+use sqlite_connector::SqlDatabase;
+use uuid::Uuid;
+
+type Pool = r2d2::Pool<PostgresConnectionManager>;
+pub type Connection = r2d2::PooledConnection<PostgresConnectionManager>;
+
+pub struct PostgreSql {
+    pool: Pool,
+}
+
+impl SqlDatabase for PostgreSql {}
+
+impl PostgreSql {
+    /// Creates a pool of at most `ConnectionLimit::limit()` connections to the server of the
+    /// config, connecting to its `database` or to `postgres` if not set.
+    ///
+    /// ```rust,no_run
+    /// # use postgres_connector::*;
+    /// # use prisma_common::config::ExplicitConfig;
+    /// # use connector::ConnectorResult;
+    /// let config: ExplicitConfig = serde_yaml::from_str(
+    ///     "connector: postgres-native\nhost: localhost\nport: 5432\nuser: postgres\npassword: prisma",
+    /// ).unwrap();
+    ///
+    /// let postgres = PostgreSql::new(&config).unwrap();
+    ///
+    /// let one: ConnectorResult<Option<i64>> = postgres.with_transaction("public", |trans| {
+    ///     let rows = trans.query("SELECT 1::bigint", &[])?;
+    ///     PostgreSql::fetch_int(&rows.get(0), 0)
+    /// });
+    ///
+    /// assert_eq!(Some(1), one.unwrap());
+    /// ```
+    pub fn new(config: &ExplicitConfig) -> ConnectorResult<PostgreSql> {
+        if config.ssl == Some(true) {
+            return Err(ConnectorError::InvalidConnectionArguments);
+        }
+
+        let mut params = ConnectParams::builder();
+
+        params
+            .port(config.port)
+            .user(&config.user, config.password.as_ref().map(String::as_str))
+            .database(config.database.as_ref().map(String::as_str).unwrap_or("postgres"));
+
+        let params = params.build(Host::Tcp(config.host.clone()));
+        let manager = PostgresConnectionManager::new(params, TlsMode::None)
+            .map_err(|e| ConnectorError::ConnectionError(e.into()))?;
+
+        let pool = r2d2::Pool::builder().max_size(config.limit()).build(manager)?;
+
+        Ok(PostgreSql { pool })
+    }
+
+    /// Takes a connection from the pool, resolving unqualified tables, like the relation tables,
+    /// in the schema of the database.
+    pub fn with_connection<F, T>(&self, db_name: &str, f: F) -> ConnectorResult<T>
+    where
+        F: FnOnce(&Connection) -> ConnectorResult<T>,
+    {
+        let conn = self.pool.get()?;
+        conn.batch_execute(&format!("SET search_path TO {}", Self::quote(db_name)))?;
+
+        f(&conn)
+    }
+
+    /// Takes a connection and starts a transaction, that is committed if the given `F` was
+    /// successful and rolled back otherwise.
+    pub fn with_transaction<F, T>(&self, db_name: &str, f: F) -> ConnectorResult<T>
+    where
+        F: FnOnce(&PostgreSqlTransaction) -> ConnectorResult<T>,
+    {
+        self.with_connection(db_name, |conn| {
+            let tx = PostgreSqlTransaction::new(conn.transaction()?);
+            let result = f(&tx);
+
+            if result.is_ok() {
+                tx.commit()?;
+            }
+
+            result
+        })
+    }
+
+    /// Quotes an identifier, like a schema or column name.
+    pub fn quote(identifier: &str) -> String {
+        format!("\"{}\"", identifier.replace('"', "\"\""))
+    }
+
+    /// Prepares the statement and passes it to `F` with the parameters to bind. Dates are
+    /// milliseconds in the parameters built from the models, and are bound as timestamps when
+    /// the statement expects one.
+    fn with_statement<F, T>(conn: &Transaction, sql: &str, params: &[ParameterizedValue], f: F) -> ConnectorResult<T>
+    where
+        F: FnOnce(&Statement, &[&ToSql]) -> ConnectorResult<T>,
+    {
+        let stmt = conn.prepare(sql)?;
+
+        let dates: Vec<Option<Box<ToSql>>> = params
+            .iter()
+            .zip(stmt.param_types())
+            .map(|(param, typ)| match param {
+                ParameterizedValue::Integer(ms) if *typ == TIMESTAMPTZ => {
+                    Some(Box::new(Utc.timestamp_millis(*ms)) as Box<ToSql>)
+                }
+                ParameterizedValue::Integer(ms) if *typ == TIMESTAMP => {
+                    Some(Box::new(Utc.timestamp_millis(*ms).naive_utc()) as Box<ToSql>)
+                }
+                _ => None,
+            })
+            .collect();
+
+        let params: Vec<&ToSql> = params
+            .iter()
+            .zip(dates.iter())
+            .map(|(param, date)| match date {
+                Some(date) => date.as_ref(),
+                None => param as &ToSql,
+            })
+            .collect();
+
+        f(&stmt, &params)
+    }
+
+    /// An integer, which can be stored as `INTEGER`, `BIGINT` or be an aggregation.
+    pub fn fetch_int(row: &Row, i: usize) -> ConnectorResult<Option<i64>> {
+        match Self::get::<i64>(row, i) {
+            Ok(value) => Ok(value),
+            Err(_) => Ok(Self::get::<i32>(row, i)?.map(i64::from)),
+        }
+    }
+
+    pub fn fetch_id(row: &Row) -> ConnectorResult<GraphqlId> {
+        Self::fetch_id_at(row, 0)
+    }
+
+    pub fn fetch_id_at(row: &Row, i: usize) -> ConnectorResult<GraphqlId> {
+        Self::fetch_optional_id(row, i)?.ok_or_else(|| ConnectorError::ColumnReadFailure(err_msg("Id column is null")))
+    }
+
+    /// Ids are either strings or generated integers.
+    fn fetch_optional_id(row: &Row, i: usize) -> ConnectorResult<Option<GraphqlId>> {
+        match Self::get::<String>(row, i) {
+            Ok(id) => Ok(id.map(GraphqlId::String)),
+            Err(_) => Ok(Self::fetch_int(row, i)?.map(|id| GraphqlId::Int(id as usize))),
+        }
+    }
+
+    /// Read and cast a `Row` into a `Record`, casting the columns from the
+    /// `DataModel` definitions.
+    pub fn read_row(row: &Row, selected_fields: &SelectedFields) -> ConnectorResult<Node> {
+        let mut fields = Vec::new();
+
+        for (i, typid) in selected_fields.type_identifiers().iter().enumerate() {
+            fields.push(Self::fetch_value(*typid, &row, i)?);
+        }
+
+        Ok(Node::new(fields))
+    }
+
+    /// Reads a column into a `PrismaValue`. UUIDs are written as strings like in SQLite, dates
+    /// are read from `TIMESTAMP` and `TIMESTAMPTZ` columns.
+    pub fn fetch_value(typ: TypeIdentifier, row: &Row, i: usize) -> ConnectorResult<PrismaValue> {
+        let value = match typ {
+            TypeIdentifier::String => Self::get(row, i)?.map(PrismaValue::String),
+            TypeIdentifier::GraphQLID | TypeIdentifier::Relation => {
+                Self::fetch_optional_id(row, i)?.map(PrismaValue::GraphqlId)
+            }
+            TypeIdentifier::UUID => match Self::get::<String>(row, i)? {
+                Some(val) => Some(PrismaValue::Uuid(Uuid::parse_str(val.as_ref())?)),
+                None => None,
+            },
+            TypeIdentifier::Int => Self::fetch_int(row, i)?.map(|val| PrismaValue::Int(val as i32)),
+            TypeIdentifier::Boolean => Self::get(row, i)?.map(PrismaValue::Boolean),
+            TypeIdentifier::Enum => Self::get(row, i)?.map(PrismaValue::Enum),
+            TypeIdentifier::Json => Self::get(row, i)?.map(PrismaValue::Json),
+            TypeIdentifier::DateTime => match Self::get::<DateTime<Utc>>(row, i) {
+                Ok(val) => val.map(PrismaValue::DateTime),
+                Err(_) => Self::get::<NaiveDateTime>(row, i)?
+                    .map(|naive| PrismaValue::DateTime(DateTime::from_utc(naive, Utc))),
+            },
+            // Averages and sums of integers are `NUMERIC`.
+            TypeIdentifier::Float => match Self::get::<f64>(row, i) {
+                Ok(val) => val.map(PrismaValue::Float),
+                Err(_) => Self::get::<Decimal>(row, i)?
+                    .and_then(|val| val.to_f64())
+                    .map(PrismaValue::Float),
+            },
+        };
+
+        Ok(value.unwrap_or(PrismaValue::Null))
+    }
+
+    fn get<T>(row: &Row, i: usize) -> ConnectorResult<Option<T>>
+    where
+        T: FromSql,
+    {
+        match row.get_opt::<_, Option<T>>(i) {
+            Some(result) => result.map_err(|e| ConnectorError::ColumnReadFailure(e.into())),
+            None => Err(ConnectorError::ColumnReadFailure(err_msg("Column index out of bounds"))),
+        }
+    }
+}
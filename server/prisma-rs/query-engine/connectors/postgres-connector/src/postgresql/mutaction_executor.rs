@@ -0,0 +1,33 @@
+use crate::PostgreSql;
+use connector::{mutaction::*, ConnectorResult, DatabaseMutactionExecutor};
+use prisma_models::*;
+use serde_json::Value;
+use sqlite_connector::DatabaseWrite;
+
+impl DatabaseMutactionExecutor for PostgreSql {
+    fn execute_raw(&self, _query: String) -> ConnectorResult<Value> {
+        // Raw access isn't supported by the SQLite connector either.
+        Ok(Value::String("hello world!".to_string()))
+    }
+
+    fn execute(
+        &self,
+        db_name: String,
+        mutaction: DatabaseMutaction,
+        parent_id: Option<GraphqlId>, // TODO: we don't need this when we handle the whole mutaction in here.
+    ) -> ConnectorResult<DatabaseMutactionResults> {
+        self.with_transaction(&db_name, |conn| Self::execute_mutaction(conn, mutaction, parent_id))
+    }
+
+    fn execute_with_nested(
+        &self,
+        db_name: String,
+        mutaction: DatabaseMutaction,
+        parent_id: Option<GraphqlId>,
+    ) -> ConnectorResult<DatabaseMutactionResults> {
+        self.with_transaction(&db_name, |conn| {
+            let own_results = Self::execute_mutaction(conn, mutaction, parent_id)?;
+            Self::execute_nested_tree(conn, own_results)
+        })
+    }
+}
@@ -0,0 +1,33 @@
+use crate::PostgreSql;
+use connector::ConnectorResult;
+use postgres::{rows::Row, transaction::Transaction};
+use prisma_query::{
+    ast::*,
+    visitor::{self, *},
+};
+
+impl PostgreSql {
+    pub(crate) fn query<F, T, S>(conn: &Transaction, query: S, mut f: F) -> ConnectorResult<Vec<T>>
+    where
+        F: FnMut(&Row) -> ConnectorResult<T>,
+        S: Into<Select>,
+    {
+        let (query_sql, params) = visitor::Postgres::build(query.into());
+
+        Self::with_statement(conn, &query_sql, &params, |stmt, params| {
+            let rows = stmt.query(params)?;
+            rows.iter().map(|row| f(&row)).collect()
+        })
+    }
+
+    /// Runs a query selecting a single count, like `count_by_model`.
+    pub(crate) fn count<S>(conn: &Transaction, query: S) -> ConnectorResult<usize>
+    where
+        S: Into<Select>,
+    {
+        let counts = Self::query(conn, query, |row| Self::fetch_int(row, 0))?;
+        let count = counts.into_iter().next().and_then(|count| count).unwrap_or(0);
+
+        Ok(count as usize)
+    }
+}
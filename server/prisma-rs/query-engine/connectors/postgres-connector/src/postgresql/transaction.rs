@@ -0,0 +1,96 @@
+use crate::PostgreSql;
+use connector::{error::ConnectorError, ConnectorResult};
+use postgres::transaction::Transaction;
+use prisma_models::*;
+use prisma_query::{
+    ast::*,
+    visitor::{self, *},
+};
+use sqlite_connector::SqlTransaction;
+use std::ops::Deref;
+
+/// A transaction executing the shared queries of the SQL connectors, giving access to the
+/// `postgres` transaction it wraps.
+pub struct PostgreSqlTransaction<'a> {
+    inner: Transaction<'a>,
+}
+
+impl<'a> PostgreSqlTransaction<'a> {
+    pub(crate) fn new(inner: Transaction<'a>) -> Self {
+        Self { inner }
+    }
+
+    pub(crate) fn commit(self) -> ConnectorResult<()> {
+        Ok(self.inner.commit()?)
+    }
+}
+
+impl<'a> Deref for PostgreSqlTransaction<'a> {
+    type Target = Transaction<'a>;
+
+    fn deref(&self) -> &Transaction<'a> {
+        &self.inner
+    }
+}
+
+impl SqlTransaction for PostgreSqlTransaction<'_> {
+    fn write(&self, query: Query) -> ConnectorResult<()> {
+        let (sql, params) = visitor::Postgres::build(query);
+
+        PostgreSql::with_statement(self, &sql, &params, |stmt, params| {
+            stmt.execute(params)?;
+            Ok(())
+        })
+    }
+
+    /// The generated id is returned by the database with `RETURNING`.
+    fn insert_returning_id(&self, insert: Insert, model: &ModelRef) -> ConnectorResult<GraphqlId> {
+        let (sql, params) = visitor::Postgres::build(insert);
+        let sql = format!("{} RETURNING {}", sql, PostgreSql::quote(model.fields().id().db_name()));
+
+        PostgreSql::with_statement(self, &sql, &params, |stmt, params| {
+            let rows = stmt.query(params)?;
+            let row = rows.iter().next().ok_or(ConnectorError::NodeDoesNotExist)?;
+
+            PostgreSql::fetch_id(&row)
+        })
+    }
+
+    fn read_ids(&self, select: Select) -> ConnectorResult<Vec<GraphqlId>> {
+        PostgreSql::query(self, select, PostgreSql::fetch_id)
+    }
+
+    fn read_nodes(&self, select: Select, selected_fields: &SelectedFields) -> ConnectorResult<Vec<Node>> {
+        PostgreSql::query(self, select, |row| PostgreSql::read_row(row, selected_fields))
+    }
+
+    /// The foreign keys can't be switched off like in SQLite, so all tables are truncated in one
+    /// statement, with `CASCADE` covering the tables referencing them.
+    fn truncate(&self, project: ProjectRef) -> ConnectorResult<()> {
+        let schema = project.schema();
+        let mut tables = Vec::new();
+
+        for model in schema.models() {
+            tables.push(String::from(model.db_name()));
+
+            for field in model.fields().scalar_list() {
+                tables.push(String::from(field.scalar_list_table().table_name()));
+            }
+        }
+
+        tables.extend(schema.relations().iter().filter_map(|r| r.relation_table_name()));
+
+        if tables.is_empty() {
+            return Ok(());
+        }
+
+        let tables: Vec<String> = tables
+            .iter()
+            .map(|table| format!("{}.{}", PostgreSql::quote(&schema.db_name), PostgreSql::quote(table)))
+            .collect();
+
+        self.batch_execute(&format!("TRUNCATE {} CASCADE", tables.join(", ")))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+use postgres_connector::PostgreSql;
+use prisma_common::config::ExplicitConfig;
+use prisma_models::*;
+use std::env;
+
+// The cases need a running server, configured in `POSTGRES_TEST_CONFIG` like a database of the
+// Prisma config, and run with `cargo test -- --ignored`.
+connector_test::conformance_tests!(#[ignore] connector: PostgreSql::new(&config()).unwrap(), migrate: migrate);
+
+fn config() -> ExplicitConfig {
+    let config = env::var("POSTGRES_TEST_CONFIG").unwrap_or_else(|_| {
+        String::from("connector: postgres-native\nhost: localhost\nport: 5432\nuser: postgres\npassword: prisma")
+    });
+
+    serde_yaml::from_str(&config).unwrap()
+}
+
+/// Creates the tables of the schema in a new Postgres schema of the same name: a table per
+/// model, a table per scalar list and a table per relation. Removing a record removes its list
+/// values and links through the foreign keys.
+fn migrate(postgres: &PostgreSql, schema: &Schema) {
+    let database = PostgreSql::quote(&schema.db_name);
+
+    postgres
+        .with_transaction(&schema.db_name, |conn| {
+            conn.batch_execute(&format!("DROP SCHEMA IF EXISTS {} CASCADE", database))?;
+            conn.batch_execute(&format!("CREATE SCHEMA {}", database))?;
+
+            for statement in create_tables(schema) {
+                conn.batch_execute(&statement)?;
+            }
+
+            Ok(())
+        })
+        .unwrap();
+}
+
+fn create_tables(schema: &Schema) -> Vec<String> {
+    let database = PostgreSql::quote(&schema.db_name);
+    let quote = PostgreSql::quote;
+    let mut statements = Vec::new();
+
+    for model in schema.models() {
+        let table = quote(model.db_name());
+        let id = model.fields().id();
+
+        let columns: Vec<String> = model
+            .fields()
+            .scalar_non_list()
+            .iter()
+            .map(|field| {
+                let mut column = format!("{} {}", quote(field.db_name()), column_type(field.type_identifier));
+
+                if field.name == id.name {
+                    column.push_str(" NOT NULL PRIMARY KEY");
+                } else if field.is_required {
+                    column.push_str(" NOT NULL");
+                }
+
+                if field.is_unique && field.name != id.name {
+                    column.push_str(" UNIQUE");
+                }
+
+                column
+            })
+            .collect();
+
+        statements.push(format!("CREATE TABLE {}.{} ({});", database, table, columns.join(", ")));
+
+        for field in model.fields().scalar_list() {
+            let list_table = quote(field.scalar_list_table().table_name());
+            let node_id = quote(ScalarListTable::NODE_ID_FIELD_NAME);
+            let position = quote(ScalarListTable::POSITION_FIELD_NAME);
+
+            let columns = vec![
+                format!(
+                    "{} TEXT NOT NULL REFERENCES {}.{}({}) ON DELETE CASCADE",
+                    node_id,
+                    database,
+                    table,
+                    quote(id.db_name())
+                ),
+                format!("{} INTEGER NOT NULL", position),
+                format!(
+                    "{} {} NOT NULL",
+                    quote(ScalarListTable::VALUE_FIELD_NAME),
+                    column_type(field.type_identifier)
+                ),
+                format!("PRIMARY KEY ({}, {})", node_id, position),
+            ];
+
+            statements.push(format!(
+                "CREATE TABLE {}.{} ({});",
+                database,
+                list_table,
+                columns.join(", ")
+            ));
+        }
+    }
+
+    for relation in schema.relations() {
+        let references = |model: ModelRef| {
+            format!(
+                "TEXT NOT NULL REFERENCES {}.{}({}) ON DELETE CASCADE",
+                database,
+                quote(model.db_name()),
+                quote(model.fields().id().db_name())
+            )
+        };
+
+        let model_a = quote(Relation::MODEL_A_DEFAULT_COLUMN);
+        let model_b = quote(Relation::MODEL_B_DEFAULT_COLUMN);
+
+        let columns = vec![
+            String::from("\"id\" TEXT NOT NULL PRIMARY KEY"),
+            format!("{} {}", model_a, references(relation.model_a())),
+            format!("{} {}", model_b, references(relation.model_b())),
+            format!("UNIQUE ({}, {})", model_a, model_b),
+        ];
+
+        let table = quote(&format!("_{}", relation.name));
+        statements.push(format!("CREATE TABLE {}.{} ({});", database, table, columns.join(", ")));
+    }
+
+    statements
+}
+
+fn column_type(type_identifier: TypeIdentifier) -> &'static str {
+    match type_identifier {
+        TypeIdentifier::Int => "INTEGER",
+        TypeIdentifier::Float => "DOUBLE PRECISION",
+        TypeIdentifier::Boolean => "BOOLEAN",
+        TypeIdentifier::DateTime => "TIMESTAMP(3)",
+        _ => "TEXT",
+    }
+}
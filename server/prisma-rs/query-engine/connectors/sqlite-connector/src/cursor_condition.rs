@@ -1,7 +1,7 @@
+use crate::ordering::Ordering;
 use connector::QueryArguments;
 use prisma_models::prelude::*;
 use prisma_query::ast::*;
-use std::sync::Arc;
 
 #[derive(Clone, Copy)]
 enum CursorType {
@@ -12,67 +12,63 @@ enum CursorType {
 pub struct CursorCondition;
 
 impl CursorCondition {
+    /// Records before or after the cursor record, in the order of all sort keys.
+    ///
+    /// The keys are compared lexicographically: a record comes after the cursor if its first key
+    /// is past the cursor's, or if it's equal and the remaining keys decide. The id is the last
+    /// key, so that records with equal sort keys are never skipped or repeated.
     pub fn build(query_arguments: &QueryArguments, model: ModelRef) -> ConditionTree {
-        match (
-            query_arguments.before.as_ref(),
-            query_arguments.after.as_ref(),
-            query_arguments.order_by.as_ref(),
-        ) {
-            (None, None, _) => ConditionTree::NoCondition,
-            (before, after, order_by) => {
-                let field = match order_by {
-                    Some(order) => Arc::clone(&order.field),
-                    None => model.fields().id(),
-                };
+        match (query_arguments.before.as_ref(), query_arguments.after.as_ref()) {
+            (None, None) => ConditionTree::NoCondition,
+            (before, after) => {
+                let model_id = model.fields().id();
+                let order_by = &query_arguments.order_by;
 
-                let sort_order: SortOrder = order_by.map(|order| order.sort_order).unwrap_or(SortOrder::Ascending);
+                // Keys after the id never decide, as ids are unique.
+                let (keys, id_order) = match order_by
+                    .iter()
+                    .position(|oby| oby.path.is_empty() && oby.field.name == model_id.name)
+                {
+                    Some(position) => (&order_by[..position], order_by[position].sort_order),
+                    None => (&order_by[..], SortOrder::Ascending),
+                };
 
                 let cursor_for = |cursor_type: CursorType, id: GraphqlId| {
-                    let model_id = model.fields().id();
-                    let where_condition = model_id.as_column().equals(id.clone());
+                    // Sort keys on related models are read through the same joins as in the query.
+                    let cursor_value = |index: usize, oby: &OrderBy| {
+                        Ordering::joins_for(Select::from_table(model.table()), &model, index, oby)
+                            .column(Ordering::column(index, oby))
+                            .so_that(ConditionTree::single(model_id.as_column().equals(id.clone())))
+                    };
 
-                    let select_query = Select::from_table(model.table())
-                        .column(field.as_column())
-                        .so_that(ConditionTree::single(where_condition));
+                    let compare =
+                        |column: Column, value: DatabaseValue, sort_order: SortOrder| match (cursor_type, sort_order) {
+                            (CursorType::Before, SortOrder::Ascending) | (CursorType::After, SortOrder::Descending) => {
+                                column.less_than(value)
+                            }
+                            (CursorType::Before, SortOrder::Descending) | (CursorType::After, SortOrder::Ascending) => {
+                                column.greater_than(value)
+                            }
+                        };
 
-                    let compare = match (cursor_type, sort_order) {
-                        (CursorType::Before, SortOrder::Ascending) => field
-                            .as_column()
-                            .equals(select_query.clone())
-                            .and(model_id.as_column().less_than(id))
-                            .or(field.as_column().less_than(select_query)),
-                        (CursorType::Before, SortOrder::Descending) => field
-                            .as_column()
-                            .equals(select_query.clone())
-                            .and(model_id.as_column().less_than(id))
-                            .or(field.as_column().greater_than(select_query)),
-                        (CursorType::After, SortOrder::Ascending) => field
-                            .as_column()
-                            .equals(select_query.clone())
-                            .and(model_id.as_column().greater_than(id))
-                            .or(field.as_column().greater_than(select_query)),
-                        (CursorType::After, SortOrder::Descending) => field
-                            .as_column()
-                            .equals(select_query.clone())
-                            .and(model_id.as_column().greater_than(id))
-                            .or(field.as_column().less_than(select_query)),
-                    };
+                    let id_condition =
+                        ConditionTree::single(compare(model_id.as_column(), id.clone().into(), id_order));
 
-                    ConditionTree::single(compare)
+                    keys.iter().enumerate().rev().fold(id_condition, |acc, (index, oby)| {
+                        let value = cursor_value(index, oby);
+                        let column = Ordering::column(index, oby);
+
+                        let tie = column.clone().equals(value.clone()).and(acc);
+                        ConditionTree::single(tie.or(compare(column, value.into(), oby.sort_order)))
+                    })
                 };
 
                 let after_cursor = after
-                    .map(|id| {
-                        let id_val = id.clone();
-                        cursor_for(CursorType::After, id_val)
-                    })
+                    .map(|id| cursor_for(CursorType::After, id.clone()))
                     .unwrap_or(ConditionTree::NoCondition);
 
                 let before_cursor = before
-                    .map(|id| {
-                        let id_val = id.clone();
-                        cursor_for(CursorType::Before, id_val)
-                    })
+                    .map(|id| cursor_for(CursorType::Before, id.clone()))
                     .unwrap_or(ConditionTree::NoCondition);
 
                 ConditionTree::and(after_cursor, before_cursor)
@@ -1,94 +1,11 @@
-use crate::SelectDefinition;
+use crate::{SelectDefinition, SqlTransaction};
 use connector::{
     filter::{Filter, NodeSelector},
     ConnectorResult,
 };
 use prisma_models::*;
-use prisma_query::ast::*;
-use rusqlite::{Row, Transaction};
 
 pub trait DatabaseRead {
-    /// Execute the `SELECT` and return a vector mapped with `F`.
-    ///
-    /// ```rust
-    /// # use rusqlite::{Connection, NO_PARAMS};
-    /// # use sqlite_connector::*;
-    /// # use prisma_query::ast::*;
-    /// # let mut conn = Connection::open_in_memory().unwrap();
-    /// # let trans = conn.transaction().unwrap();
-    /// trans.execute(
-    ///     "CREATE TABLE users (id, name Text);",
-    ///     NO_PARAMS
-    /// );
-    ///
-    /// trans.execute(
-    ///     "INSERT INTO users (id, name) VALUES (1, 'Bob');",
-    ///     NO_PARAMS
-    /// );
-    ///
-    /// #[derive(Debug, PartialEq)]
-    /// struct User {
-    ///     id: i64,
-    ///     name: String,
-    /// };
-    ///
-    /// let select = Select::from_table("users")
-    ///     .column("id")
-    ///     .column("name");
-    ///
-    /// let users = Sqlite::query(&trans, select, |row| Ok(User {
-    ///     id: row.get(0),
-    ///     name: row.get(1),
-    /// })).unwrap();
-    ///
-    /// assert_eq!(
-    ///     vec![User { id: 1, name: String::from("Bob") }],
-    ///     users,
-    /// );
-    /// ```
-    fn query<F, T, S>(conn: &Transaction, query: S, f: F) -> ConnectorResult<Vec<T>>
-    where
-        F: FnMut(&Row) -> ConnectorResult<T>,
-        S: Into<Select>;
-
-    /// Count the records of the given query.
-    ///
-    /// ```rust
-    /// # use rusqlite::{Connection, NO_PARAMS};
-    /// # use sqlite_connector::*;
-    /// # use prisma_query::ast::*;
-    /// # let mut conn = Connection::open_in_memory().unwrap();
-    /// # let trans = conn.transaction().unwrap();
-    /// trans.execute(
-    ///     "CREATE TABLE users (id, name Text);",
-    ///     NO_PARAMS
-    /// ).unwrap();
-    ///
-    /// trans.execute(
-    ///     "INSERT INTO users (id, name) VALUES (1, 'Bob');",
-    ///     NO_PARAMS
-    /// ).unwrap();
-    ///
-    /// trans.execute(
-    ///     "INSERT INTO users (id, name) VALUES (2, 'Alice');",
-    ///     NO_PARAMS
-    /// ).unwrap();
-    ///
-    /// assert_eq!(
-    ///     2,
-    ///     Sqlite::count(&trans, "users", ConditionTree::default()).unwrap(),
-    /// );
-    ///
-    /// assert_eq!(
-    ///     1,
-    ///     Sqlite::count(&trans, "users", "name".equals("Alice")).unwrap(),
-    /// );
-    /// ```
-    fn count<C, T>(conn: &Transaction, table: T, into_args: C) -> ConnectorResult<usize>
-    where
-        C: Into<ConditionTree>,
-        T: Into<Table>;
-
     /// Find all ids from the `Model` with the filter being true.
     ///
     /// ```rust
@@ -117,11 +34,13 @@ pub trait DatabaseRead {
     /// let find_bob = ScalarFilter {
     ///     field: Arc::clone(&name_field),
     ///     condition: ScalarCondition::Equals(PrismaValue::from("Bob")),
+    ///     mode: StringMode::Default,
     /// };
     ///
     /// let find_alice = ScalarFilter {
     ///     field: name_field,
     ///     condition: ScalarCondition::Equals(PrismaValue::from("Alice")),
+    ///     mode: StringMode::Default,
     /// };
     ///
     /// let filter = Filter::or(vec![
@@ -136,7 +55,7 @@ pub trait DatabaseRead {
     ///     ids,
     /// );
     /// ```
-    fn ids_for<T>(conn: &Transaction, model: ModelRef, into_args: T) -> ConnectorResult<Vec<GraphqlId>>
+    fn ids_for<T>(conn: &dyn SqlTransaction, model: ModelRef, into_args: T) -> ConnectorResult<Vec<GraphqlId>>
     where
         T: SelectDefinition;
 
@@ -171,7 +90,7 @@ pub trait DatabaseRead {
     ///     Sqlite::id_for(&trans, &find_bob).unwrap(),
     /// );
     /// ```
-    fn id_for(conn: &Transaction, node_selector: &NodeSelector) -> ConnectorResult<GraphqlId>;
+    fn id_for(conn: &dyn SqlTransaction, node_selector: &NodeSelector) -> ConnectorResult<GraphqlId>;
 
     /// Find the node for the given selector, selecting all scalar fields.
     ///
@@ -214,12 +133,12 @@ pub trait DatabaseRead {
     ///     single_node.node.values,
     /// );
     /// ```
-    fn find_node(conn: &Transaction, node_selector: &NodeSelector) -> ConnectorResult<SingleNode>;
+    fn find_node(conn: &dyn SqlTransaction, node_selector: &NodeSelector) -> ConnectorResult<SingleNode>;
 
     /// Find a child of a parent. Will return an error if no child found with
     /// the given parameters. A more restrictive version of `get_ids_by_parents`.
     fn get_id_by_parent(
-        conn: &Transaction,
+        conn: &dyn SqlTransaction,
         parent_field: RelationFieldRef,
         parent_id: &GraphqlId,
         selector: &Option<NodeSelector>,
@@ -289,7 +208,7 @@ pub trait DatabaseRead {
     /// );
     /// ```
     fn get_ids_by_parents<T>(
-        conn: &Transaction,
+        conn: &dyn SqlTransaction,
         parent_field: RelationFieldRef,
         parent_id: Vec<&GraphqlId>,
         selector: Option<T>,
@@ -8,33 +8,105 @@ pub use delete::*;
 pub use relation::*;
 pub use update::*;
 
+use crate::SqlTransaction;
 use connector::{
-    mutaction::{DatabaseMutactionResults, NestedDatabaseMutaction, TopLevelDatabaseMutaction},
+    mutaction::{DatabaseMutaction, DatabaseMutactionResults, NestedDatabaseMutaction, TopLevelDatabaseMutaction},
     ConnectorResult,
 };
 use prisma_models::GraphqlId;
 use prisma_query::ast::Query;
-use rusqlite::Transaction;
 
 pub trait DatabaseWrite {
     /// Execute a single statement in the database.
-    fn execute_one<T>(conn: &Transaction, query: T) -> ConnectorResult<()>
+    fn execute_one<T>(conn: &dyn SqlTransaction, query: T) -> ConnectorResult<()>
     where
         T: Into<Query>;
 
     /// Execute a multiple statements in the database.
-    fn execute_many<T>(conn: &Transaction, queries: Vec<T>) -> ConnectorResult<()>
+    fn execute_many<T>(conn: &dyn SqlTransaction, queries: Vec<T>) -> ConnectorResult<()>
     where
         T: Into<Query>;
 
+    /// Execute a top level or a nested mutaction, the latter requiring the id of its parent.
+    fn execute_mutaction(
+        conn: &dyn SqlTransaction,
+        mutaction: DatabaseMutaction,
+        parent_id: Option<GraphqlId>,
+    ) -> ConnectorResult<DatabaseMutactionResults>;
+
     fn execute_toplevel(
-        conn: &Transaction,
+        conn: &dyn SqlTransaction,
         mutaction: TopLevelDatabaseMutaction,
     ) -> ConnectorResult<DatabaseMutactionResults>;
 
     fn execute_nested(
-        conn: &Transaction,
+        conn: &dyn SqlTransaction,
         mutaction: NestedDatabaseMutaction,
         parent_id: GraphqlId,
     ) -> ConnectorResult<DatabaseMutactionResults>;
+
+    /// Executes the nested mutactions of every result recursively in the same transaction.
+    /// Results are handled in the order they were returned, and the result of a parent is pushed
+    /// after the results of its children, keeping `pop` on the returned results pointing to the
+    /// mutaction originally requested.
+    ///
+    /// ```rust
+    /// # use prisma_models::*;
+    /// # use rusqlite::{Connection, NO_PARAMS};
+    /// # use sqlite_connector::*;
+    /// # use connector::mutaction::*;
+    /// # use serde_json;
+    /// # use std::fs::File;
+    /// # let mut conn = Connection::open_in_memory().unwrap();
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # conn.execute("ATTACH DATABASE ':memory:' AS 'test'", NO_PARAMS).unwrap();
+    /// # let trans = conn.transaction().unwrap();
+    /// # trans.execute("CREATE TABLE test.User (id Text, name Text);", NO_PARAMS).unwrap();
+    /// # trans.execute("CREATE TABLE test.Site (id Text, name Text);", NO_PARAMS).unwrap();
+    /// # trans.execute("CREATE TABLE test._UserToSites (A Text, B Text, id Text);", NO_PARAMS).unwrap();
+    /// # trans.execute("CREATE TABLE test.User_cats (nodeId Text, position Integer, value Text);", NO_PARAMS).unwrap();
+    /// # trans.execute("CREATE TABLE test.Site_tags (nodeId Text, position Integer, value Text);", NO_PARAMS).unwrap();
+    /// # let count = |table: &str| -> i64 {
+    /// #     trans.query_row(&format!("SELECT COUNT(*) FROM test.{}", table), NO_PARAMS, |row| row.get(0)).unwrap()
+    /// # };
+    /// let user = schema.find_model("User").unwrap();
+    ///
+    /// let mut site = PrismaArgs::new();
+    /// site.insert("id", GraphqlId::from("site1"));
+    /// site.insert("name", "A Cat Blog");
+    ///
+    /// let mut nested_mutactions = NestedMutactions::default();
+    /// nested_mutactions.creates.push(NestedCreateNode {
+    ///     relation_field: user.fields().find_from_relation_fields("sites").unwrap(),
+    ///     non_list_args: site,
+    ///     list_args: Vec::new(),
+    ///     top_is_create: true,
+    ///     nested_mutactions: NestedMutactions::default(),
+    /// });
+    ///
+    /// let mut args = PrismaArgs::new();
+    /// args.insert("id", GraphqlId::from("user1"));
+    /// args.insert("name", "Bob");
+    ///
+    /// let create = TopLevelDatabaseMutaction::CreateNode(CreateNode {
+    ///     model: user,
+    ///     non_list_args: args,
+    ///     list_args: Vec::new(),
+    ///     nested_mutactions,
+    /// });
+    ///
+    /// // The mutaction itself only creates the user, as the native bridge expects.
+    /// let results = Sqlite::execute_toplevel(&trans, create).unwrap();
+    /// assert_eq!((1, 0), (count("User"), count("Site")));
+    ///
+    /// // The nested create runs once, connected to the user.
+    /// Sqlite::execute_nested_tree(&trans, results).unwrap();
+    /// assert_eq!((1, 1, 1), (count("User"), count("Site"), count("_UserToSites")));
+    /// ```
+    fn execute_nested_tree(
+        conn: &dyn SqlTransaction,
+        own_results: DatabaseMutactionResults,
+    ) -> ConnectorResult<DatabaseMutactionResults>;
 }
@@ -1,13 +1,15 @@
+use crate::SqlTransaction;
 use connector::{
     filter::{Filter, NodeSelector},
+    mutaction::JsonUpdate,
     ConnectorResult,
 };
 use prisma_models::*;
-use rusqlite::Transaction;
 
 /// Functions to update records in the database.
 pub trait DatabaseUpdate {
-    /// Updates one record and any associated list record in the database.
+    /// Updates one record and any associated list record in the database. JSON fields
+    /// are partially updated with the `json_updates`.
     /// ```rust
     /// # use prisma_models::*;
     /// # use rusqlite::{Connection, NO_PARAMS};
@@ -49,6 +51,7 @@ pub trait DatabaseUpdate {
     ///     &selector,
     ///     &args,
     ///     &[("cats", vec![])],
+    ///     &[],
     /// ).unwrap();
     ///
     /// let record = Sqlite::find_node(&trans, &selector).unwrap();
@@ -56,10 +59,11 @@ pub trait DatabaseUpdate {
     /// assert_eq!(&PrismaValue::from("Alice"), record.get_field_value("name").unwrap());
     /// ```
     fn execute_update<T>(
-        conn: &Transaction,
+        conn: &dyn SqlTransaction,
         node_selector: &NodeSelector,
         non_list_args: &PrismaArgs,
         list_args: &[(T, PrismaListValue)],
+        json_updates: &[JsonUpdate],
     ) -> ConnectorResult<GraphqlId>
     where
         T: AsRef<str>;
@@ -121,7 +125,7 @@ pub trait DatabaseUpdate {
     /// assert_eq!(&PrismaValue::from("Brooke"), record.get_field_value("name").unwrap());
     /// ```
     fn execute_update_many<T>(
-        conn: &Transaction,
+        conn: &dyn SqlTransaction,
         model: ModelRef,
         filter: &Filter,
         non_list_args: &PrismaArgs,
@@ -200,6 +204,7 @@ pub trait DatabaseUpdate {
     ///     relation_field,
     ///     &args,
     ///     &[("tags", vec![])],
+    ///     &[],
     /// ).unwrap();
     ///
     /// assert_eq!(
@@ -213,12 +218,13 @@ pub trait DatabaseUpdate {
     /// assert_eq!(&PrismaValue::from("A Mouse Blog"), record.get_field_value("name").unwrap());
     /// ```
     fn execute_nested_update<T>(
-        conn: &Transaction,
+        conn: &dyn SqlTransaction,
         parent_id: &GraphqlId,
         node_selector: &Option<NodeSelector>,
         relation_field: RelationFieldRef,
         non_list_args: &PrismaArgs,
         list_args: &[(T, PrismaListValue)],
+        json_updates: &[JsonUpdate],
     ) -> ConnectorResult<GraphqlId>
     where
         T: AsRef<str>;
@@ -308,7 +314,7 @@ pub trait DatabaseUpdate {
     /// }
     /// ```
     fn execute_nested_update_many<T>(
-        conn: &Transaction,
+        conn: &dyn SqlTransaction,
         parent_id: &GraphqlId,
         filter: &Option<Filter>,
         relation_field: RelationFieldRef,
@@ -363,7 +369,7 @@ pub trait DatabaseUpdate {
     /// assert_eq!(2, Sqlite::count(&trans, "User_cats", ConditionTree::default()).unwrap())
     /// ```
     fn update_list_args<T>(
-        conn: &Transaction,
+        conn: &dyn SqlTransaction,
         ids: &[GraphqlId],
         model: ModelRef,
         list_args: &[(T, PrismaListValue)],
@@ -0,0 +1,103 @@
+use crate::{filter_conversion::AliasedCondition, ordering::Ordering};
+use connector::QueryArguments;
+use prisma_models::prelude::*;
+use prisma_query::ast::*;
+use std::sync::Arc;
+
+pub struct DistinctCondition;
+
+impl DistinctCondition {
+    const TABLE_ALIAS: &'static str = "prismaDistinctTable";
+    const RELATION_ALIAS: &'static str = "prismaDistinctRelation";
+    const PARENT_ALIAS: &'static str = "prismaDistinctParent";
+    const ROW_NUMBER_ALIAS: &'static str = "prismaDistinctRowNumber";
+
+    /// Records that come first in the order of the query among the records with equal values in
+    /// the `distinct` fields. Related records are distinct per parent, which is read from the
+    /// given relation column of the outer query.
+    ///
+    /// Only the filter is applied before picking the first records, so that cursors page through
+    /// the same distinct records.
+    pub fn build(
+        query_arguments: &QueryArguments,
+        model: ModelRef,
+        parent: Option<(&RelationFieldRef, Column)>,
+    ) -> ConditionTree {
+        if query_arguments.distinct.is_empty() {
+            return ConditionTree::NoCondition;
+        }
+
+        let id_field = model.fields().id();
+        let order_by = &query_arguments.order_by;
+        let ordering = Ordering::for_model(Arc::clone(&model), order_by, false);
+
+        let filter: ConditionTree = query_arguments
+            .filter
+            .clone()
+            .map(|f| f.aliased_cond(None))
+            .unwrap_or(ConditionTree::NoCondition);
+
+        let select = Ordering::joins(Select::from_table(model.table()), &model, order_by)
+            .column(id_field.as_column())
+            .so_that(filter);
+
+        let (from_field, outer_parent_column) = match parent {
+            Some((from_field, column)) => (Some(from_field), Some(column)),
+            None => (None, None),
+        };
+
+        let (select, parent_column) = match from_field {
+            Some(from_field) => {
+                let relation = from_field.relation();
+                let parent_column = relation
+                    .column_for_relation_side(from_field.relation_side)
+                    .table(Self::RELATION_ALIAS);
+
+                let child_column = relation
+                    .column_for_relation_side(from_field.relation_side.opposite())
+                    .table(Self::RELATION_ALIAS);
+
+                let select = select
+                    .column(parent_column.clone().alias(Self::PARENT_ALIAS))
+                    .inner_join(
+                        relation
+                            .relation_table()
+                            .alias(Self::RELATION_ALIAS)
+                            .on(child_column.equals(id_field.as_column())),
+                    );
+
+                (select, Some(parent_column))
+            }
+            None => (select, None),
+        };
+
+        // Records are numbered per parent and combination of distinct values.
+        let row_number = parent_column
+            .into_iter()
+            .chain(query_arguments.distinct.iter().map(|field| field.as_column()))
+            .fold(
+                ordering.into_iter().fold(row_number(), |acc, ord| acc.order_by(ord)),
+                |acc, column| acc.partition_by(column),
+            );
+
+        let row_number: Function = row_number.into();
+        let numbered = select.value(row_number.alias(Self::ROW_NUMBER_ALIAS));
+
+        let first_records =
+            ConditionTree::single(Column::from((Self::TABLE_ALIAS, Self::ROW_NUMBER_ALIAS)).equals(1i64));
+
+        let conditions = match outer_parent_column {
+            Some(outer_parent_column) => {
+                let same_parent = Column::from((Self::TABLE_ALIAS, Self::PARENT_ALIAS)).equals(outer_parent_column);
+                ConditionTree::and(first_records, ConditionTree::single(same_parent))
+            }
+            None => first_records,
+        };
+
+        let first_ids = Select::from_table(Table::from(numbered).alias(Self::TABLE_ALIAS))
+            .column((Self::TABLE_ALIAS, id_field.db_name()))
+            .so_that(conditions);
+
+        ConditionTree::single(id_field.as_column().in_selection(first_ids))
+    }
+}
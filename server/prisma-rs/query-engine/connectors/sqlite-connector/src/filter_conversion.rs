@@ -1,3 +1,4 @@
+use crate::{full_text_search::FullTextSearch, json::Json};
 use connector::filter::*;
 use prisma_models::prelude::*;
 use prisma_query::ast::*;
@@ -161,6 +162,8 @@ impl AliasedCondition for Filter {
             Filter::Scalar(filter) => filter.aliased_cond(alias),
             Filter::OneRelationIsNull(filter) => filter.aliased_cond(alias),
             Filter::Relation(filter) => filter.aliased_cond(alias),
+            Filter::ScalarList(filter) => filter.aliased_cond(alias),
+            Filter::Json(filter) => filter.aliased_cond(alias),
             Filter::BoolFilter(b) => {
                 if b {
                     ConditionTree::NoCondition
@@ -168,7 +171,8 @@ impl AliasedCondition for Filter {
                     ConditionTree::NegativeCondition
                 }
             }
-            _ => unimplemented!(),
+            // Subscriptions already select the node in question.
+            Filter::NodeSubscription => ConditionTree::NoCondition,
         }
     }
 }
@@ -191,7 +195,8 @@ impl AliasedCondition for ScalarFilter {
     ///
     /// let sf = ScalarFilter {
     ///     field: Arc::clone(&field),
-    ///     condition: ScalarCondition::Equals(PrismaValue::from("foo"))
+    ///     condition: ScalarCondition::Equals(PrismaValue::from("foo")),
+    ///     mode: StringMode::Default,
     /// };
     ///
     /// let expected: ConditionTree = (("test", "User"), "name").equals("foo").into();
@@ -208,32 +213,224 @@ impl AliasedCondition for ScalarFilter {
             None => self.field.as_column(),
         };
 
-        let condition = match self.condition {
-            ScalarCondition::Equals(PrismaValue::Null) => column.is_null(),
-            ScalarCondition::NotEquals(PrismaValue::Null) => column.is_not_null(),
-            ScalarCondition::Equals(value) => column.equals(value),
-            ScalarCondition::NotEquals(value) => column.not_equals(value),
-            ScalarCondition::Contains(value) => column.like(format!("{}", value)),
-            ScalarCondition::NotContains(value) => column.not_like(format!("{}", value)),
-            ScalarCondition::StartsWith(value) => column.begins_with(format!("{}", value)),
-            ScalarCondition::NotStartsWith(value) => column.not_begins_with(format!("{}", value)),
-            ScalarCondition::EndsWith(value) => column.ends_into(format!("{}", value)),
-            ScalarCondition::NotEndsWith(value) => column.not_ends_into(format!("{}", value)),
-            ScalarCondition::LessThan(value) => column.less_than(value),
-            ScalarCondition::LessThanOrEquals(value) => column.less_than_or_equals(value),
-            ScalarCondition::GreaterThan(value) => column.greater_than(value),
-            ScalarCondition::GreaterThanOrEquals(value) => column.greater_than_or_equals(value),
-            ScalarCondition::In(values) => match values.split_first() {
-                Some((PrismaValue::Null, tail)) if tail.is_empty() => column.is_null(),
-                _ => column.in_selection(values),
-            },
-            ScalarCondition::NotIn(values) => match values.split_first() {
-                Some((PrismaValue::Null, tail)) if tail.is_empty() => column.is_not_null(),
-                _ => column.not_in_selection(values),
-            },
+        if let ScalarCondition::Search(search) = self.condition {
+            let id = self.field.model().id_column();
+
+            let id_column = match alias {
+                Some(ref alias) => id.table(alias.to_string(None)),
+                None => id,
+            };
+
+            return FullTextSearch::condition(id_column, &self.field, &search);
+        }
+
+        // Only strings have a case to ignore.
+        let mode = match self.field.type_identifier {
+            TypeIdentifier::String => self.mode,
+            _ => StringMode::Default,
         };
 
-        ConditionTree::single(condition)
+        scalar_condition(column, self.condition, mode)
+    }
+}
+
+impl AliasedCondition for ScalarListFilter {
+    /// Conversion from a `ScalarListFilter` to a query condition tree. Aliased when in a nested `SELECT`.
+    ///
+    /// The values of a list field are stored in a separate table, so the condition selects the
+    /// ids of the records having matching values in there.
+    ///
+    /// ```rust
+    /// # use sqlite_connector::*;
+    /// # use prisma_models::*;
+    /// # use connector::filter::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let template: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+    /// let schema = template.build(String::from("test"));
+    /// let model = schema.find_model("User").unwrap();
+    /// let field = model.fields().find_from_scalar("cats").unwrap();
+    ///
+    /// let slf = ScalarListFilter {
+    ///     field: Arc::clone(&field),
+    ///     condition: ScalarListCondition::Contains(PrismaValue::Int(1)),
+    /// };
+    ///
+    /// let sub_select = Select::from_table(("test", "User_cats"))
+    ///     .column((("test", "User_cats"), "nodeId"))
+    ///     .so_that((("test", "User_cats"), "value").equals(PrismaValue::Int(1)));
+    ///
+    /// let expected: ConditionTree = (("test", "User"), "id").in_selection(sub_select).into();
+    /// assert_eq!(expected, slf.aliased_cond(None));
+    /// ```
+    ///
+    /// Records with an empty list contain none of the values, and every list contains all of no
+    /// values.
+    ///
+    /// ```rust
+    /// # use sqlite_connector::*;
+    /// # use prisma_models::*;
+    /// # use connector::filter::*;
+    /// # use rusqlite::{Connection, NO_PARAMS};
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// # let mut conn = Connection::open_in_memory().unwrap();
+    /// #
+    /// # let template: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+    /// # let schema = template.build(String::from("test"));
+    /// # conn.execute("ATTACH DATABASE ':memory:' AS 'test'", NO_PARAMS).unwrap();
+    /// # let trans = conn.transaction().unwrap();
+    /// # trans.execute("CREATE TABLE test.User (id Text, name Text);", NO_PARAMS).unwrap();
+    /// # trans.execute("CREATE TABLE test.User_cats (nodeId Text, position Int, value Int);", NO_PARAMS).unwrap();
+    /// trans.execute(
+    ///     "INSERT INTO test.User (id, name) VALUES ('alice', 'Alice'), ('bob', 'Bob'), ('eve', 'Eve');",
+    ///     NO_PARAMS
+    /// ).unwrap();
+    ///
+    /// // Alice has the cats 1 and 2, Bob has the cat 2 and Eve has none.
+    /// trans.execute(
+    ///     "INSERT INTO test.User_cats (nodeId, position, value) \
+    ///         VALUES ('alice', 1, 1), ('alice', 2, 2), ('bob', 1, 2);",
+    ///     NO_PARAMS
+    /// ).unwrap();
+    ///
+    /// let model = schema.find_model("User").unwrap();
+    /// let field = model.fields().find_from_scalar("cats").unwrap();
+    /// let selected_fields = SelectedFields::from(model.fields().id());
+    ///
+    /// let users = |condition: ScalarListCondition| {
+    ///     let filter = Filter::from(ScalarListFilter { field: Arc::clone(&field), condition });
+    ///     let query = QueryBuilder::get_nodes(Arc::clone(&model), &selected_fields, filter);
+    ///
+    ///     Sqlite::query(&trans, query, |row| Ok(row.get::<_, String>(0))).unwrap()
+    /// };
+    ///
+    /// let cats = |values: &[i32]| values.iter().map(|value| PrismaValue::Int(*value)).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(vec!["alice", "bob"], users(ScalarListCondition::Contains(PrismaValue::Int(2))));
+    /// assert_eq!(vec!["alice"], users(ScalarListCondition::ContainsEvery(cats(&[1, 2]))));
+    /// assert_eq!(vec!["alice", "bob", "eve"], users(ScalarListCondition::ContainsEvery(cats(&[]))));
+    /// assert_eq!(vec!["alice", "bob"], users(ScalarListCondition::ContainsSome(cats(&[2, 3]))));
+    /// assert!(users(ScalarListCondition::ContainsSome(cats(&[3]))).is_empty());
+    /// ```
+    fn aliased_cond(self, alias: Option<Alias>) -> ConditionTree {
+        let id = self.field.model().id_column();
+
+        let column = match alias {
+            Some(ref alias) => id.table(alias.to_string(None)),
+            None => id,
+        };
+
+        let list_table = self.field.scalar_list_table();
+
+        let node_ids = |condition: Compare| {
+            let sub_select = Select::from_table(list_table.table())
+                .column(list_table.node_id_column())
+                .so_that(condition);
+
+            ConditionTree::single(column.clone().in_selection(sub_select))
+        };
+
+        match self.condition {
+            ScalarListCondition::Contains(value) => node_ids(list_table.value_column().equals(value)),
+            ScalarListCondition::ContainsSome(values) => node_ids(list_table.value_column().in_selection(values)),
+            // Every value has to be found in the list of the record, so each one is a condition.
+            ScalarListCondition::ContainsEvery(values) => values
+                .into_iter()
+                .map(|value| node_ids(list_table.value_column().equals(value)))
+                .fold(ConditionTree::NoCondition, |acc, condition| match acc {
+                    ConditionTree::NoCondition => condition,
+                    acc => ConditionTree::and(acc, condition),
+                }),
+        }
+    }
+}
+
+impl AliasedCondition for JsonFilter {
+    /// Conversion from a `JsonFilter` to a query condition tree. Aliased when in a nested `SELECT`.
+    ///
+    /// Conditions on a value also check its JSON type, as the SQL values read from documents
+    /// don't tell strings apart from arrays and objects.
+    ///
+    /// ```rust
+    /// # use sqlite_connector::*;
+    /// # use prisma_models::*;
+    /// # use connector::filter::*;
+    /// # use rusqlite::{Connection, NO_PARAMS};
+    /// # use serde_json::{self, json};
+    /// # use std::{fs::File, sync::Arc};
+    /// # let mut conn = Connection::open_in_memory().unwrap();
+    /// #
+    /// # let template: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+    /// # let schema = template.build(String::from("test"));
+    /// # conn.execute("ATTACH DATABASE ':memory:' AS 'test'", NO_PARAMS).unwrap();
+    /// # let trans = conn.transaction().unwrap();
+    /// # trans.execute("CREATE TABLE test.Site (id Text, name Text, meta Text);", NO_PARAMS).unwrap();
+    /// trans.execute(
+    ///     r#"INSERT INTO test.Site (id, meta) VALUES
+    ///         ('site1', '{"a": {"b": 3}, "tags": ["x", "y"], "title": "Cats"}'),
+    ///         ('site2', '{"a": {"b": "3"}, "tags": "x y", "title": null}'),
+    ///         ('site3', NULL),
+    ///         ('site4', '{"a.b": 3, "list": [{"c": true}]}');"#,
+    ///     NO_PARAMS
+    /// ).unwrap();
+    ///
+    /// let model = schema.find_model("Site").unwrap();
+    /// let field = model.fields().find_from_scalar("meta").unwrap();
+    /// let selected_fields = SelectedFields::from(model.fields().id());
+    ///
+    /// let sites = |path: &[&str], condition: JsonCondition| {
+    ///     let path = path.iter().map(|segment| segment.to_string()).collect();
+    ///     let filter = Filter::Json(JsonFilter { field: Arc::clone(&field), path, condition });
+    ///     let query = QueryBuilder::get_nodes(Arc::clone(&model), &selected_fields, filter);
+    ///
+    ///     Sqlite::query(&trans, query, |row| Ok(row.get::<_, String>(0))).unwrap()
+    /// };
+    ///
+    /// // Numbers and strings are different values.
+    /// assert_eq!(vec!["site1"], sites(&["a", "b"], JsonCondition::Equals(json!(3))));
+    /// assert_eq!(vec!["site2"], sites(&["a", "b"], JsonCondition::Equals(json!("3"))));
+    ///
+    /// // Keys are never split at dots, and numbers are array indices.
+    /// assert_eq!(vec!["site4"], sites(&["a.b"], JsonCondition::Equals(json!(3))));
+    /// assert_eq!(vec!["site4"], sites(&["list", "0", "c"], JsonCondition::Equals(json!(true))));
+    ///
+    /// // Only explicit nulls equal null, missing values and documents don't.
+    /// assert_eq!(vec!["site2"], sites(&["title"], JsonCondition::Equals(json!(null))));
+    ///
+    /// assert_eq!(vec!["site1"], sites(&["tags"], JsonCondition::ArrayContains(json!("x"))));
+    /// assert_eq!(vec!["site2"], sites(&["tags"], JsonCondition::StringContains(String::from("x"))));
+    ///
+    /// // Without a path the condition applies to the whole document.
+    /// let document = json!({"a": {"b": 3}, "tags": ["x", "y"], "title": "Cats"});
+    /// assert_eq!(vec!["site1"], sites(&[], JsonCondition::Equals(document)));
+    /// ```
+    fn aliased_cond(self, alias: Option<Alias>) -> ConditionTree {
+        let column = match alias {
+            Some(ref alias) => self.field.as_column().table(alias.to_string(None)),
+            None => self.field.as_column(),
+        };
+
+        let path = Json::path(&self.path);
+        let json_type = json_type(column.clone(), path.clone());
+
+        match self.condition {
+            JsonCondition::Equals(serde_json::Value::Null) => ConditionTree::single(json_type.equals("null")),
+            JsonCondition::Equals(value) => {
+                ConditionTree::single(json_extract(column, path).equals(Json::extracted(&value)))
+            }
+            JsonCondition::ArrayContains(value) => {
+                let elements = Select::from_table(json_each(column, path)).column("value");
+                json_type
+                    .equals("array")
+                    .and(Json::extracted(&value).in_selection(elements))
+            }
+            JsonCondition::StringContains(value) => json_type
+                .equals("text")
+                .and(instr(json_extract(column, path), value).greater_than(0)),
+        }
     }
 }
 
@@ -461,3 +658,68 @@ impl AliasedCondition for OneRelationIsNullFilter {
         ConditionTree::single(condition)
     }
 }
+
+/// The condition on a column, shared by scalar filters and conditions on aggregated values.
+///
+/// Substrings are matched with `replace` and `substr` instead of `LIKE` patterns, so that `%` and
+/// `_` in the values are not taken as wildcards and matches are case-sensitive like equality. A
+/// string contains a value if removing the value changes it, which every database tells the same
+/// way.
+/// Insensitive conditions compare the lowercased column with lowercased values. SQLite only folds
+/// the case of ASCII characters, so the values are folded the same way.
+pub(crate) fn scalar_condition(column: Column, condition: ScalarCondition, mode: StringMode) -> ConditionTree {
+    let expression: DatabaseValue = match mode {
+        StringMode::Default => column.into(),
+        StringMode::Insensitive => lower(column).into(),
+    };
+
+    let fold = |value: PrismaValue| match (mode, value) {
+        (StringMode::Insensitive, PrismaValue::String(s)) => PrismaValue::String(s.to_ascii_lowercase()),
+        (_, value) => value,
+    };
+
+    let char_count = |value: &PrismaValue| format!("{}", value).chars().count() as i64;
+
+    // Every string contains the empty string, which can't be removed from it.
+    match condition {
+        ScalarCondition::Contains(ref value) if char_count(value) == 0 => {
+            return ConditionTree::single(expression.is_not_null());
+        }
+        ScalarCondition::NotContains(ref value) if char_count(value) == 0 => return ConditionTree::NegativeCondition,
+        _ => (),
+    }
+
+    let condition = match condition {
+        ScalarCondition::Equals(PrismaValue::Null) => expression.is_null(),
+        ScalarCondition::NotEquals(PrismaValue::Null) => expression.is_not_null(),
+        ScalarCondition::Equals(value) => expression.equals(fold(value)),
+        ScalarCondition::NotEquals(value) => expression.not_equals(fold(value)),
+        ScalarCondition::Contains(value) => replace(expression.clone(), fold(value), "").not_equals(expression),
+        ScalarCondition::NotContains(value) => replace(expression.clone(), fold(value), "").equals(expression),
+        ScalarCondition::StartsWith(value) => substr(expression, 1, char_count(&value)).equals(fold(value)),
+        ScalarCondition::NotStartsWith(value) => substr(expression, 1, char_count(&value)).not_equals(fold(value)),
+        ScalarCondition::EndsWith(value) => last_chars(expression, char_count(&value)).equals(fold(value)),
+        ScalarCondition::NotEndsWith(value) => last_chars(expression, char_count(&value)).not_equals(fold(value)),
+        ScalarCondition::LessThan(value) => expression.less_than(fold(value)),
+        ScalarCondition::LessThanOrEquals(value) => expression.less_than_or_equals(fold(value)),
+        ScalarCondition::GreaterThan(value) => expression.greater_than(fold(value)),
+        ScalarCondition::GreaterThanOrEquals(value) => expression.greater_than_or_equals(fold(value)),
+        ScalarCondition::In(values) => match values.split_first() {
+            Some((PrismaValue::Null, tail)) if tail.is_empty() => expression.is_null(),
+            _ => expression.in_selection(values.into_iter().map(fold).collect::<Vec<_>>()),
+        },
+        ScalarCondition::NotIn(values) => match values.split_first() {
+            Some((PrismaValue::Null, tail)) if tail.is_empty() => expression.is_not_null(),
+            _ => expression.not_in_selection(values.into_iter().map(fold).collect::<Vec<_>>()),
+        },
+        ScalarCondition::Search(_) => unreachable!("Searches use the full-text search table of the model"),
+    };
+
+    ConditionTree::single(condition)
+}
+
+/// The last `n` characters of the expression. Only some databases count negative positions of
+/// `substr` from the end, so the position is computed from the length.
+fn last_chars(expression: DatabaseValue, n: i64) -> Function {
+    substr(expression.clone(), length(expression) - (n - 1), n)
+}
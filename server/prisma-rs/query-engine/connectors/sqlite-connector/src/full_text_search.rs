@@ -0,0 +1,85 @@
+use prisma_models::prelude::*;
+use prisma_query::ast::*;
+
+pub struct FullTextSearch;
+
+/// Searches in the FTS5 table of a model, see `FullTextSearchTable`.
+impl FullTextSearch {
+    /// Records matching all words of the search in the given field.
+    pub fn condition(id_column: Column, field: &ScalarField, search: &str) -> ConditionTree {
+        match Self::search_condition(field, search) {
+            ConditionTree::NegativeCondition => ConditionTree::NegativeCondition,
+            condition => {
+                let model = field.model();
+                let fts = model.full_text_search_table();
+
+                let matching_ids = Select::from_table(fts.table())
+                    .column(fts.node_id_column())
+                    .so_that(condition);
+
+                ConditionTree::single(id_column.in_selection(matching_ids))
+            }
+        }
+    }
+
+    /// Left joins the rank of the records of `model` in the search, aliased as `table_alias`.
+    /// Records not matching the search rank after all matches in the given order, so that they
+    /// come last whichever way the ranks are sorted and cursors compare them like any rank.
+    pub fn rank_join(
+        select: Select,
+        model: &ModelRef,
+        table_alias: &str,
+        field: &ScalarField,
+        search: &str,
+        sort_order: SortOrder,
+    ) -> Select {
+        let fts = model.full_text_search_table();
+        let id = model.fields().id().as_column();
+        let matches_alias = format!("{}Matches", table_alias);
+
+        let matches = Select::from_table(fts.table())
+            .column(fts.node_id_column())
+            .column(fts.rank_column())
+            .so_that(Self::search_condition(field, search));
+
+        let no_match = match sort_order {
+            SortOrder::Ascending => std::f64::MAX,
+            SortOrder::Descending => std::f64::MIN,
+        };
+
+        let matched_rank = Column::from((matches_alias.as_str(), FullTextSearchTable::RANK_FIELD_NAME));
+        let matched_id = Column::from((matches_alias.as_str(), FullTextSearchTable::NODE_ID_FIELD_NAME));
+
+        let ranks = Select::from_table(model.table())
+            .column(id.clone().alias(FullTextSearchTable::NODE_ID_FIELD_NAME))
+            .value(coalesce(matched_rank, no_match).alias(FullTextSearchTable::RANK_FIELD_NAME))
+            .left_outer_join(
+                Table::from(matches)
+                    .alias(matches_alias)
+                    .on(matched_id.equals(id.clone())),
+            );
+
+        let node_id = Column::from((table_alias, FullTextSearchTable::NODE_ID_FIELD_NAME));
+
+        select.left_outer_join(Table::from(ranks).alias(table_alias.to_string()).on(node_id.equals(id)))
+    }
+
+    /// Matches the search in the column of the field. Every word is quoted, so that the search is
+    /// never read as FTS5 query syntax. A search without words matches nothing.
+    fn search_condition(field: &ScalarField, search: &str) -> ConditionTree {
+        let words: Vec<String> = search
+            .split_whitespace()
+            .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+            .collect();
+
+        if words.is_empty() {
+            return ConditionTree::NegativeCondition;
+        }
+
+        let model = field.model();
+        let fts = model.full_text_search_table();
+        let query = format!("{{{}}} : ({})", field.db_name(), words.join(" "));
+
+        ConditionTree::single(fts.search_column().equals(query))
+    }
+}
@@ -0,0 +1,40 @@
+use connector::mutaction::JsonOperation;
+use prisma_query::ast::*;
+
+pub struct Json;
+
+/// Tooling for the documents of JSON fields, which are stored as text and read with the functions
+/// of the SQLite JSON1 extension.
+impl Json {
+    /// The JSON path of the object keys and array indices, like `$."a"[0]`. Keys are quoted, so
+    /// that they may contain dots or brackets.
+    pub fn path(path: &[String]) -> String {
+        path.iter().fold(String::from("$"), |mut acc, segment| {
+            match segment.parse::<usize>() {
+                Ok(index) => acc.push_str(&format!("[{}]", index)),
+                Err(_) => acc.push_str(&format!(".\"{}\"", segment)),
+            }
+
+            acc
+        })
+    }
+
+    /// A JSON value the way `json_extract` reads it from a document, as an SQL value for scalars
+    /// and as minified text for arrays and objects, so that it compares to extracted values.
+    pub fn extracted(value: &serde_json::Value) -> Function {
+        json_extract(value.to_string(), "$").into()
+    }
+
+    /// The document in the column after the operation. Columns without a document are taken as
+    /// an empty object.
+    pub fn update(column: Column, operation: &JsonOperation) -> Function {
+        let document = coalesce(column, "{}");
+
+        match operation {
+            JsonOperation::SetPath { path, value } => {
+                json_set(document, Self::path(path), json(value.to_string())).into()
+            }
+            JsonOperation::Merge(value) => json_patch(document, json(value.to_string())).into(),
+        }
+    }
+}
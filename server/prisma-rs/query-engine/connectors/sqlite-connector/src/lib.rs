@@ -12,21 +12,35 @@
 //! - [DatabaseWrite](trait.DatabaseWrite.html) for writing to the database.
 //! - [TransactionalExecutor](trait.TransactionalExecutor.html) for abstract transaction and connection handling.
 //!
-//! The implementation is currently only for [SQLite](struct.Sqlite.html).
+//! Reading and writing is implemented for every [SqlDatabase](trait.SqlDatabase.html), executing
+//! the queries of the builders in a [SqlTransaction](trait.SqlTransaction.html) of the database.
+//! The other SQL connectors use the builders and the shared implementation without the `sqlite`
+//! feature, that adds the connector for [SQLite](struct.Sqlite.html).
 mod cursor_condition;
 mod database_read;
 mod database_write;
+mod distinct_condition;
 mod filter_conversion;
+mod full_text_search;
+mod json;
 mod mutaction;
 mod ordering;
 mod query_builder;
+mod read;
+mod sql_database;
+#[cfg(feature = "sqlite")]
 mod sqlite;
+#[cfg(feature = "sqlite")]
 mod transactional_executor;
+mod write;
 
 pub use database_read::*;
 pub use database_write::*;
 pub use filter_conversion::*;
 pub use mutaction::*;
-pub use query_builder::SelectDefinition;
+pub use query_builder::{QueryBuilder, SelectDefinition};
+pub use sql_database::*;
+#[cfg(feature = "sqlite")]
 pub use sqlite::*;
+#[cfg(feature = "sqlite")]
 pub use transactional_executor::*;
@@ -1,7 +1,8 @@
 use prisma_models::prelude::*;
 use prisma_query::ast::*;
 
-use connector::{error::ConnectorError, ConnectorResult};
+use crate::json::Json;
+use connector::{error::ConnectorError, mutaction::JsonUpdate, ConnectorResult};
 
 pub struct MutationBuilder;
 
@@ -112,12 +113,22 @@ impl MutationBuilder {
         Some(result)
     }
 
-    pub fn update_one(model: ModelRef, id: &GraphqlId, args: &PrismaArgs) -> ConnectorResult<Option<Update>> {
-        Self::update_many(model, &[id; 1], args).map(|updates| updates.into_iter().next())
+    pub fn update_one(
+        model: ModelRef,
+        id: &GraphqlId,
+        args: &PrismaArgs,
+        json_updates: &[JsonUpdate],
+    ) -> ConnectorResult<Option<Update>> {
+        Self::update_many(model, &[id; 1], args, json_updates).map(|updates| updates.into_iter().next())
     }
 
-    pub fn update_many(model: ModelRef, ids: &[&GraphqlId], args: &PrismaArgs) -> ConnectorResult<Vec<Update>> {
-        if args.args.is_empty() || ids.is_empty() {
+    pub fn update_many(
+        model: ModelRef,
+        ids: &[&GraphqlId],
+        args: &PrismaArgs,
+        json_updates: &[JsonUpdate],
+    ) -> ConnectorResult<Vec<Update>> {
+        if (args.args.is_empty() && json_updates.is_empty()) || ids.is_empty() {
             return Ok(Vec::new());
         }
 
@@ -136,6 +147,11 @@ impl MutationBuilder {
             query = query.set(field.db_name(), value.clone());
         }
 
+        for update in json_updates {
+            let document = Json::update(update.field.as_column(), &update.operation);
+            query = query.set(update.field.db_name(), document);
+        }
+
         let result: Vec<Update> = ids
             .chunks(Self::PARAMETER_LIMIT)
             .into_iter()
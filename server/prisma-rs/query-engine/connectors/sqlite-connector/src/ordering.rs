@@ -1,3 +1,4 @@
+use crate::full_text_search::FullTextSearch;
 use prisma_models::prelude::*;
 use prisma_query::ast::*;
 
@@ -7,79 +8,145 @@ pub struct Ordering;
 
 /// Tooling for generating orderings for different query types.
 impl Ordering {
-    pub fn for_model(model: ModelRef, order_by: Option<&OrderBy>, reverse: bool) -> OrderVec {
+    pub fn for_model(model: ModelRef, order_by: &[OrderBy], reverse: bool) -> OrderVec {
         Self::by_fields(
-            order_by.map(|oby| oby.field.as_column()),
+            order_by
+                .iter()
+                .enumerate()
+                .map(|(index, oby)| (Self::column(index, oby), oby.sort_order))
+                .collect(),
             model.fields().id().as_column(),
-            order_by,
             reverse,
         )
     }
 
-    pub fn internal<C>(second_field: C, order_by: Option<&OrderBy>, reverse: bool) -> OrderVec
+    pub fn internal<C>(second_field: C, order_by: &[OrderBy], reverse: bool) -> OrderVec
     where
         C: Into<Column>,
     {
         Self::by_fields(
-            order_by.map(|oby| oby.field.as_column()),
+            order_by
+                .iter()
+                .enumerate()
+                .map(|(index, oby)| (Self::column(index, oby), oby.sort_order))
+                .collect(),
             second_field.into(),
-            order_by,
             reverse,
         )
     }
 
+    /// Orders by the sort keys of a subselect, which exposes them as `column_alias`es.
     pub fn aliased_internal(
         alias: &str,
         secondary_alias: &str,
         secondary_field: &str,
-        order_by: Option<&OrderBy>,
+        order_by: &[OrderBy],
         reverse: bool,
     ) -> OrderVec {
         Self::by_fields(
-            order_by.map(|oby| (alias, oby.field.db_name()).into()),
+            order_by
+                .iter()
+                .enumerate()
+                .map(|(index, oby)| ((alias, Self::column_alias(index).as_str()).into(), oby.sort_order))
+                .collect(),
             (secondary_alias, secondary_field).into(),
-            order_by,
             reverse,
         )
     }
 
-    fn by_fields(
-        first_column: Option<Column>,
-        second_column: Column,
-        order_by: Option<&OrderBy>,
-        reverse: bool,
-    ) -> OrderVec {
-        let default_order = order_by
-            .as_ref()
-            .map(|order| order.sort_order)
-            .unwrap_or(SortOrder::Ascending);
-
-        let ordering = match first_column {
-            Some(first) => {
-                if first != second_column {
-                    match (default_order, reverse) {
-                        (SortOrder::Ascending, true) => vec![first.descend(), second_column.descend()],
-                        (SortOrder::Descending, true) => vec![first.ascend(), second_column.descend()],
-                        (SortOrder::Ascending, false) => vec![first.ascend(), second_column.ascend()],
-                        (SortOrder::Descending, false) => vec![first.descend(), second_column.ascend()],
-                    }
-                } else {
-                    match (default_order, reverse) {
-                        (SortOrder::Ascending, true) => vec![second_column.descend()],
-                        (SortOrder::Descending, true) => vec![second_column.ascend()],
-                        (SortOrder::Ascending, false) => vec![second_column.ascend()],
-                        (SortOrder::Descending, false) => vec![second_column.descend()],
-                    }
-                }
-            }
-            _ => match (default_order, reverse) {
-                (SortOrder::Ascending, true) => vec![second_column.descend()],
-                (SortOrder::Descending, true) => vec![second_column.ascend()],
-                (SortOrder::Ascending, false) => vec![second_column.ascend()],
-                (SortOrder::Descending, false) => vec![second_column.descend()],
-            },
+    /// The column of the `index`th sort key. Keys on related models and ranks in full-text
+    /// searches are read from the tables joined by `joins`.
+    pub fn column(index: usize, order_by: &OrderBy) -> Column {
+        if order_by.search.is_some() {
+            let table_alias = Self::table_alias(index, 0);
+            (table_alias.as_str(), FullTextSearchTable::RANK_FIELD_NAME).into()
+        } else if order_by.path.is_empty() {
+            order_by.field.as_column()
+        } else {
+            let table_alias = Self::table_alias(index, order_by.path.len() - 1);
+            (table_alias.as_str(), order_by.field.db_name()).into()
+        }
+    }
+
+    /// The name under which a subselect exposes the `index`th sort key.
+    pub fn column_alias(index: usize) -> String {
+        format!("prismaOrderByColumn{}", index)
+    }
+
+    /// Left joins the related models of all sort keys with a relation path to a select on `model`.
+    pub fn joins(select: Select, model: &ModelRef, order_by: &[OrderBy]) -> Select {
+        order_by
+            .iter()
+            .enumerate()
+            .fold(select, |acc, (index, oby)| Self::joins_for(acc, model, index, oby))
+    }
+
+    /// Left joins the models along the path of the `index`th sort key. Every hop joins the relation
+    /// table first, which for inline relations is the table holding the foreign key. Sorting by
+    /// relevance joins the ranks of the records in the search instead.
+    pub fn joins_for(select: Select, model: &ModelRef, index: usize, order_by: &OrderBy) -> Select {
+        if let Some(ref search) = order_by.search {
+            let table_alias = Self::table_alias(index, 0);
+            return FullTextSearch::rank_join(
+                select,
+                model,
+                &table_alias,
+                &order_by.field,
+                search,
+                order_by.sort_order,
+            );
+        }
+
+        let mut parent_id = model.fields().id().as_column();
+        let mut select = select;
+
+        for (hop, field) in order_by.path.iter().enumerate() {
+            let relation = field.relation();
+            let related_model = field.related_model();
+            let relation_alias = format!("prismaOrderByRelation{}_{}", index, hop);
+            let table_alias = Self::table_alias(index, hop);
+
+            let relation_join = relation.relation_table().alias(relation_alias.clone()).on(relation
+                .column_for_relation_side(field.relation_side)
+                .table(relation_alias.clone())
+                .equals(parent_id));
+
+            let related_id = related_model.fields().id().as_column().table(table_alias.clone());
+            let related_join = related_model.table().alias(table_alias).on(related_id.clone().equals(
+                relation
+                    .column_for_relation_side(field.relation_side.opposite())
+                    .table(relation_alias),
+            ));
+
+            select = select.left_outer_join(relation_join).left_outer_join(related_join);
+            parent_id = related_id;
+        }
+
+        select
+    }
+
+    fn table_alias(index: usize, hop: usize) -> String {
+        format!("prismaOrderByTable{}_{}", index, hop)
+    }
+
+    /// Orders by the given columns in order, followed by the second column as
+    /// an ascending tie-breaker, unless it's already one of the columns.
+    fn by_fields(columns: Vec<(Column, SortOrder)>, second_column: Column, reverse: bool) -> OrderVec {
+        let order = |column: Column, sort_order: SortOrder| match (sort_order, reverse) {
+            (SortOrder::Ascending, false) | (SortOrder::Descending, true) => column.ascend(),
+            (SortOrder::Ascending, true) | (SortOrder::Descending, false) => column.descend(),
         };
 
+        let needs_tie_breaker = columns.iter().all(|(column, _)| column != &second_column);
+        let mut ordering: OrderVec = columns
+            .into_iter()
+            .map(|(column, sort_order)| order(column, sort_order))
+            .collect();
+
+        if needs_tie_breaker {
+            ordering.push(order(second_column, SortOrder::Ascending));
+        }
+
         ordering
     }
 }
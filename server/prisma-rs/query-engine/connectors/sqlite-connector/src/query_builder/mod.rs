@@ -1,9 +1,14 @@
 mod related_nodes;
 
-use crate::{cursor_condition::CursorCondition, filter_conversion::AliasedCondition, ordering::Ordering};
+use crate::{
+    cursor_condition::CursorCondition,
+    distinct_condition::DistinctCondition,
+    filter_conversion::{scalar_condition, AliasedCondition},
+    ordering::Ordering,
+};
 use connector::{
-    filter::{Filter, NodeSelector},
-    QueryArguments,
+    filter::{Filter, NodeSelector, StringMode},
+    Aggregation, AggregationSelection, QueryArguments,
 };
 use prisma_models::prelude::*;
 use prisma_query::ast::*;
@@ -42,42 +47,212 @@ impl SelectDefinition for Select {
 
 impl SelectDefinition for QueryArguments {
     fn into_select(self, model: ModelRef) -> Select {
-        let cursor: ConditionTree = CursorCondition::build(&self, Arc::clone(&model));
-        let order_by = self.order_by;
-        let ordering = Ordering::for_model(Arc::clone(&model), order_by.as_ref(), self.last.is_some());
-
-        let filter: ConditionTree = self
-            .filter
-            .map(|f| f.aliased_cond(None))
-            .unwrap_or(ConditionTree::NoCondition);
-
-        let conditions = match (filter, cursor) {
-            (ConditionTree::NoCondition, cursor) => cursor,
-            (filter, ConditionTree::NoCondition) => filter,
-            (filter, cursor) => ConditionTree::and(filter, cursor),
-        };
+        // +1 to see if there's more data
+        select_page(self, model, 1)
+    }
+}
 
-        let (skip, limit) = match self.last.or(self.first) {
-            Some(c) => (self.skip.unwrap_or(0), Some(c + 1)), // +1 to see if there's more data
-            None => (self.skip.unwrap_or(0), None),
-        };
+/// The records of the page the arguments select, followed by up to `lookahead` records past it.
+fn select_page(query_arguments: QueryArguments, model: ModelRef, lookahead: u32) -> Select {
+    let cursor: ConditionTree = CursorCondition::build(&query_arguments, Arc::clone(&model));
+    let distinct: ConditionTree = DistinctCondition::build(&query_arguments, Arc::clone(&model), None);
+    let ordering = Ordering::for_model(
+        Arc::clone(&model),
+        &query_arguments.order_by,
+        query_arguments.last.is_some(),
+    );
 
-        let select_ast = Select::from_table(model.table())
-            .so_that(conditions)
-            .offset(skip as usize);
+    let filter: ConditionTree = query_arguments
+        .filter
+        .map(|f| f.aliased_cond(None))
+        .unwrap_or(ConditionTree::NoCondition);
 
-        let select_ast = ordering.into_iter().fold(select_ast, |acc, ord| acc.order_by(ord));
+    let and = |left: ConditionTree, right: ConditionTree| match (left, right) {
+        (ConditionTree::NoCondition, right) => right,
+        (left, ConditionTree::NoCondition) => left,
+        (left, right) => ConditionTree::and(left, right),
+    };
 
-        match limit {
-            Some(limit) => select_ast.limit(limit as usize),
-            None => select_ast,
-        }
+    let conditions = and(and(filter, distinct), cursor);
+
+    let (skip, limit) = match query_arguments.last.or(query_arguments.first) {
+        Some(c) => (query_arguments.skip.unwrap_or(0), Some(c + lookahead)),
+        None => (query_arguments.skip.unwrap_or(0), None),
+    };
+
+    let select_ast = Ordering::joins(Select::from_table(model.table()), &model, &query_arguments.order_by)
+        .so_that(conditions)
+        .offset(skip as usize);
+
+    let select_ast = ordering.into_iter().fold(select_ast, |acc, ord| acc.order_by(ord));
+
+    match limit {
+        Some(limit) => select_ast.limit(limit as usize),
+        None => select_ast,
     }
 }
 
 pub struct QueryBuilder;
 
 impl QueryBuilder {
+    /// Pages requested with `last` are read in reverse order from the cursor, with one more
+    /// record to see if there's more data.
+    ///
+    /// ```rust
+    /// # use prisma_models::*;
+    /// # use rusqlite::{Connection, NO_PARAMS};
+    /// # use sqlite_connector::*;
+    /// # use connector::QueryArguments;
+    /// # use serde_json;
+    /// # use std::fs::File;
+    /// # let mut conn = Connection::open_in_memory().unwrap();
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # conn.execute("ATTACH DATABASE ':memory:' AS 'test'", NO_PARAMS).unwrap();
+    /// # let trans = conn.transaction().unwrap();
+    /// # trans.execute("CREATE TABLE test.Site (id Text, name Text);", NO_PARAMS).unwrap();
+    /// trans.execute(
+    ///     "INSERT INTO test.Site (id) VALUES ('site1'), ('site2'), ('site3'), ('site4'), ('site5');",
+    ///     NO_PARAMS
+    /// ).unwrap();
+    ///
+    /// let model = schema.find_model("Site").unwrap();
+    /// let selected_fields = SelectedFields::from(model.fields().id());
+    /// let ids = |args: QueryArguments| {
+    ///     let query = QueryBuilder::get_nodes(model.clone(), &selected_fields, args);
+    ///     Sqlite::query(&trans, query, |row| Ok(row.get::<_, GraphqlId>(0))).unwrap()
+    /// };
+    ///
+    /// let before = QueryArguments {
+    ///     last: Some(2),
+    ///     before: Some(GraphqlId::from("site4")),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec![GraphqlId::from("site3"), GraphqlId::from("site2"), GraphqlId::from("site1")],
+    ///     ids(before),
+    /// );
+    ///
+    /// let between = QueryArguments {
+    ///     after: Some(GraphqlId::from("site1")),
+    ///     before: Some(GraphqlId::from("site4")),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(vec![GraphqlId::from("site2"), GraphqlId::from("site3")], ids(between));
+    /// ```
+    ///
+    /// Records are sorted by all keys in order, and cursors compare all of them.
+    ///
+    /// ```rust
+    /// # use prisma_models::*;
+    /// # use rusqlite::{Connection, NO_PARAMS};
+    /// # use sqlite_connector::*;
+    /// # use connector::QueryArguments;
+    /// # use serde_json;
+    /// # use std::fs::File;
+    /// # let mut conn = Connection::open_in_memory().unwrap();
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # conn.execute("ATTACH DATABASE ':memory:' AS 'test'", NO_PARAMS).unwrap();
+    /// # let trans = conn.transaction().unwrap();
+    /// # trans.execute("CREATE TABLE test.Site (id Text, name Text);", NO_PARAMS).unwrap();
+    /// trans.execute(
+    ///     "INSERT INTO test.Site (id, name) \
+    ///         VALUES ('site1', 'B'), ('site2', 'A'), ('site3', 'B'), ('site4', 'A'), ('site5', 'C');",
+    ///     NO_PARAMS
+    /// ).unwrap();
+    ///
+    /// let model = schema.find_model("Site").unwrap();
+    /// let selected_fields = SelectedFields::from(model.fields().id());
+    /// let ids = |args: QueryArguments| {
+    ///     let query = QueryBuilder::get_nodes(model.clone(), &selected_fields, args);
+    ///     let ids = Sqlite::query(&trans, query, |row| Ok(row.get::<_, String>(0))).unwrap();
+    ///
+    ///     ids.join(", ")
+    /// };
+    ///
+    /// let sort_key = |name: &str, sort_order: SortOrder| OrderBy {
+    ///     path: vec![],
+    ///     field: model.fields().find_from_scalar(name).unwrap(),
+    ///     sort_order,
+    ///     search: None,
+    /// };
+    ///
+    /// // By name, and by id in descending order for equal names.
+    /// let order_by = vec![sort_key("name", SortOrder::Ascending), sort_key("id", SortOrder::Descending)];
+    ///
+    /// let all = QueryArguments { order_by: order_by.clone(), ..Default::default() };
+    /// assert_eq!("site4, site2, site3, site1, site5", ids(all));
+    ///
+    /// let after = QueryArguments {
+    ///     after: Some(GraphqlId::from("site2")),
+    ///     order_by: order_by.clone(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!("site3, site1, site5", ids(after));
+    ///
+    /// let before = QueryArguments {
+    ///     last: Some(1),
+    ///     before: Some(GraphqlId::from("site1")),
+    ///     order_by,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!("site3, site2", ids(before));
+    /// ```
+    ///
+    /// Records not matching the search of a relevance key come last, whichever way it's sorted.
+    ///
+    /// ```rust
+    /// # use prisma_models::*;
+    /// # use rusqlite::{Connection, NO_PARAMS};
+    /// # use sqlite_connector::*;
+    /// # use connector::QueryArguments;
+    /// # use serde_json;
+    /// # use std::fs::File;
+    /// # let mut conn = Connection::open_in_memory().unwrap();
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # conn.execute("ATTACH DATABASE ':memory:' AS 'test'", NO_PARAMS).unwrap();
+    /// # let trans = conn.transaction().unwrap();
+    /// # trans.execute("CREATE TABLE test.Site (id Text, name Text);", NO_PARAMS).unwrap();
+    /// # trans.execute("CREATE VIRTUAL TABLE test.Site_fts USING fts5(nodeId UNINDEXED, name);", NO_PARAMS).unwrap();
+    /// trans.execute(
+    ///     "INSERT INTO test.Site (id, name) \
+    ///         VALUES ('site1', 'cat dog bird fish'), ('site2', 'dog'), ('site3', 'cat'), ('site4', 'bird'), \
+    ///         ('site5', 'fish');",
+    ///     NO_PARAMS
+    /// ).unwrap();
+    ///
+    /// trans.execute("INSERT INTO test.Site_fts (nodeId, name) SELECT id, name FROM test.Site;", NO_PARAMS).unwrap();
+    ///
+    /// let model = schema.find_model("Site").unwrap();
+    /// let selected_fields = SelectedFields::from(model.fields().id());
+    /// let ids = |sort_order: SortOrder| {
+    ///     let order_by = OrderBy {
+    ///         path: vec![],
+    ///         field: model.fields().find_from_scalar("name").unwrap(),
+    ///         sort_order,
+    ///         search: Some(String::from("cat")),
+    ///     };
+    ///
+    ///     let args = QueryArguments { order_by: vec![order_by], ..Default::default() };
+    ///     let query = QueryBuilder::get_nodes(model.clone(), &selected_fields, args);
+    ///     let ids = Sqlite::query(&trans, query, |row| Ok(row.get::<_, String>(0))).unwrap();
+    ///
+    ///     ids.join(", ")
+    /// };
+    ///
+    /// // Better matches rank lower, so the most relevant records come first in ascending order.
+    /// assert_eq!("site3, site1, site2, site4, site5", ids(SortOrder::Ascending));
+    /// assert_eq!("site1, site3, site2, site4, site5", ids(SortOrder::Descending));
+    /// ```
     pub fn get_nodes<T>(model: ModelRef, selected_fields: &SelectedFields, query: T) -> Select
     where
         T: SelectDefinition,
@@ -88,6 +263,77 @@ impl QueryBuilder {
             .fold(query.into_select(model), |acc, col| acc.column(col.clone()))
     }
 
+    /// Related records are distinct per parent, so records with equal values can be returned
+    /// once for each of their parents.
+    ///
+    /// ```rust
+    /// # use prisma_models::*;
+    /// # use rusqlite::{Connection, NO_PARAMS};
+    /// # use sqlite_connector::*;
+    /// # use connector::QueryArguments;
+    /// # use serde_json;
+    /// # use std::fs::File;
+    /// # let mut conn = Connection::open_in_memory().unwrap();
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # conn.execute("ATTACH DATABASE ':memory:' AS 'test'", NO_PARAMS).unwrap();
+    /// # let trans = conn.transaction().unwrap();
+    /// # trans.execute("CREATE TABLE test.Site (id Text, name Text);", NO_PARAMS).unwrap();
+    /// # trans.execute("CREATE TABLE test._UserToSites (A Text, B Text, id Text);", NO_PARAMS).unwrap();
+    /// trans.execute(
+    ///     "INSERT INTO test.Site (id, name) \
+    ///         VALUES ('site1', 'Blog'), ('site2', 'Blog'), ('site3', 'Shop'), ('site4', 'Blog');",
+    ///     NO_PARAMS
+    /// ).unwrap();
+    ///
+    /// trans.execute(
+    ///     "INSERT INTO test._UserToSites (A, B) \
+    ///         VALUES ('site1', 'alice'), ('site2', 'alice'), ('site3', 'alice'), ('site4', 'bob');",
+    ///     NO_PARAMS
+    /// ).unwrap();
+    ///
+    /// let user = schema.find_model("User").unwrap();
+    /// let site = schema.find_model("Site").unwrap();
+    /// let sites = user.fields().find_from_relation_fields("sites").unwrap();
+    /// let name = site.fields().find_from_scalar("name").unwrap();
+    /// let parents = vec![GraphqlId::from("alice"), GraphqlId::from("bob")];
+    ///
+    /// let distinct = |first: Option<u32>| QueryArguments {
+    ///     first,
+    ///     distinct: vec![name.clone()],
+    ///     ..Default::default()
+    /// };
+    ///
+    /// // The parent is the last column.
+    /// let id = SelectedField::from(site.fields().id());
+    /// let selected_fields = SelectedFields::new(vec![id], Some(sites.clone()));
+    /// let related = |args: QueryArguments| {
+    ///     let query = QueryBuilder::get_related_nodes(sites.clone(), &parents, args, &selected_fields);
+    ///     let mut rows = Sqlite::query(&trans, query, |row| {
+    ///         Ok((row.get::<_, String>(2), row.get::<_, String>(0)))
+    ///     }).unwrap();
+    ///
+    ///     rows.sort();
+    ///     rows
+    /// };
+    ///
+    /// let expected = vec![
+    ///     (String::from("alice"), String::from("site1")),
+    ///     (String::from("alice"), String::from("site3")),
+    ///     (String::from("bob"), String::from("site4")),
+    /// ];
+    ///
+    /// assert_eq!(expected, related(distinct(None)));
+    /// assert_eq!(expected, related(distinct(Some(2))));
+    ///
+    /// // Top level queries pick one record of all records with equal values.
+    /// let selected_fields = SelectedFields::from(site.fields().id());
+    /// let query = QueryBuilder::get_nodes(site.clone(), &selected_fields, distinct(None));
+    /// let ids = Sqlite::query(&trans, query, |row| Ok(row.get::<_, String>(0))).unwrap();
+    ///
+    /// assert_eq!(vec![String::from("site1"), String::from("site3")], ids);
+    /// ```
     pub fn get_related_nodes(
         from_field: RelationFieldRef,
         from_node_ids: &[GraphqlId],
@@ -106,6 +352,17 @@ impl QueryBuilder {
         select_ast
     }
 
+    /// The paginated related nodes of a single parent, for databases without window functions.
+    pub fn get_related_nodes_of_parent(
+        from_field: RelationFieldRef,
+        from_node_id: &GraphqlId,
+        query_arguments: QueryArguments,
+        selected_fields: &SelectedFields,
+    ) -> Select {
+        let from_node_ids = [from_node_id.clone()];
+        RelatedNodesQueryBuilder::new(from_field, &from_node_ids, query_arguments, selected_fields).with_limits()
+    }
+
     pub fn get_scalar_list_values_by_node_ids(list_field: ScalarFieldRef, node_ids: Vec<GraphqlId>) -> Select {
         let model = list_field.model();
         let table_name = format!("{}_{}", model.db_name(), list_field.name);
@@ -128,7 +385,8 @@ impl QueryBuilder {
         let mut selected_fields = SelectedFields::default();
         selected_fields.add_scalar(id_field.clone(), false);
 
-        let base_query = Self::get_nodes(model, &selected_fields, query_arguments);
+        let page = select_page(query_arguments, Arc::clone(&model), 0);
+        let base_query = Self::get_nodes(model, &selected_fields, page);
 
         let table = Table::from(base_query).alias("sub");
         let column = Column::from(("sub", id_field.db_name()));
@@ -137,7 +395,156 @@ impl QueryBuilder {
         select_ast
     }
 
+    /// Counts the related nodes of every parent with the filter and distinct fields of the
+    /// arguments, grouped by parent. Parents without related nodes have no row.
+    ///
+    /// ```rust
+    /// # use prisma_models::*;
+    /// # use rusqlite::{Connection, NO_PARAMS};
+    /// # use sqlite_connector::*;
+    /// # use connector::QueryArguments;
+    /// # use serde_json;
+    /// # use std::fs::File;
+    /// # let mut conn = Connection::open_in_memory().unwrap();
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # conn.execute("ATTACH DATABASE ':memory:' AS 'test'", NO_PARAMS).unwrap();
+    /// # let trans = conn.transaction().unwrap();
+    /// # trans.execute("CREATE TABLE test.Site (id Text, name Text);", NO_PARAMS).unwrap();
+    /// # trans.execute("CREATE TABLE test._UserToSites (A Text, B Text, id Text);", NO_PARAMS).unwrap();
+    /// trans.execute(
+    ///     "INSERT INTO test.Site (id, name) VALUES ('site1', 'Cats'), ('site2', 'Dogs'), ('site3', 'Birds');",
+    ///     NO_PARAMS
+    /// ).unwrap();
+    ///
+    /// // Alice has two sites, Bob has one and Eve has none.
+    /// trans.execute(
+    ///     "INSERT INTO test._UserToSites (A, B) VALUES ('site1', 'alice'), ('site2', 'alice'), ('site3', 'bob');",
+    ///     NO_PARAMS
+    /// ).unwrap();
+    ///
+    /// let sites = schema.find_model("User").unwrap().fields().find_from_relation_fields("sites").unwrap();
+    /// let parents = vec![GraphqlId::from("alice"), GraphqlId::from("bob"), GraphqlId::from("eve")];
+    /// let query = QueryBuilder::count_related_nodes(sites, &parents, QueryArguments::default());
+    ///
+    /// let mut counts = Sqlite::query(&trans, query, |row| {
+    ///     let parent_id: GraphqlId = row.get(0);
+    ///     let count: i64 = row.get(1);
+    ///
+    ///     Ok((parent_id, count))
+    /// }).unwrap();
+    ///
+    /// counts.sort_by_key(|(_, count)| *count);
+    ///
+    /// assert_eq!(
+    ///     vec![(GraphqlId::from("bob"), 1), (GraphqlId::from("alice"), 2)],
+    ///     counts,
+    /// );
+    /// ```
+    pub fn count_related_nodes(
+        from_field: RelationFieldRef,
+        from_node_ids: &[GraphqlId],
+        query_arguments: QueryArguments,
+    ) -> Select {
+        let selected_fields = SelectedFields::from(from_field.related_model().fields().id());
+        RelatedNodesQueryBuilder::new(from_field, from_node_ids, query_arguments, &selected_fields).count()
+    }
+
     pub fn count_by_table(database: &str, table: &str) -> Select {
         Select::from_table((database, table)).value(count(asterisk()))
     }
+
+    /// Aggregates the records of the page the arguments select, exposing the values of the
+    /// grouped fields first and the aggregations after them.
+    ///
+    /// Groups are filtered by their aggregations in an outer select, which reads the aggregations
+    /// needed by the `having` conditions after the selected ones.
+    pub fn aggregate(model: ModelRef, query_arguments: QueryArguments, selection: &AggregationSelection) -> Select {
+        let mut selected_fields = SelectedFields::from(model.fields().id());
+        for field in selection.fields() {
+            if selected_fields.scalar.iter().all(|sf| sf.field.name != field.name) {
+                selected_fields.add_scalar(field, false);
+            }
+        }
+
+        let page = select_page(query_arguments, Arc::clone(&model), 0);
+        let base_query = Self::get_nodes(model, &selected_fields, page);
+        let column = |field: &ScalarFieldRef| Column::from(("sub", field.db_name()));
+
+        let aggregations = selection
+            .aggregations
+            .iter()
+            .chain(selection.having.iter().map(|filter| &filter.aggregation));
+
+        let grouped = selection.group_by.iter().enumerate().fold(
+            Select::from_table(Table::from(base_query).alias("sub")),
+            |acc, (i, field)| acc.column(column(field).alias(Self::group_alias(i))),
+        );
+
+        let grouped = aggregations.enumerate().fold(grouped, |acc, (i, aggregation)| {
+            let value = match aggregation {
+                Aggregation::Count => count(asterisk()),
+                Aggregation::Sum(field) => sum(column(field)),
+                Aggregation::Avg(field) => avg(column(field)),
+                Aggregation::Min(field) => min(column(field)),
+                Aggregation::Max(field) => max(column(field)),
+            };
+
+            acc.value(value.alias(Self::aggregation_alias(i)))
+        });
+
+        let grouped = selection
+            .group_by
+            .iter()
+            .fold(grouped, |acc, field| acc.group_by(column(field)));
+
+        if selection.having.is_empty() {
+            return selection
+                .group_by
+                .iter()
+                .fold(grouped, |acc, field| acc.order_by(column(field).ascend()));
+        }
+
+        let aggregated = |alias: String| Column::from(("aggregated", alias.as_str()));
+        let offset = selection.aggregations.len();
+
+        let conditions = selection
+            .having
+            .iter()
+            .enumerate()
+            .fold(ConditionTree::NoCondition, |acc, (i, filter)| {
+                let condition = scalar_condition(
+                    aggregated(Self::aggregation_alias(offset + i)),
+                    filter.condition.clone(),
+                    StringMode::Default,
+                );
+
+                match acc {
+                    ConditionTree::NoCondition => condition,
+                    acc => ConditionTree::and(acc, condition),
+                }
+            });
+
+        let select_ast = (0..selection.group_by.len())
+            .map(|i| aggregated(Self::group_alias(i)))
+            .chain((0..offset).map(|i| aggregated(Self::aggregation_alias(i))))
+            .fold(
+                Select::from_table(Table::from(grouped).alias("aggregated")),
+                |acc, col| acc.column(col),
+            )
+            .so_that(conditions);
+
+        (0..selection.group_by.len()).fold(select_ast, |acc, i| {
+            acc.order_by(aggregated(Self::group_alias(i)).ascend())
+        })
+    }
+
+    fn group_alias(index: usize) -> String {
+        format!("prismaGroupByColumn{}", index)
+    }
+
+    fn aggregation_alias(index: usize) -> String {
+        format!("prismaAggregation{}", index)
+    }
 }
@@ -1,8 +1,12 @@
-use crate::{cursor_condition::CursorCondition, filter_conversion::AliasedCondition, ordering::Ordering};
+use crate::{
+    cursor_condition::CursorCondition, distinct_condition::DistinctCondition, filter_conversion::AliasedCondition,
+    ordering::Ordering,
+};
 use connector::QueryArguments;
 use prisma_models::prelude::*;
 use prisma_query::ast::{
-    row_number, Aliasable, Column, Comparable, ConditionTree, Conjuctive, Function, Joinable, Select, Table,
+    asterisk, count, row_number, Aliasable, Column, Comparable, ConditionTree, Conjuctive, Function, Joinable, Select,
+    Table,
 };
 use std::sync::Arc;
 
@@ -14,8 +18,9 @@ pub struct RelatedNodesQueryBuilder<'a> {
     relation: Arc<Relation>,
     related_model: ModelRef,
     window_limits: (u32, u32),
-    order_by: Option<OrderBy>,
+    order_by: Vec<OrderBy>,
     cursor_condition: ConditionTree,
+    distinct_condition: ConditionTree,
     reverse_order: bool,
 }
 
@@ -35,7 +40,16 @@ impl<'a> RelatedNodesQueryBuilder<'a> {
         let cursor_condition = CursorCondition::build(&query_arguments, related_model.clone());
         let window_limits = query_arguments.window_limits();
 
-        let order_by: Option<OrderBy> = query_arguments.order_by;
+        let parent_column = relation
+            .column_for_relation_side(from_field.relation_side)
+            .table(Relation::TABLE_ALIAS);
+        let distinct_condition = DistinctCondition::build(
+            &query_arguments,
+            related_model.clone(),
+            Some((&from_field, parent_column)),
+        );
+
+        let order_by: Vec<OrderBy> = query_arguments.order_by;
         let conditions: ConditionTree = query_arguments
             .filter
             .map(|f| f.aliased_cond(None))
@@ -53,6 +67,7 @@ impl<'a> RelatedNodesQueryBuilder<'a> {
             window_limits,
             order_by,
             cursor_condition,
+            distinct_condition,
             reverse_order,
         }
     }
@@ -61,12 +76,13 @@ impl<'a> RelatedNodesQueryBuilder<'a> {
         let relation_side_column = self.relation_side_column();
         let base_query = self.base_query();
         let cursor_condition = self.cursor_condition;
+        let distinct_condition = self.distinct_condition;
 
         let ordering = Ordering::aliased_internal(
             Self::BASE_TABLE_ALIAS,
             Self::BASE_TABLE_ALIAS,
             SelectedFields::RELATED_MODEL_ALIAS,
-            self.order_by.as_ref(),
+            &self.order_by,
             self.reverse_order,
         );
 
@@ -74,12 +90,18 @@ impl<'a> RelatedNodesQueryBuilder<'a> {
         let conditions = relation_side_column
             .in_selection(self.from_node_ids.to_owned())
             .and(self.conditions)
+            .and(distinct_condition)
             .and(cursor_condition);
 
-        let base_with_conditions = match self.order_by {
-            Some(order_by) => base_query.column(order_by.field.as_column()).so_that(conditions),
-            None => base_query.so_that(conditions),
-        };
+        // The window function orders by the sort keys, so the base table has to expose them.
+        let base_with_conditions = self
+            .order_by
+            .iter()
+            .enumerate()
+            .fold(base_query, |acc, (index, order_by)| {
+                acc.column(Ordering::column(index, order_by).alias(Ordering::column_alias(index)))
+            })
+            .so_that(conditions);
 
         let row_number_part: Function = ordering
             .into_iter()
@@ -101,34 +123,64 @@ impl<'a> RelatedNodesQueryBuilder<'a> {
         let opposite_relation_side_column = self.opposite_relation_side_column();
         let base_query = self.base_query();
         let cursor_condition = self.cursor_condition;
+        let distinct_condition = self.distinct_condition;
 
         // TODO: prisma query crate slice handling
         let conditions = relation_side_column
             .clone()
             .in_selection(self.from_node_ids.to_owned())
             .and(cursor_condition)
+            .and(distinct_condition)
             .and(self.conditions);
 
-        Ordering::internal(
-            opposite_relation_side_column,
-            self.order_by.as_ref(),
-            self.reverse_order,
-        )
-        .into_iter()
-        .fold(base_query.so_that(conditions), |acc, ord| acc.order_by(ord))
+        Ordering::internal(opposite_relation_side_column, &self.order_by, self.reverse_order)
+            .into_iter()
+            .fold(base_query.so_that(conditions), |acc, ord| acc.order_by(ord))
+    }
+
+    /// Paginates with `LIMIT` and `OFFSET` instead of numbering the rows, which only works for
+    /// the related nodes of a single parent.
+    pub fn with_limits(self) -> Select {
+        let (start, end) = self.window_limits;
+        let select = self.without_pagination().offset((start - 1) as usize);
+
+        select.limit((end - start + 1) as usize)
+    }
+
+    /// Counts the related nodes of every parent, reading the parent id and the count.
+    pub fn count(self) -> Select {
+        let relation_side_column = self.relation_side_column();
+        let conditions = relation_side_column
+            .clone()
+            .in_selection(self.from_node_ids.to_owned())
+            .and(self.distinct_condition)
+            .and(self.conditions);
+
+        Select::from_table(self.related_model.table())
+            .column(relation_side_column.clone())
+            .value(count(asterisk()))
+            .inner_join(
+                self.relation_table()
+                    .on(self.id_column().equals(self.opposite_relation_side_column())),
+            )
+            .so_that(conditions)
+            .group_by(relation_side_column)
     }
 
     fn base_query(&self) -> Select {
         let select = Select::from_table(self.from_field.related_model().table());
 
-        self.selected_fields
+        let select = self
+            .selected_fields
             .columns()
             .into_iter()
             .fold(select, |acc, col| acc.column(col.clone()))
             .inner_join(
                 self.relation_table()
                     .on(self.id_column().equals(self.opposite_relation_side_column())),
-            )
+            );
+
+        Ordering::joins(select, &self.related_model, &self.order_by)
     }
 
     fn id_column(&self) -> Column {
@@ -0,0 +1,28 @@
+use connector::ConnectorResult;
+use prisma_models::*;
+use prisma_query::ast::*;
+
+/// A database implementing [DatabaseRead](trait.DatabaseRead.html) and
+/// [DatabaseWrite](trait.DatabaseWrite.html) with the shared queries of this crate. The
+/// differences between the databases are in the [SqlTransaction](trait.SqlTransaction.html)
+/// the queries are executed in.
+pub trait SqlDatabase {}
+
+/// A transaction of an SQL database, rendering the queries with the visitor of the database and
+/// reading the results back into the Prisma types.
+pub trait SqlTransaction {
+    /// Execute a statement not returning any rows.
+    fn write(&self, query: Query) -> ConnectorResult<()>;
+
+    /// Insert a record with a generated id, returning the id.
+    fn insert_returning_id(&self, insert: Insert, model: &ModelRef) -> ConnectorResult<GraphqlId>;
+
+    /// Execute the `SELECT`, reading the first column of every row as an id.
+    fn read_ids(&self, select: Select) -> ConnectorResult<Vec<GraphqlId>>;
+
+    /// Execute the `SELECT`, reading every row as a node of the selected fields.
+    fn read_nodes(&self, select: Select, selected_fields: &SelectedFields) -> ConnectorResult<Vec<Node>>;
+
+    /// Delete all data of the project, ignoring the foreign keys between the tables.
+    fn truncate(&self, project: ProjectRef) -> ConnectorResult<()>;
+}
@@ -1,9 +1,9 @@
 mod mutaction_executor;
 mod read;
 mod resolver;
-mod write;
+mod transaction;
 
-use crate::{Connection, TransactionalExecutor};
+use crate::{Connection, SqlDatabase, TransactionalExecutor};
 use chrono::{DateTime, Utc};
 use connector::*;
 use prisma_models::prelude::*;
@@ -19,6 +19,8 @@ pub struct Sqlite {
     test_mode: bool,
 }
 
+impl SqlDatabase for Sqlite {}
+
 impl TransactionalExecutor for Sqlite {
     fn with_connection<'a, F, T>(&self, db_name: &str, f: F) -> ConnectorResult<T>
     where
@@ -91,6 +93,44 @@ impl Sqlite {
         Ok(())
     }
 
+    /// Creates the full-text search indexes of the models in `schema`, and indexes a model again
+    /// when its index covers other string fields than the model has. Indexes are filled with the
+    /// existing records. Models without a table yet are skipped.
+    pub fn create_full_text_search_indexes(&self, schema: &Schema) -> ConnectorResult<()> {
+        self.with_transaction(&schema.db_name, |tx| {
+            for model in schema.models() {
+                let fts = model.full_text_search_table();
+
+                if Self::column_names(tx, &schema.db_name, model.db_name())?.is_empty() {
+                    continue;
+                }
+
+                if Self::column_names(tx, &schema.db_name, fts.table_name())? != fts.column_names() {
+                    for statement in fts.drop_statements().into_iter().chain(fts.create_statements()) {
+                        tx.execute(&statement, NO_PARAMS)?;
+                    }
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    /// The columns of a table, or none if the table doesn't exist.
+    fn column_names(conn: &Transaction, database: &str, table: &str) -> ConnectorResult<Vec<String>> {
+        let quote = |identifier: &str| format!("\"{}\"", identifier.replace('"', "\"\""));
+        let mut stmt = conn.prepare(&format!("PRAGMA {}.table_info({})", quote(database), quote(table)))?;
+
+        let columns = stmt
+            .query_map(NO_PARAMS, |row| {
+                let name: String = row.get(1);
+                name
+            })?
+            .collect::<Result<Vec<String>, _>>()?;
+
+        Ok(columns)
+    }
+
     pub fn without_foreign_key_checks<F, T>(conn: &Transaction, f: F) -> ConnectorResult<T>
     where
         F: FnOnce() -> ConnectorResult<T>,
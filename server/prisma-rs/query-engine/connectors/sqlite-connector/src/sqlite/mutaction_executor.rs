@@ -23,15 +23,18 @@ impl DatabaseMutactionExecutor for Sqlite {
         mutaction: DatabaseMutaction,
         parent_id: Option<GraphqlId>, // TODO: we don't need this when we handle the whole mutaction in here.
     ) -> ConnectorResult<DatabaseMutactionResults> {
-        self.with_transaction(&db_name, |conn| {
-            let mut results = DatabaseMutactionResults::default();
-
-            match mutaction {
-                DatabaseMutaction::TopLevel(tlm) => results.merge(Self::execute_toplevel(conn, tlm)?),
-                DatabaseMutaction::Nested(nm) => results.merge(Self::execute_nested(conn, nm, parent_id.unwrap())?),
-            }
+        self.with_transaction(&db_name, |conn| Self::execute_mutaction(conn, mutaction, parent_id))
+    }
 
-            Ok(results)
+    fn execute_with_nested(
+        &self,
+        db_name: String,
+        mutaction: DatabaseMutaction,
+        parent_id: Option<GraphqlId>,
+    ) -> ConnectorResult<DatabaseMutactionResults> {
+        self.with_transaction(&db_name, |conn| {
+            let own_results = Self::execute_mutaction(conn, mutaction, parent_id)?;
+            Self::execute_nested_tree(conn, own_results)
         })
     }
 }
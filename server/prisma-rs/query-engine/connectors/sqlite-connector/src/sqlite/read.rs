@@ -1,19 +1,51 @@
-use crate::{query_builder::QueryBuilder, AliasedCondition, DatabaseRead, SelectDefinition, Sqlite};
-use connector::{
-    error::*,
-    filter::{Filter, NodeSelector},
-    ConnectorResult,
-};
-use prisma_models::*;
+use crate::Sqlite;
+use connector::ConnectorResult;
 use prisma_query::{
     ast::*,
     visitor::{self, *},
 };
 use rusqlite::{Row, Transaction};
-use std::sync::Arc;
 
-impl DatabaseRead for Sqlite {
-    fn query<F, T, S>(conn: &Transaction, query: S, mut f: F) -> ConnectorResult<Vec<T>>
+impl Sqlite {
+    /// Execute the `SELECT` and return a vector mapped with `F`.
+    ///
+    /// ```rust
+    /// # use rusqlite::{Connection, NO_PARAMS};
+    /// # use sqlite_connector::*;
+    /// # use prisma_query::ast::*;
+    /// # let mut conn = Connection::open_in_memory().unwrap();
+    /// # let trans = conn.transaction().unwrap();
+    /// trans.execute(
+    ///     "CREATE TABLE users (id, name Text);",
+    ///     NO_PARAMS
+    /// );
+    ///
+    /// trans.execute(
+    ///     "INSERT INTO users (id, name) VALUES (1, 'Bob');",
+    ///     NO_PARAMS
+    /// );
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct User {
+    ///     id: i64,
+    ///     name: String,
+    /// };
+    ///
+    /// let select = Select::from_table("users")
+    ///     .column("id")
+    ///     .column("name");
+    ///
+    /// let users = Sqlite::query(&trans, select, |row| Ok(User {
+    ///     id: row.get(0),
+    ///     name: row.get(1),
+    /// })).unwrap();
+    ///
+    /// assert_eq!(
+    ///     vec![User { id: 1, name: String::from("Bob") }],
+    ///     users,
+    /// );
+    /// ```
+    pub fn query<F, T, S>(conn: &Transaction, query: S, mut f: F) -> ConnectorResult<Vec<T>>
     where
         F: FnMut(&Row) -> ConnectorResult<T>,
         S: Into<Select>,
@@ -29,7 +61,40 @@ impl DatabaseRead for Sqlite {
         Ok(res?)
     }
 
-    fn count<C, T>(conn: &Transaction, table: T, conditions: C) -> ConnectorResult<usize>
+    /// Count the records of the given query.
+    ///
+    /// ```rust
+    /// # use rusqlite::{Connection, NO_PARAMS};
+    /// # use sqlite_connector::*;
+    /// # use prisma_query::ast::*;
+    /// # let mut conn = Connection::open_in_memory().unwrap();
+    /// # let trans = conn.transaction().unwrap();
+    /// trans.execute(
+    ///     "CREATE TABLE users (id, name Text);",
+    ///     NO_PARAMS
+    /// ).unwrap();
+    ///
+    /// trans.execute(
+    ///     "INSERT INTO users (id, name) VALUES (1, 'Bob');",
+    ///     NO_PARAMS
+    /// ).unwrap();
+    ///
+    /// trans.execute(
+    ///     "INSERT INTO users (id, name) VALUES (2, 'Alice');",
+    ///     NO_PARAMS
+    /// ).unwrap();
+    ///
+    /// assert_eq!(
+    ///     2,
+    ///     Sqlite::count(&trans, "users", ConditionTree::default()).unwrap(),
+    /// );
+    ///
+    /// assert_eq!(
+    ///     1,
+    ///     Sqlite::count(&trans, "users", "name".equals("Alice")).unwrap(),
+    /// );
+    /// ```
+    pub fn count<C, T>(conn: &Transaction, table: T, conditions: C) -> ConnectorResult<usize>
     where
         C: Into<ConditionTree>,
         T: Into<Table>,
@@ -49,102 +114,4 @@ impl DatabaseRead for Sqlite {
 
         Ok(res as usize)
     }
-
-    fn ids_for<T>(conn: &Transaction, model: ModelRef, into_select: T) -> ConnectorResult<Vec<GraphqlId>>
-    where
-        T: SelectDefinition,
-    {
-        let select = {
-            let selected_fields = SelectedFields::from(model.fields().id());
-            QueryBuilder::get_nodes(model, &selected_fields, into_select)
-        };
-
-        let ids = Self::query(conn, select, |row| {
-            let id: GraphqlId = row.get(0);
-            Ok(id)
-        })?;
-
-        Ok(ids)
-    }
-
-    fn id_for(conn: &Transaction, node_selector: &NodeSelector) -> ConnectorResult<GraphqlId> {
-        let model = node_selector.field.model();
-        let opt_id = Self::ids_for(conn, model, node_selector.clone())?.into_iter().next();
-
-        opt_id.ok_or_else(|| ConnectorError::NodeNotFoundForWhere(NodeSelectorInfo::from(node_selector)))
-    }
-
-    fn find_node(conn: &Transaction, node_selector: &NodeSelector) -> ConnectorResult<SingleNode> {
-        let model = node_selector.field.model();
-        let selected_fields = SelectedFields::from(Arc::clone(&model));
-
-        let select = QueryBuilder::get_nodes(model, &selected_fields, node_selector);
-
-        let node = Self::query(conn, select, |row| Self::read_row(row, &selected_fields))?
-            .into_iter()
-            .next()
-            .ok_or_else(|| ConnectorError::NodeNotFoundForWhere(NodeSelectorInfo::from(node_selector)))?;
-
-        Ok(SingleNode::new(node, selected_fields.names()))
-    }
-
-    fn get_id_by_parent(
-        conn: &Transaction,
-        parent_field: RelationFieldRef,
-        parent_id: &GraphqlId,
-        selector: &Option<NodeSelector>,
-    ) -> ConnectorResult<GraphqlId> {
-        let ids = Self::get_ids_by_parents(conn, Arc::clone(&parent_field), vec![parent_id], selector.clone())?;
-
-        let id = ids
-            .into_iter()
-            .next()
-            .ok_or_else(|| ConnectorError::NodesNotConnected {
-                relation_name: parent_field.relation().name.clone(),
-                parent_name: parent_field.model().name.clone(),
-                parent_where: None,
-                child_name: parent_field.related_model().name.clone(),
-                child_where: selector.as_ref().map(NodeSelectorInfo::from),
-            })?;
-
-        Ok(id)
-    }
-
-    fn get_ids_by_parents<T>(
-        conn: &Transaction,
-        parent_field: RelationFieldRef,
-        parent_ids: Vec<&GraphqlId>,
-        selector: Option<T>,
-    ) -> ConnectorResult<Vec<GraphqlId>>
-    where
-        T: Into<Filter>,
-    {
-        let related_model = parent_field.related_model();
-        let relation = parent_field.relation();
-        let child_id_field = relation.column_for_relation_side(parent_field.relation_side.opposite());
-        let parent_id_field = relation.column_for_relation_side(parent_field.relation_side);
-
-        let subselect = Select::from_table(relation.relation_table())
-            .column(child_id_field)
-            .so_that(parent_id_field.in_selection(parent_ids));
-
-        let conditions = related_model.fields().id().db_name().in_selection(subselect);
-
-        let conditions = match selector {
-            Some(into_cond) => {
-                let filter: Filter = into_cond.into();
-                conditions.and(filter.aliased_cond(None))
-            }
-            None => conditions.into(),
-        };
-
-        let select = Select::from_table(related_model.table())
-            .column(related_model.fields().id().as_column())
-            .so_that(conditions);
-
-        Self::query(conn, select, |row| {
-            let id: GraphqlId = row.get(0);
-            Ok(id)
-        })
-    }
 }
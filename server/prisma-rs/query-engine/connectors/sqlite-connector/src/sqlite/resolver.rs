@@ -2,6 +2,7 @@ use crate::{query_builder::QueryBuilder, DatabaseRead, Sqlite, TransactionalExec
 use connector::{filter::NodeSelector, *};
 use itertools::Itertools;
 use prisma_models::*;
+use std::sync::Arc;
 
 impl DataResolver for Sqlite {
     fn get_node_by_where(
@@ -72,11 +73,63 @@ impl DataResolver for Sqlite {
         self.with_transaction(db_name, |conn| Self::count(conn, table, query))
     }
 
+    fn count_related_nodes(
+        &self,
+        from_field: RelationFieldRef,
+        from_node_ids: &[GraphqlId],
+        query_arguments: QueryArguments,
+    ) -> ConnectorResult<Vec<RelatedNodesCount>> {
+        let db_name = &from_field.model().schema().db_name;
+        let query = QueryBuilder::count_related_nodes(from_field, from_node_ids, query_arguments);
+
+        self.with_transaction(db_name, |conn| {
+            Self::query(conn, query, |row| {
+                let count: i64 = row.get(1);
+
+                Ok(RelatedNodesCount {
+                    parent_id: row.get(0),
+                    count: count as usize,
+                })
+            })
+        })
+    }
+
     fn count_by_table(&self, database: &str, table: &str) -> ConnectorResult<usize> {
         let query = QueryBuilder::count_by_table(database, table);
         self.with_transaction(database, |conn| Self::count(conn, table, query))
     }
 
+    fn aggregate(
+        &self,
+        model: ModelRef,
+        query_arguments: QueryArguments,
+        selection: AggregationSelection,
+    ) -> ConnectorResult<Vec<AggregationRow>> {
+        let db_name = &model.schema().db_name;
+        let query = QueryBuilder::aggregate(Arc::clone(&model), query_arguments, &selection);
+
+        self.with_transaction(db_name, |conn| {
+            Self::query(conn, query, |row| {
+                let group = selection
+                    .group_by
+                    .iter()
+                    .enumerate()
+                    .map(|(i, field)| Sqlite::fetch_value(field.type_identifier, row, i))
+                    .collect::<ConnectorResult<Vec<PrismaValue>>>()?;
+
+                let offset = group.len();
+                let values = selection
+                    .aggregations
+                    .iter()
+                    .enumerate()
+                    .map(|(i, aggregation)| Sqlite::fetch_value(aggregation.type_identifier(), row, offset + i))
+                    .collect::<ConnectorResult<Vec<PrismaValue>>>()?;
+
+                Ok(AggregationRow { group, values })
+            })
+        })
+    }
+
     fn get_scalar_list_values_by_node_ids(
         &self,
         list_field: ScalarFieldRef,
@@ -0,0 +1,41 @@
+use crate::{mutaction::MutationBuilder, SqlTransaction, Sqlite};
+use connector::ConnectorResult;
+use prisma_models::*;
+use prisma_query::{
+    ast::*,
+    visitor::{self, *},
+};
+use rusqlite::Transaction;
+
+impl SqlTransaction for Transaction<'_> {
+    fn write(&self, query: Query) -> ConnectorResult<()> {
+        let (sql, params) = dbg!(visitor::Sqlite::build(query));
+        self.prepare(&sql)?.execute(&params)?;
+
+        Ok(())
+    }
+
+    fn insert_returning_id(&self, insert: Insert, _: &ModelRef) -> ConnectorResult<GraphqlId> {
+        self.write(insert.into())?;
+
+        Ok(GraphqlId::Int(self.last_insert_rowid() as usize))
+    }
+
+    fn read_ids(&self, select: Select) -> ConnectorResult<Vec<GraphqlId>> {
+        Sqlite::query(self, select, Sqlite::fetch_id)
+    }
+
+    fn read_nodes(&self, select: Select, selected_fields: &SelectedFields) -> ConnectorResult<Vec<Node>> {
+        Sqlite::query(self, select, |row| Sqlite::read_row(row, selected_fields))
+    }
+
+    fn truncate(&self, project: ProjectRef) -> ConnectorResult<()> {
+        Sqlite::without_foreign_key_checks(self, || {
+            for delete in MutationBuilder::truncate_tables(project) {
+                self.write(delete.into())?;
+            }
+
+            Ok(())
+        })
+    }
+}
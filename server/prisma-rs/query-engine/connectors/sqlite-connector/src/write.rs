@@ -11,16 +11,28 @@ pub use update::*;
 use crate::*;
 use connector::{error::ConnectorError, mutaction::*, ConnectorResult};
 use prisma_models::GraphqlId;
-use prisma_query::{
-    ast::Query,
-    visitor::{self, Visitor},
-};
-use rusqlite::Transaction;
+use prisma_query::ast::Query;
 use std::sync::Arc;
 
-impl DatabaseWrite for Sqlite {
+impl<D> DatabaseWrite for D
+where
+    D: SqlDatabase,
+{
+    fn execute_mutaction(
+        conn: &dyn SqlTransaction,
+        mutaction: DatabaseMutaction,
+        parent_id: Option<GraphqlId>,
+    ) -> ConnectorResult<DatabaseMutactionResults> {
+        match mutaction {
+            DatabaseMutaction::TopLevel(tlm) => Self::execute_toplevel(conn, tlm),
+            DatabaseMutaction::Nested(nm) => {
+                Self::execute_nested(conn, nm, parent_id.ok_or(ConnectorError::MissingParentId)?)
+            }
+        }
+    }
+
     fn execute_toplevel(
-        conn: &Transaction,
+        conn: &dyn SqlTransaction,
         mutaction: TopLevelDatabaseMutaction,
     ) -> ConnectorResult<DatabaseMutactionResults> {
         let mut results = DatabaseMutactionResults::default();
@@ -36,7 +48,7 @@ impl DatabaseWrite for Sqlite {
                 });
             }
             TopLevelDatabaseMutaction::UpdateNode(ref un) => {
-                let id = Self::execute_update(conn, &un.where_, &un.non_list_args, &un.list_args)?;
+                let id = Self::execute_update(conn, &un.where_, &un.non_list_args, &un.list_args, &un.json_updates)?;
 
                 results.push(DatabaseMutactionResult {
                     identifier: Identifier::Id(id),
@@ -67,6 +79,7 @@ impl DatabaseWrite for Sqlite {
                         &ups.update.where_,
                         &ups.update.non_list_args,
                         &ups.update.list_args,
+                        &ups.update.json_updates,
                     )?;
 
                     results.push(DatabaseMutactionResult {
@@ -127,7 +140,7 @@ impl DatabaseWrite for Sqlite {
     }
 
     fn execute_nested(
-        conn: &Transaction,
+        conn: &dyn SqlTransaction,
         mutaction: NestedDatabaseMutaction,
         parent_id: GraphqlId,
     ) -> ConnectorResult<DatabaseMutactionResults> {
@@ -160,6 +173,7 @@ impl DatabaseWrite for Sqlite {
                     Arc::clone(&un.relation_field),
                     &un.non_list_args,
                     &un.list_args,
+                    &un.json_updates,
                 )?;
 
                 let result = DatabaseMutactionResult {
@@ -187,6 +201,7 @@ impl DatabaseWrite for Sqlite {
                             Arc::clone(&ups.update.relation_field),
                             &ups.update.non_list_args,
                             &ups.update.list_args,
+                            &ups.update.json_updates,
                         )?;
 
                         results.push(DatabaseMutactionResult {
@@ -288,17 +303,14 @@ impl DatabaseWrite for Sqlite {
         Ok(results)
     }
 
-    fn execute_one<T>(conn: &Transaction, query: T) -> ConnectorResult<()>
+    fn execute_one<T>(conn: &dyn SqlTransaction, query: T) -> ConnectorResult<()>
     where
         T: Into<Query>,
     {
-        let (sql, params) = dbg!(visitor::Sqlite::build(query));
-        conn.prepare(&sql)?.execute(&params)?;
-
-        Ok(())
+        conn.write(query.into())
     }
 
-    fn execute_many<T>(conn: &Transaction, queries: Vec<T>) -> ConnectorResult<()>
+    fn execute_many<T>(conn: &dyn SqlTransaction, queries: Vec<T>) -> ConnectorResult<()>
     where
         T: Into<Query>,
     {
@@ -308,4 +320,24 @@ impl DatabaseWrite for Sqlite {
 
         Ok(())
     }
+
+    fn execute_nested_tree(
+        conn: &dyn SqlTransaction,
+        own_results: DatabaseMutactionResults,
+    ) -> ConnectorResult<DatabaseMutactionResults> {
+        let mut results = DatabaseMutactionResults::default();
+
+        for result in own_results {
+            if let Some(nested) = result.nested_mutactions() {
+                for mutaction in nested.mutactions() {
+                    let nested_results = Self::execute_nested(conn, mutaction, result.id().clone())?;
+                    results.merge(Self::execute_nested_tree(conn, nested_results)?);
+                }
+            }
+
+            results.push(result);
+        }
+
+        Ok(results)
+    }
 }
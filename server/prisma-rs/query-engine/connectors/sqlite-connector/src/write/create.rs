@@ -1,15 +1,17 @@
 use crate::{
     mutaction::{MutationBuilder, NestedActions},
-    DatabaseCreate, DatabaseRead, DatabaseWrite, Sqlite,
+    DatabaseCreate, DatabaseRead, DatabaseWrite, SqlDatabase, SqlTransaction,
 };
 use connector::ConnectorResult;
 use prisma_models::{GraphqlId, ModelRef, PrismaArgs, PrismaListValue, RelationFieldRef};
-use rusqlite::Transaction;
 use std::sync::Arc;
 
-impl DatabaseCreate for Sqlite {
+impl<D> DatabaseCreate for D
+where
+    D: SqlDatabase,
+{
     fn execute_create<T>(
-        conn: &Transaction,
+        conn: &dyn SqlTransaction,
         model: ModelRef,
         non_list_args: &PrismaArgs,
         list_args: &[(T, PrismaListValue)],
@@ -19,11 +21,12 @@ impl DatabaseCreate for Sqlite {
     {
         let (insert, returned_id) = MutationBuilder::create_node(Arc::clone(&model), non_list_args.clone());
 
-        Self::execute_one(conn, insert)?;
-
         let id = match returned_id {
-            Some(id) => id,
-            None => GraphqlId::Int(conn.last_insert_rowid() as usize),
+            Some(id) => {
+                Self::execute_one(conn, insert)?;
+                id
+            }
+            None => conn.insert_returning_id(insert, &model)?,
         };
 
         for (field_name, list_value) in list_args {
@@ -39,7 +42,7 @@ impl DatabaseCreate for Sqlite {
     }
 
     fn execute_nested_create<T>(
-        conn: &Transaction,
+        conn: &dyn SqlTransaction,
         parent_id: &GraphqlId,
         actions: &NestedActions,
         relation_field: RelationFieldRef,
@@ -50,7 +53,7 @@ impl DatabaseCreate for Sqlite {
         T: AsRef<str>,
     {
         if let Some((select, check)) = actions.required_check(parent_id)? {
-            let ids = Self::query(conn, select, Self::fetch_id)?;
+            let ids = conn.read_ids(select)?;
             check.call_box(ids.into_iter().next())?
         };
 
@@ -1,6 +1,6 @@
 use crate::{
     mutaction::{DeleteActions, MutationBuilder, NestedActions},
-    DatabaseDelete, DatabaseRead, DatabaseWrite, Sqlite,
+    DatabaseDelete, DatabaseRead, DatabaseWrite, SqlDatabase, SqlTransaction,
 };
 use connector::{
     error::{ConnectorError, NodeSelectorInfo},
@@ -8,18 +8,20 @@ use connector::{
     ConnectorResult,
 };
 use prisma_models::{GraphqlId, ModelRef, ProjectRef, RelationFieldRef, SingleNode};
-use rusqlite::Transaction;
 use std::sync::Arc;
 
-impl DatabaseDelete for Sqlite {
-    fn execute_delete(conn: &Transaction, node_selector: &NodeSelector) -> ConnectorResult<SingleNode> {
+impl<D> DatabaseDelete for D
+where
+    D: SqlDatabase,
+{
+    fn execute_delete(conn: &dyn SqlTransaction, node_selector: &NodeSelector) -> ConnectorResult<SingleNode> {
         let model = node_selector.field.model();
         let node = Self::find_node(conn, node_selector)?;
 
         let id = node.get_id_value(Arc::clone(&model)).unwrap();
 
         DeleteActions::check_relation_violations(Arc::clone(&model), &[id], |select| {
-            let ids = Self::query(conn, select, Self::fetch_id)?;
+            let ids = conn.read_ids(select)?;
             Ok(ids.into_iter().next())
         })?;
 
@@ -29,25 +31,24 @@ impl DatabaseDelete for Sqlite {
         Ok(node)
     }
 
-    fn execute_delete_many(conn: &Transaction, model: ModelRef, filter: &Filter) -> ConnectorResult<usize> {
+    fn execute_delete_many(conn: &dyn SqlTransaction, model: ModelRef, filter: &Filter) -> ConnectorResult<usize> {
         let ids = Self::ids_for(conn, Arc::clone(&model), filter.clone())?;
         let ids: Vec<&GraphqlId> = ids.iter().map(|id| &*id).collect();
         let count = ids.len();
 
         DeleteActions::check_relation_violations(Arc::clone(&model), ids.as_slice(), |select| {
-            let ids = Self::query(conn, select, Self::fetch_id)?;
+            let ids = conn.read_ids(select)?;
             Ok(ids.into_iter().next())
         })?;
 
-        let deletes = { MutationBuilder::delete_many(model, ids.as_slice()) };
-
+        let deletes = MutationBuilder::delete_many(model, ids.as_slice());
         Self::execute_many(conn, deletes)?;
 
         Ok(count)
     }
 
     fn execute_nested_delete(
-        conn: &Transaction,
+        conn: &dyn SqlTransaction,
         parent_id: &GraphqlId,
         actions: &NestedActions,
         node_selector: &Option<NodeSelector>,
@@ -82,25 +83,25 @@ impl DatabaseDelete for Sqlite {
 
         {
             let (select, check) = actions.ensure_connected(parent_id, &child_id);
-            let ids = Self::query(conn, select, Self::fetch_id)?;
+            let ids = conn.read_ids(select)?;
             check.call_box(ids.into_iter().next())?;
         }
 
         let related_model = relation_field.related_model();
 
-        DeleteActions::check_relation_violations(related_model, &[&child_id; 1], |select| {
-            let ids = Self::query(conn, select, Self::fetch_id)?;
+        DeleteActions::check_relation_violations(Arc::clone(&related_model), &[&child_id], |select| {
+            let ids = conn.read_ids(select)?;
             Ok(ids.into_iter().next())
         })?;
 
-        let deletes = MutationBuilder::delete_many(relation_field.related_model(), &[&child_id]);
+        let deletes = MutationBuilder::delete_many(related_model, &[&child_id]);
         Self::execute_many(conn, deletes)?;
 
         Ok(())
     }
 
     fn execute_nested_delete_many(
-        conn: &Transaction,
+        conn: &dyn SqlTransaction,
         parent_id: &GraphqlId,
         filter: &Option<Filter>,
         relation_field: RelationFieldRef,
@@ -109,27 +110,20 @@ impl DatabaseDelete for Sqlite {
         let count = ids.len();
 
         let ids: Vec<&GraphqlId> = ids.iter().map(|id| &*id).collect();
-        let model = relation_field.model();
+        let related_model = relation_field.related_model();
 
-        DeleteActions::check_relation_violations(model, ids.as_slice(), |select| {
-            let ids = Self::query(conn, select, Self::fetch_id)?;
+        DeleteActions::check_relation_violations(Arc::clone(&related_model), ids.as_slice(), |select| {
+            let ids = conn.read_ids(select)?;
             Ok(ids.into_iter().next())
         })?;
 
-        let deletes = MutationBuilder::delete_many(relation_field.related_model(), ids.as_slice());
-
+        let deletes = MutationBuilder::delete_many(related_model, ids.as_slice());
         Self::execute_many(conn, deletes)?;
 
         Ok(count)
     }
 
-    fn execute_reset_data(conn: &Transaction, project: ProjectRef) -> ConnectorResult<()> {
-        Self::without_foreign_key_checks(conn, || {
-            let deletes = MutationBuilder::truncate_tables(project);
-
-            Self::execute_many(conn, deletes)
-        })?;
-
-        Ok(())
+    fn execute_reset_data(conn: &dyn SqlTransaction, project: ProjectRef) -> ConnectorResult<()> {
+        conn.truncate(project)
     }
 }
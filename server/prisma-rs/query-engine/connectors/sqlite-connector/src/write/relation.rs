@@ -1,22 +1,24 @@
 use crate::{
     mutaction::{MutationBuilder, NestedActions},
-    DatabaseRead, DatabaseRelation, DatabaseWrite, Sqlite,
+    DatabaseRead, DatabaseRelation, DatabaseWrite, SqlDatabase, SqlTransaction,
 };
 use connector::{filter::NodeSelector, ConnectorResult};
 use prisma_models::{GraphqlId, RelationFieldRef};
-use rusqlite::Transaction;
 use std::sync::Arc;
 
-impl DatabaseRelation for Sqlite {
+impl<D> DatabaseRelation for D
+where
+    D: SqlDatabase,
+{
     fn execute_connect(
-        conn: &Transaction,
+        conn: &dyn SqlTransaction,
         parent_id: &GraphqlId,
         actions: &NestedActions,
         node_selector: &NodeSelector,
         relation_field: RelationFieldRef,
     ) -> ConnectorResult<()> {
         if let Some((select, check)) = actions.required_check(parent_id)? {
-            let ids = Self::query(conn, select, Self::fetch_id)?;
+            let ids = conn.read_ids(select)?;
             check.call_box(ids.into_iter().next())?
         }
 
@@ -37,13 +39,13 @@ impl DatabaseRelation for Sqlite {
     }
 
     fn execute_disconnect(
-        conn: &Transaction,
+        conn: &dyn SqlTransaction,
         parent_id: &GraphqlId,
         actions: &NestedActions,
         node_selector: &Option<NodeSelector>,
     ) -> ConnectorResult<()> {
         if let Some((select, check)) = actions.required_check(parent_id)? {
-            let ids = Self::query(conn, select, Self::fetch_id)?;
+            let ids = conn.read_ids(select)?;
             check.call_box(ids.into_iter().next())?
         }
 
@@ -51,7 +53,7 @@ impl DatabaseRelation for Sqlite {
             None => {
                 let (select, check) = actions.ensure_parent_is_connected(parent_id);
 
-                let ids = Self::query(conn, select, Self::fetch_id)?;
+                let ids = conn.read_ids(select)?;
                 check.call_box(ids.into_iter().next())?;
 
                 Self::execute_one(conn, actions.removal_by_parent(parent_id))
@@ -60,7 +62,7 @@ impl DatabaseRelation for Sqlite {
                 let child_id = Self::id_for(conn, selector)?;
                 let (select, check) = actions.ensure_connected(parent_id, &child_id);
 
-                let ids = Self::query(conn, select, Self::fetch_id)?;
+                let ids = conn.read_ids(select)?;
                 check.call_box(ids.into_iter().next())?;
 
                 Self::execute_one(conn, actions.removal_by_parent_and_child(parent_id, &child_id))
@@ -69,14 +71,14 @@ impl DatabaseRelation for Sqlite {
     }
 
     fn execute_set(
-        conn: &Transaction,
+        conn: &dyn SqlTransaction,
         parent_id: &GraphqlId,
         actions: &NestedActions,
         node_selectors: &Vec<NodeSelector>,
         relation_field: RelationFieldRef,
     ) -> ConnectorResult<()> {
         if let Some((select, check)) = actions.required_check(parent_id)? {
-            let ids = Self::query(conn, select, Self::fetch_id)?;
+            let ids = conn.read_ids(select)?;
             check.call_box(ids.into_iter().next())?
         }
 
@@ -1,25 +1,29 @@
-use crate::{mutaction::MutationBuilder, DatabaseRead, DatabaseUpdate, DatabaseWrite, Sqlite};
+use crate::{mutaction::MutationBuilder, DatabaseRead, DatabaseUpdate, DatabaseWrite, SqlDatabase, SqlTransaction};
 use connector::{
     filter::{Filter, NodeSelector},
+    mutaction::JsonUpdate,
     ConnectorResult,
 };
 use prisma_models::{GraphqlId, ModelRef, PrismaArgs, PrismaListValue, RelationFieldRef};
-use rusqlite::Transaction;
 use std::sync::Arc;
 
-impl DatabaseUpdate for Sqlite {
+impl<D> DatabaseUpdate for D
+where
+    D: SqlDatabase,
+{
     fn execute_update<T>(
-        conn: &Transaction,
+        conn: &dyn SqlTransaction,
         node_selector: &NodeSelector,
         non_list_args: &PrismaArgs,
         list_args: &[(T, PrismaListValue)],
+        json_updates: &[JsonUpdate],
     ) -> ConnectorResult<GraphqlId>
     where
         T: AsRef<str>,
     {
         let model = node_selector.field.model();
         let id = Self::id_for(conn, node_selector)?;
-        let updating = MutationBuilder::update_one(Arc::clone(&model), &id, non_list_args)?;
+        let updating = MutationBuilder::update_one(Arc::clone(&model), &id, non_list_args, json_updates)?;
 
         if let Some(update) = updating {
             Self::execute_one(conn, update)?;
@@ -31,7 +35,7 @@ impl DatabaseUpdate for Sqlite {
     }
 
     fn execute_update_many<T>(
-        conn: &Transaction,
+        conn: &dyn SqlTransaction,
         model: ModelRef,
         filter: &Filter,
         non_list_args: &PrismaArgs,
@@ -45,7 +49,7 @@ impl DatabaseUpdate for Sqlite {
 
         let updates = {
             let ids: Vec<&GraphqlId> = ids.iter().map(|id| &*id).collect();
-            MutationBuilder::update_many(Arc::clone(&model), ids.as_slice(), non_list_args)?
+            MutationBuilder::update_many(Arc::clone(&model), ids.as_slice(), non_list_args, &[])?
         };
 
         Self::execute_many(conn, updates)?;
@@ -55,12 +59,13 @@ impl DatabaseUpdate for Sqlite {
     }
 
     fn execute_nested_update<T>(
-        conn: &Transaction,
+        conn: &dyn SqlTransaction,
         parent_id: &GraphqlId,
         node_selector: &Option<NodeSelector>,
         relation_field: RelationFieldRef,
         non_list_args: &PrismaArgs,
         list_args: &[(T, PrismaListValue)],
+        json_updates: &[JsonUpdate],
     ) -> ConnectorResult<GraphqlId>
     where
         T: AsRef<str>,
@@ -72,11 +77,11 @@ impl DatabaseUpdate for Sqlite {
         let id = Self::get_id_by_parent(conn, Arc::clone(&relation_field), parent_id, node_selector)?;
 
         let node_selector = NodeSelector::from((relation_field.related_model().fields().id(), id));
-        Self::execute_update(conn, &node_selector, non_list_args, list_args)
+        Self::execute_update(conn, &node_selector, non_list_args, list_args, json_updates)
     }
 
     fn execute_nested_update_many<T>(
-        conn: &Transaction,
+        conn: &dyn SqlTransaction,
         parent_id: &GraphqlId,
         filter: &Option<Filter>,
         relation_field: RelationFieldRef,
@@ -91,7 +96,7 @@ impl DatabaseUpdate for Sqlite {
 
         let updates = {
             let ids: Vec<&GraphqlId> = ids.iter().map(|id| &*id).collect();
-            MutationBuilder::update_many(relation_field.related_model(), ids.as_slice(), non_list_args)?
+            MutationBuilder::update_many(relation_field.related_model(), ids.as_slice(), non_list_args, &[])?
         };
 
         Self::execute_many(conn, updates)?;
@@ -101,7 +106,7 @@ impl DatabaseUpdate for Sqlite {
     }
 
     fn update_list_args<T>(
-        conn: &Transaction,
+        conn: &dyn SqlTransaction,
         ids: &[GraphqlId],
         model: ModelRef,
         list_args: &[(T, PrismaListValue)],
@@ -0,0 +1,130 @@
+use prisma_models::*;
+use rusqlite::NO_PARAMS;
+use sqlite_connector::{Sqlite, TransactionalExecutor};
+use std::{env, fs};
+
+connector_test::conformance_tests!(Sqlite::new(1, true).unwrap(), migrate);
+
+/// Creates the tables of the schema in a new database file: a table per model, a table per
+/// scalar list and a table per relation. Removing a record removes its list values and links
+/// through the foreign keys. The full-text search indexes are created like when the server
+/// starts, so that their triggers run on every write of the suite.
+fn migrate(sqlite: &Sqlite, schema: &Schema) {
+    let server_root = env::var("SERVER_ROOT").unwrap_or_else(|_| String::from("."));
+
+    fs::create_dir_all(format!("{}/db", server_root)).unwrap();
+    let _ = fs::remove_file(format!("{}/db/{}.db", server_root, schema.db_name));
+
+    sqlite
+        .with_transaction(&schema.db_name, |conn| {
+            for statement in create_tables(schema) {
+                conn.execute(&statement, NO_PARAMS)?;
+            }
+
+            Ok(())
+        })
+        .unwrap();
+
+    sqlite.create_full_text_search_indexes(schema).unwrap();
+}
+
+fn create_tables(schema: &Schema) -> Vec<String> {
+    let database = quote(&schema.db_name);
+    let mut statements = Vec::new();
+
+    for model in schema.models() {
+        let table = quote(model.db_name());
+        let id = model.fields().id();
+
+        let columns: Vec<String> = model
+            .fields()
+            .scalar_non_list()
+            .iter()
+            .map(|field| {
+                let mut column = format!("{} {}", quote(field.db_name()), column_type(field.type_identifier));
+
+                if field.name == id.name {
+                    column.push_str(" NOT NULL PRIMARY KEY");
+                } else if field.is_required {
+                    column.push_str(" NOT NULL");
+                }
+
+                if field.is_unique && field.name != id.name {
+                    column.push_str(" UNIQUE");
+                }
+
+                column
+            })
+            .collect();
+
+        statements.push(format!("CREATE TABLE {}.{} ({});", database, table, columns.join(", ")));
+
+        for field in model.fields().scalar_list() {
+            let list_table = quote(&format!("{}_{}", model.db_name(), field.db_name()));
+            let node_id = quote(ScalarListTable::NODE_ID_FIELD_NAME);
+            let position = quote(ScalarListTable::POSITION_FIELD_NAME);
+
+            let columns = vec![
+                format!(
+                    "{} TEXT NOT NULL REFERENCES {}({}) ON DELETE CASCADE",
+                    node_id,
+                    table,
+                    quote(id.db_name())
+                ),
+                format!("{} INTEGER NOT NULL", position),
+                format!(
+                    "{} {} NOT NULL",
+                    quote(ScalarListTable::VALUE_FIELD_NAME),
+                    column_type(field.type_identifier)
+                ),
+                format!("PRIMARY KEY ({}, {})", node_id, position),
+            ];
+
+            statements.push(format!(
+                "CREATE TABLE {}.{} ({});",
+                database,
+                list_table,
+                columns.join(", ")
+            ));
+        }
+    }
+
+    for relation in schema.relations() {
+        let references = |model: ModelRef| {
+            format!(
+                "TEXT NOT NULL REFERENCES {}({}) ON DELETE CASCADE",
+                quote(model.db_name()),
+                quote(model.fields().id().db_name())
+            )
+        };
+
+        let model_a = quote(Relation::MODEL_A_DEFAULT_COLUMN);
+        let model_b = quote(Relation::MODEL_B_DEFAULT_COLUMN);
+
+        let columns = vec![
+            String::from("\"id\" TEXT NOT NULL PRIMARY KEY"),
+            format!("{} {}", model_a, references(relation.model_a())),
+            format!("{} {}", model_b, references(relation.model_b())),
+            format!("UNIQUE ({}, {})", model_a, model_b),
+        ];
+
+        let table = quote(&format!("_{}", relation.name));
+        statements.push(format!("CREATE TABLE {}.{} ({});", database, table, columns.join(", ")));
+    }
+
+    statements
+}
+
+fn column_type(type_identifier: TypeIdentifier) -> &'static str {
+    match type_identifier {
+        TypeIdentifier::Int => "INTEGER",
+        TypeIdentifier::Float => "REAL",
+        TypeIdentifier::Boolean => "BOOLEAN",
+        TypeIdentifier::DateTime => "DATETIME",
+        _ => "TEXT",
+    }
+}
+
+fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
@@ -0,0 +1,297 @@
+//! Aggregation queries
+//!
+//! `aggregateUser` aggregates all records matching its arguments into a single object, while
+//! `groupByUser(by: [...])` returns an object per distinct combination of the grouped fields.
+//! Both select the number of records in `_count` and aggregations of fields in `_sum`, `_avg`,
+//! `_min` and `_max`. Groups additionally select the values of their grouped fields.
+
+use crate::{query_ast, value, CoreError, CoreResult};
+use connector::{
+    filter::ScalarCondition, Aggregation, AggregationFilter, AggregationRow, AggregationSelection, QueryArguments,
+};
+use graphql_parser::query::{Field, Selection, Value};
+use inflector::Inflector;
+use prisma_models::{ModelRef, ScalarFieldRef, SchemaRef};
+use std::sync::Arc;
+
+/// Prefix of the root fields aggregating all records of a model, like `aggregateUser`.
+pub const AGGREGATE_PREFIX: &str = "aggregate";
+
+/// Prefix of the root fields aggregating groups of records of a model, like `groupByUser`.
+pub const GROUP_BY_PREFIX: &str = "groupBy";
+
+/// The selectable aggregations of fields, by name.
+pub const FIELD_AGGREGATIONS: &[&str] = &["_sum", "_avg", "_min", "_max"];
+
+/// Filter suffixes of the `having` conditions, ordered so that longer suffixes are tried first.
+pub const HAVING_SUFFIXES: &[&str] = &["_not", "_lte", "_gte", "_lt", "_gt"];
+
+#[derive(Debug, Clone)]
+pub struct AggregateQuery {
+    pub name: String,
+    pub model: ModelRef,
+    pub args: QueryArguments,
+    pub selection: AggregationSelection,
+
+    /// The selected fields of the resulting objects
+    pub fields: Vec<AggregateField>,
+
+    /// Grouped queries return a list of groups instead of a single object
+    pub grouped: bool,
+}
+
+/// A field selected on an aggregation result, by response key.
+#[derive(Debug, Clone)]
+pub struct AggregateField {
+    pub key: String,
+    pub value: AggregateValue,
+}
+
+#[derive(Debug, Clone)]
+pub enum AggregateValue {
+    /// The value of the grouped field at the position
+    Group(usize),
+
+    /// The value of the aggregation at the position
+    Aggregation(usize),
+
+    /// The aggregations of fields in `_sum`, `_avg`, `_min` or `_max`
+    Object(Vec<AggregateField>),
+
+    TypeName(String),
+}
+
+/// The aggregated rows with the selection to render them.
+#[derive(Debug)]
+pub struct AggregateQueryResult {
+    pub name: String,
+    pub fields: Vec<AggregateField>,
+    pub grouped: bool,
+    pub rows: Vec<AggregationRow>,
+}
+
+impl AggregateQuery {
+    /// Builds the query if the root field aggregates a model.
+    pub fn build(schema: &SchemaRef, field: &Field) -> Option<CoreResult<Self>> {
+        let (grouped, model_name) = if field.name.starts_with(GROUP_BY_PREFIX) {
+            (true, &field.name[GROUP_BY_PREFIX.len()..])
+        } else if field.name.starts_with(AGGREGATE_PREFIX) {
+            (false, &field.name[AGGREGATE_PREFIX.len()..])
+        } else {
+            return None;
+        };
+
+        let model = schema.find_model(model_name).ok()?;
+
+        Some(Self::new(model, field, grouped))
+    }
+
+    fn new(model: ModelRef, field: &Field, grouped: bool) -> CoreResult<Self> {
+        let mut selection = AggregationSelection::default();
+
+        let args = if grouped {
+            let mut where_ = vec![];
+
+            for argument in field.arguments.iter() {
+                match argument {
+                    (name, value) if name == "by" => {
+                        selection.group_by = query_ast::extract_scalar_fields(&model, "by", value)?
+                    }
+                    (name, value) if name == "having" => selection.having = extract_having(&model, value)?,
+                    argument => where_.push(argument),
+                }
+            }
+
+            query_ast::extract_query_args(Arc::clone(&model), where_)?
+        } else {
+            query_ast::extract_query_args(Arc::clone(&model), &field.arguments)?
+        };
+
+        if grouped && selection.group_by.is_empty() {
+            return Err(CoreError::QueryValidationError(format!(
+                "`{}` requires at least one field in `by`",
+                field.name
+            )));
+        }
+
+        let type_name = if grouped {
+            format!("{}GroupByResult", model.name)
+        } else {
+            format!("{}AggregateResult", model.name)
+        };
+
+        let fields = aggregate_fields(&model, &type_name, &field.selection_set.items, &mut selection)?;
+
+        Ok(Self {
+            name: field.alias.as_ref().unwrap_or(&field.name).clone(),
+            model,
+            args,
+            selection,
+            fields,
+            grouped,
+        })
+    }
+}
+
+/// Parses `having: {_count_gt: 1, age_avg_lte: 30}`, combining all conditions with `AND`.
+fn extract_having(model: &ModelRef, value: &Value) -> CoreResult<Vec<AggregationFilter>> {
+    let object = match value {
+        Value::Object(obj) => obj,
+        Value::Null => return Ok(vec![]),
+        _ => {
+            return Err(CoreError::QueryValidationError(format!(
+                "`having` on model {} must be an object",
+                model.name
+            )));
+        }
+    };
+
+    object
+        .iter()
+        .map(|(key, value)| {
+            let unknown =
+                || CoreError::QueryValidationError(format!("Unknown condition `{}` on model {}", key, model.name));
+
+            let (aggregation_key, suffix) = HAVING_SUFFIXES
+                .iter()
+                .filter(|suffix| key.ends_with(*suffix))
+                .map(|suffix| (&key[..key.len() - suffix.len()], *suffix))
+                .find(|(aggregation_key, _)| having_aggregation(model, aggregation_key).is_some())
+                .unwrap_or((key.as_str(), ""));
+
+            let aggregation = having_aggregation(model, aggregation_key).ok_or_else(unknown)?;
+            let value = value::coerce(aggregation.type_identifier(), value).ok_or_else(|| {
+                CoreError::QueryValidationError(format!("Invalid value `{}` for condition `{}`", value, key))
+            })?;
+
+            let condition = match suffix {
+                "" => ScalarCondition::Equals(value),
+                "_not" => ScalarCondition::NotEquals(value),
+                "_lt" => ScalarCondition::LessThan(value),
+                "_lte" => ScalarCondition::LessThanOrEquals(value),
+                "_gt" => ScalarCondition::GreaterThan(value),
+                "_gte" => ScalarCondition::GreaterThanOrEquals(value),
+                _ => unreachable!(),
+            };
+
+            Ok(AggregationFilter { aggregation, condition })
+        })
+        .collect()
+}
+
+/// The aggregation of a `having` key without its suffix, like `_count` or `age_avg`.
+fn having_aggregation(model: &ModelRef, key: &str) -> Option<Aggregation> {
+    if key == "_count" {
+        return Some(Aggregation::Count);
+    }
+
+    FIELD_AGGREGATIONS
+        .iter()
+        .filter(|name| key.ends_with(*name))
+        .filter_map(|name| {
+            let field = model.fields().find_from_scalar(&key[..key.len() - name.len()]).ok()?;
+            Some(field_aggregation(name, field))
+        })
+        .find(Aggregation::is_supported)
+}
+
+/// The aggregation of a field by its name in the selection, like `_sum`.
+pub(crate) fn field_aggregation(name: &str, field: ScalarFieldRef) -> Aggregation {
+    match name {
+        "_sum" => Aggregation::Sum(field),
+        "_avg" => Aggregation::Avg(field),
+        "_min" => Aggregation::Min(field),
+        "_max" => Aggregation::Max(field),
+        _ => unreachable!(),
+    }
+}
+
+/// Resolves the selection of an aggregation result, adding the selected aggregations to `selection`.
+fn aggregate_fields(
+    model: &ModelRef,
+    type_name: &str,
+    items: &[Selection],
+    selection: &mut AggregationSelection,
+) -> CoreResult<Vec<AggregateField>> {
+    let mut fields = vec![];
+
+    for item in items {
+        let field = match item {
+            Selection::Field(field) => field,
+            _ => continue,
+        };
+
+        let value = match field.name.as_str() {
+            "__typename" => AggregateValue::TypeName(type_name.to_string()),
+            "_count" => AggregateValue::Aggregation(selection.add(Aggregation::Count)),
+            name if FIELD_AGGREGATIONS.contains(&name) => {
+                AggregateValue::Object(aggregated_fields(model, name, &field.selection_set.items, selection)?)
+            }
+            name => match selection.group_by.iter().position(|grouped| &grouped.name == name) {
+                Some(position) => AggregateValue::Group(position),
+                None => {
+                    return Err(CoreError::QueryValidationError(format!(
+                        "Field `{}` of `{}` can only be selected if it's grouped by",
+                        name, type_name
+                    )));
+                }
+            },
+        };
+
+        fields.push(AggregateField {
+            key: field.alias.as_ref().unwrap_or(&field.name).clone(),
+            value,
+        });
+    }
+
+    Ok(fields)
+}
+
+/// Resolves the fields selected in `_sum`, `_avg`, `_min` or `_max`.
+fn aggregated_fields(
+    model: &ModelRef,
+    aggregation_name: &str,
+    items: &[Selection],
+    selection: &mut AggregationSelection,
+) -> CoreResult<Vec<AggregateField>> {
+    let mut fields = vec![];
+
+    for item in items {
+        let field = match item {
+            Selection::Field(field) => field,
+            _ => continue,
+        };
+
+        let value = match field.name.as_str() {
+            "__typename" => AggregateValue::TypeName(aggregation_type_name(&model.name, aggregation_name)),
+            name => {
+                let aggregation = model
+                    .fields()
+                    .find_from_scalar(name)
+                    .ok()
+                    .map(|field| field_aggregation(aggregation_name, field))
+                    .filter(Aggregation::is_supported)
+                    .ok_or_else(|| {
+                        CoreError::QueryValidationError(format!(
+                            "Field `{}` of model {} can't be aggregated in `{}`",
+                            name, model.name, aggregation_name
+                        ))
+                    })?;
+
+                AggregateValue::Aggregation(selection.add(aggregation))
+            }
+        };
+
+        fields.push(AggregateField {
+            key: field.alias.as_ref().unwrap_or(&field.name).clone(),
+            value,
+        });
+    }
+
+    Ok(fields)
+}
+
+/// The name of the type of `_sum`, `_avg`, `_min` or `_max` on a model, like `UserSumAggregate`.
+pub fn aggregation_type_name(model_name: &str, aggregation_name: &str) -> String {
+    format!("{}{}Aggregate", model_name, aggregation_name[1..].to_pascal_case())
+}
@@ -16,7 +16,7 @@ mod single;
 use graphql_parser::query::{Field, Selection, Value};
 use inflector::Inflector;
 
-use crate::{CoreError, CoreResult, PrismaQuery};
+use crate::{filters, CoreError, CoreResult, PrismaQuery};
 use connector::{filter::NodeSelector, QueryArguments};
 use prisma_models::{
     Field as ModelField, ModelRef, OrderBy, PrismaValue, RelationFieldRef, SchemaRef, SelectedField, SelectedFields,
@@ -134,14 +134,16 @@ pub trait BuilderExt {
                                     .fields()
                                     .find_from_scalar(vec[0])
                                     .map(|val| QueryArguments {
-                                        order_by: Some(OrderBy {
+                                        order_by: vec![OrderBy {
+                                            path: vec![],
                                             field: Arc::clone(&val),
                                             sort_order: match vec[1] {
                                                 "ASC" => SortOrder::Ascending,
                                                 "DESC" => SortOrder::Descending,
                                                 _ => unreachable!(),
                                             },
-                                        }),
+                                            search: None,
+                                        }],
                                         ..res
                                     })
                                     .map_err(|_| CoreError::QueryValidationError(format!("Unknown field `{}`", vec[0])))
@@ -149,7 +151,8 @@ pub trait BuilderExt {
                                 Err(CoreError::QueryValidationError("...".into()))
                             }
                         }
-                        ("where", _) => panic!("lolnope"),
+                        ("where", where_) => filters::extract_filter(Arc::clone(&model), where_)
+                            .map(|filter| QueryArguments { filter: Some(filter), ..res }),
                         (name, _) => Err(CoreError::QueryValidationError(format!("Unknown key: `{}`", name))),
                     }
                 } else {
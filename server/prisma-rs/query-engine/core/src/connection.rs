@@ -0,0 +1,109 @@
+//! Relay-style connections
+//!
+//! A connection field (`usersConnection` on the query type, `postsConnection` on a model) is loaded
+//! like the corresponding list field, with the records selected in `edges { node { ... } }`.
+//! The rest of its selection only shapes the response around those records.
+
+use graphql_parser::query::{Field, Selection};
+use prisma_models::GraphqlId;
+use std::collections::HashMap;
+
+/// Suffix of connection field names.
+pub const CONNECTION_SUFFIX: &str = "Connection";
+
+/// A field selected on a connection, an edge, the page info or the aggregate.
+/// The selection of `node` is not part of it, as it's loaded by the query itself.
+#[derive(Debug, Clone)]
+pub struct ConnectionField {
+    /// The response key of the field
+    pub key: String,
+    pub name: String,
+    pub fields: Vec<ConnectionField>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConnectionSelection {
+    pub fields: Vec<ConnectionField>,
+}
+
+impl ConnectionSelection {
+    pub fn new(field: &Field) -> Self {
+        Self {
+            fields: connection_fields(&field.selection_set.items),
+        }
+    }
+
+    /// The total count is only queried if `aggregate` is selected.
+    pub fn selects_aggregate(&self) -> bool {
+        self.fields.iter().any(|field| field.name == "aggregate")
+    }
+
+    /// The selection of the records of a connection field, in `edges { node { ... } }`.
+    pub fn node_selection(field: &Field) -> &[Selection] {
+        find_field(&field.selection_set.items, "edges")
+            .and_then(|edges| find_field(&edges.selection_set.items, "node"))
+            .map(|node| node.selection_set.items.as_slice())
+            .unwrap_or(&[])
+    }
+}
+
+/// The page of records of a connection.
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionPage {
+    pub has_next_page: bool,
+    pub has_previous_page: bool,
+
+    /// The number of records matching the filter, if `aggregate` is selected
+    pub count: Option<usize>,
+}
+
+/// Everything a connection response holds besides the records themselves.
+#[derive(Debug, Clone)]
+pub struct ConnectionResult {
+    pub selection: ConnectionSelection,
+
+    /// Name of the model, for the `__typename` of the connection types
+    pub model_name: String,
+
+    /// Pages by parent id. Root connections have a single page without a parent.
+    pub pages: HashMap<Option<GraphqlId>, ConnectionPage>,
+}
+
+impl ConnectionResult {
+    pub fn page(&self, parent_id: Option<&GraphqlId>) -> ConnectionPage {
+        self.pages.get(&parent_id.cloned()).cloned().unwrap_or_default()
+    }
+}
+
+/// The cursor of a record is its id, as accepted by `after` and `before`.
+pub fn cursor(id: &GraphqlId) -> String {
+    match id {
+        GraphqlId::String(s) => s.clone(),
+        GraphqlId::Int(i) => i.to_string(),
+        GraphqlId::UUID(u) => u.to_hyphenated().to_string(),
+    }
+}
+
+fn connection_fields(items: &[Selection]) -> Vec<ConnectionField> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            Selection::Field(field) => Some(ConnectionField {
+                key: field.alias.as_ref().unwrap_or(&field.name).clone(),
+                name: field.name.clone(),
+                fields: match field.name.as_str() {
+                    "node" => vec![],
+                    _ => connection_fields(&field.selection_set.items),
+                },
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+fn find_field<'a>(items: &'a [Selection], name: &str) -> Option<&'a Field> {
+    items.iter().find_map(|item| match item {
+        Selection::Field(field) if field.name == name => Some(field),
+        _ => None,
+    })
+}
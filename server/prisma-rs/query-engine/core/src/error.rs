@@ -12,6 +12,9 @@ pub enum CoreError {
 
     #[fail(display = "Query validation error: {}", _0)]
     QueryValidationError(String),
+
+    #[fail(display = "Unexpected result of the mutaction: {}", _0)]
+    MutactionResultError(String),
 }
 
 impl From<ConnectorError> for CoreError {
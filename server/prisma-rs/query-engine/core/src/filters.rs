@@ -0,0 +1,293 @@
+//! Parsing of the GraphQL `where` argument into connector filters
+
+use crate::{value, CoreError, CoreResult};
+use connector::filter::*;
+use graphql_parser::query::Value;
+use prisma_models::{Field as ModelField, ModelRef, RelationFieldRef, ScalarField, ScalarFieldRef, TypeIdentifier};
+use std::sync::Arc;
+
+/// Filter suffixes on scalar fields, ordered so that longer suffixes are tried first.
+const SCALAR_SUFFIXES: &[&str] = &[
+    "_not_starts_with",
+    "_not_ends_with",
+    "_not_contains",
+    "_starts_with",
+    "_ends_with",
+    "_contains",
+    "_search",
+    "_not_in",
+    "_not",
+    "_lte",
+    "_gte",
+    "_in",
+    "_lt",
+    "_gt",
+];
+
+/// Filter suffixes on scalar list fields, ordered so that longer suffixes are tried first.
+const SCALAR_LIST_SUFFIXES: &[&str] = &["_contains_every", "_contains_some", "_contains"];
+
+/// Filter suffix of the conditions on values in JSON fields.
+const JSON_SUFFIX: &str = "_json";
+
+const RELATION_SUFFIXES: &[&str] = &["_every", "_some", "_none"];
+
+/// Key of the case sensitivity of the string filters in a `where` object. Field names can't start
+/// with an underscore, so it never shadows a field.
+pub(crate) const FILTER_MODE: &str = "_mode";
+
+/// Parses a `where` input object of the given model into a filter.
+/// All entries of the object are combined with `AND`.
+///
+/// `_mode: insensitive` makes the string filters of the object ignore case. It doesn't apply to
+/// the nested objects of `AND`, `OR`, `NOT` and relation filters.
+pub fn extract_filter(model: ModelRef, where_: &Value) -> CoreResult<Filter> {
+    let object = match where_ {
+        Value::Object(obj) => obj,
+        _ => {
+            return Err(CoreError::QueryValidationError(format!(
+                "Filter on model {} must be an object",
+                model.name
+            )));
+        }
+    };
+
+    let mode = match object.get(FILTER_MODE) {
+        Some(value) => extract_mode(value)?,
+        None => StringMode::Default,
+    };
+
+    let filters = object
+        .iter()
+        .filter(|(key, _)| key.as_str() != FILTER_MODE)
+        .map(|(key, value)| extract_entry(Arc::clone(&model), key, value, mode))
+        .collect::<CoreResult<Vec<Filter>>>()?;
+
+    Ok(match filters.len() {
+        1 => filters.into_iter().next().unwrap(),
+        _ => Filter::and(filters),
+    })
+}
+
+fn extract_mode(value: &Value) -> CoreResult<StringMode> {
+    match value {
+        Value::Enum(mode) if mode == "default" => Ok(StringMode::Default),
+        Value::Enum(mode) if mode == "insensitive" => Ok(StringMode::Insensitive),
+        Value::Null => Ok(StringMode::Default),
+        _ => Err(CoreError::QueryValidationError(format!(
+            "Invalid filter mode `{}`, expected `default` or `insensitive`",
+            value
+        ))),
+    }
+}
+
+fn extract_entry(model: ModelRef, key: &str, value: &Value, mode: StringMode) -> CoreResult<Filter> {
+    match key {
+        "AND" => Ok(Filter::and(extract_many(model, value)?)),
+        "OR" => Ok(Filter::or(extract_many(model, value)?)),
+        "NOT" => Ok(Filter::not(extract_many(model, value)?)),
+        _ => match model.fields().find_from_all(key) {
+            Ok(ModelField::Scalar(field)) => scalar_filter(Arc::clone(field), "", value, mode),
+            Ok(ModelField::Relation(field)) => relation_filter(Arc::clone(field), "", value),
+            Err(_) => extract_suffixed(model, key, value, mode),
+        },
+    }
+}
+
+/// `AND`, `OR` and `NOT` take a list of filters, or a single one.
+fn extract_many(model: ModelRef, value: &Value) -> CoreResult<Vec<Filter>> {
+    match value {
+        Value::List(values) => values.iter().map(|v| extract_filter(Arc::clone(&model), v)).collect(),
+        single => Ok(vec![extract_filter(model, single)?]),
+    }
+}
+
+fn extract_suffixed(model: ModelRef, key: &str, value: &Value, mode: StringMode) -> CoreResult<Filter> {
+    let unknown = || CoreError::QueryValidationError(format!("Unknown filter `{}` on model {}", key, model.name));
+
+    if key.ends_with(JSON_SUFFIX) {
+        let field_name = &key[..key.len() - JSON_SUFFIX.len()];
+
+        match model.fields().find_from_scalar(field_name) {
+            Ok(ref field) if !field.is_list && field.type_identifier == TypeIdentifier::Json => {
+                return json_filter(Arc::clone(field), value);
+            }
+            _ => (),
+        }
+    }
+
+    for suffix in SCALAR_LIST_SUFFIXES {
+        if key.ends_with(suffix) {
+            let field_name = &key[..key.len() - suffix.len()];
+
+            match model.fields().find_from_scalar(field_name) {
+                Ok(ref field) if field.is_list => return scalar_list_filter(Arc::clone(field), suffix, value),
+                _ => (),
+            }
+        }
+    }
+
+    for suffix in SCALAR_SUFFIXES {
+        if key.ends_with(suffix) {
+            let field_name = &key[..key.len() - suffix.len()];
+
+            if let Ok(field) = model.fields().find_from_scalar(field_name) {
+                return scalar_filter(field, suffix, value, mode);
+            }
+        }
+    }
+
+    for suffix in RELATION_SUFFIXES {
+        if key.ends_with(suffix) {
+            let field_name = &key[..key.len() - suffix.len()];
+
+            if let Ok(field) = model.fields().find_from_relation_fields(field_name) {
+                return relation_filter(field, suffix, value);
+            }
+        }
+    }
+
+    Err(unknown())
+}
+
+fn scalar_filter(field: ScalarFieldRef, suffix: &str, input: &Value, mode: StringMode) -> CoreResult<Filter> {
+    if field.is_list {
+        return Err(CoreError::QueryValidationError(format!(
+            "Scalar list field `{}` can't be filtered with `{}{}`",
+            field.name, field.name, suffix
+        )));
+    }
+
+    let condition = match suffix {
+        "_search" => ScalarCondition::Search(search_query(&field, input)?),
+        "" => ScalarCondition::Equals(value::to_prisma_value(&field, input)?),
+        "_not" => ScalarCondition::NotEquals(value::to_prisma_value(&field, input)?),
+        "_contains" => ScalarCondition::Contains(value::to_prisma_value(&field, input)?),
+        "_not_contains" => ScalarCondition::NotContains(value::to_prisma_value(&field, input)?),
+        "_starts_with" => ScalarCondition::StartsWith(value::to_prisma_value(&field, input)?),
+        "_not_starts_with" => ScalarCondition::NotStartsWith(value::to_prisma_value(&field, input)?),
+        "_ends_with" => ScalarCondition::EndsWith(value::to_prisma_value(&field, input)?),
+        "_not_ends_with" => ScalarCondition::NotEndsWith(value::to_prisma_value(&field, input)?),
+        "_lt" => ScalarCondition::LessThan(value::to_prisma_value(&field, input)?),
+        "_lte" => ScalarCondition::LessThanOrEquals(value::to_prisma_value(&field, input)?),
+        "_gt" => ScalarCondition::GreaterThan(value::to_prisma_value(&field, input)?),
+        "_gte" => ScalarCondition::GreaterThanOrEquals(value::to_prisma_value(&field, input)?),
+        "_in" => ScalarCondition::In(value::to_prisma_list_value(&field, input)?),
+        "_not_in" => ScalarCondition::NotIn(value::to_prisma_list_value(&field, input)?),
+        _ => unreachable!(),
+    };
+
+    Ok(Filter::Scalar(ScalarFilter { field, condition, mode }))
+}
+
+/// Full-text searches take the words to find in a string field.
+fn search_query(field: &ScalarField, input: &Value) -> CoreResult<String> {
+    match input {
+        Value::String(search) if field.type_identifier == TypeIdentifier::String => Ok(search.clone()),
+        _ => Err(CoreError::QueryValidationError(format!(
+            "Invalid search `{}` on field `{}`",
+            input, field.name
+        ))),
+    }
+}
+
+fn scalar_list_filter(field: ScalarFieldRef, suffix: &str, input: &Value) -> CoreResult<Filter> {
+    let condition = match suffix {
+        "_contains" => ScalarListCondition::Contains(value::to_prisma_value(&field, input)?),
+        "_contains_every" => ScalarListCondition::ContainsEvery(value::to_prisma_list_value(&field, input)?),
+        "_contains_some" => ScalarListCondition::ContainsSome(value::to_prisma_list_value(&field, input)?),
+        _ => unreachable!(),
+    };
+
+    Ok(Filter::ScalarList(ScalarListFilter { field, condition }))
+}
+
+/// Parses `meta_json: {path: ["a", "b"], equals: 3}`. The conditions apply to the value at the
+/// path, or the whole document without one, and are combined with `AND`.
+fn json_filter(field: ScalarFieldRef, input: &Value) -> CoreResult<Filter> {
+    let object = match input {
+        Value::Object(obj) => obj,
+        _ => {
+            return Err(CoreError::QueryValidationError(format!(
+                "Filter `{}{}` must be an object",
+                field.name, JSON_SUFFIX
+            )));
+        }
+    };
+
+    let path = match object.get("path") {
+        Some(path) => value::to_json_path(path)?,
+        None => vec![],
+    };
+
+    let filters = object
+        .iter()
+        .filter(|(key, _)| key.as_str() != "path")
+        .map(|(key, input)| {
+            let condition = match (key.as_str(), input) {
+                ("equals", input) => JsonCondition::Equals(value::to_json(input)?),
+                ("array_contains", input) => JsonCondition::ArrayContains(value::to_json(input)?),
+                ("string_contains", Value::String(s)) => JsonCondition::StringContains(s.clone()),
+                _ => {
+                    return Err(CoreError::QueryValidationError(format!(
+                        "Invalid condition `{}: {}` in filter `{}{}`",
+                        key, input, field.name, JSON_SUFFIX
+                    )));
+                }
+            };
+
+            Ok(Filter::Json(JsonFilter {
+                field: Arc::clone(&field),
+                path: path.clone(),
+                condition,
+            }))
+        })
+        .collect::<CoreResult<Vec<Filter>>>()?;
+
+    Ok(match filters.len() {
+        1 => filters.into_iter().next().unwrap(),
+        _ => Filter::and(filters),
+    })
+}
+
+fn relation_filter(field: RelationFieldRef, suffix: &str, value: &Value) -> CoreResult<Filter> {
+    let condition = match (field.is_list, suffix) {
+        (true, "_every") => RelationCondition::EveryRelatedNode,
+        (true, "_some") => RelationCondition::AtLeastOneRelatedNode,
+        (true, "_none") => RelationCondition::NoRelatedNode,
+        (false, "") => RelationCondition::ToOneRelatedNode,
+        _ => {
+            return Err(CoreError::QueryValidationError(format!(
+                "Relation field `{}` can't be filtered with `{}{}`",
+                field.name, field.name, suffix
+            )));
+        }
+    };
+
+    match value {
+        // `author: null` selects records without a related record.
+        Value::Null if condition == RelationCondition::ToOneRelatedNode => {
+            Ok(Filter::OneRelationIsNull(OneRelationIsNullFilter { field }))
+        }
+        _ => {
+            let nested_filter = match value {
+                Value::Object(obj) if obj.is_empty() => Filter::BoolFilter(true),
+                _ => extract_filter(field.related_model(), value)?,
+            };
+
+            Ok(Filter::Relation(RelationFilter {
+                field,
+                nested_filter: Box::new(nested_filter),
+                condition,
+            }))
+        }
+    }
+}
+
+/// Parses an optional `where` input, defaulting to a filter matching every record.
+pub fn extract_optional_filter(model: ModelRef, where_: Option<&Value>) -> CoreResult<Filter> {
+    match where_ {
+        Some(where_) => extract_filter(model, where_),
+        None => Ok(Filter::from(true)),
+    }
+}
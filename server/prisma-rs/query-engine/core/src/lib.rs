@@ -1,13 +1,25 @@
 #![deny(warnings)]
 
+mod aggregation;
+mod connection;
 mod error;
+mod filters;
+mod mutation_ast;
+mod normalize;
 mod query_ast;
 mod query_executor;
+mod schema;
+mod value;
 
 mod builders;
 
+pub use aggregation::*;
+pub use connection::*;
 pub use error::*;
+pub use mutation_ast::*;
+pub use normalize::NormalizedOperation;
 pub use query_ast::*;
 pub use query_executor::*;
+pub use schema::*;
 
 pub type CoreResult<T> = Result<T, CoreError>;
@@ -0,0 +1,530 @@
+//! Prisma mutation AST module
+
+use crate::{filters, query_ast, value, CoreError, CoreResult, PrismaQuery};
+use connector::{filter::NodeSelector, mutaction::*};
+use graphql_parser::{self as gql, query::*};
+use inflector::Inflector;
+use prisma_models::{Field as ModelField, *};
+use std::{collections::BTreeMap, sync::Arc};
+
+/// A top-level write, together with the selection used to read back the written record.
+#[derive(Debug, Clone)]
+pub struct WriteQuery {
+    pub name: String,
+    pub model: ModelRef,
+    pub mutaction: TopLevelDatabaseMutaction,
+    pub selected_fields: SelectedFields,
+    pub nested: Vec<PrismaQuery>,
+}
+
+#[derive(Debug)]
+enum MutationType {
+    Create(ModelRef),
+    Update(ModelRef),
+    Upsert(ModelRef),
+    Delete(ModelRef),
+    UpdateMany(ModelRef),
+    DeleteMany(ModelRef),
+}
+
+impl MutationType {
+    /// Infers the mutation type from the field name
+    fn infer_root(model: &ModelRef, field: &gql::query::Field) -> Option<Self> {
+        let singular = &model.name;
+        let plural = model.name.to_plural();
+        let model = Arc::clone(model);
+
+        match field.name.as_str() {
+            name if name == format!("create{}", singular) => Some(MutationType::Create(model)),
+            name if name == format!("update{}", singular) => Some(MutationType::Update(model)),
+            name if name == format!("upsert{}", singular) => Some(MutationType::Upsert(model)),
+            name if name == format!("delete{}", singular) => Some(MutationType::Delete(model)),
+            name if name == format!("updateMany{}", plural) => Some(MutationType::UpdateMany(model)),
+            name if name == format!("deleteMany{}", plural) => Some(MutationType::DeleteMany(model)),
+            _ => None,
+        }
+    }
+}
+
+type ListArgs = Vec<(String, PrismaListValue)>;
+
+/// Suffix of the `data` keys partially updating JSON fields, like `meta_json`.
+const JSON_UPDATE_SUFFIX: &str = "_json";
+
+/// The arguments of a `data` input object.
+#[derive(Default)]
+struct WriteArgs {
+    non_list_args: PrismaArgs,
+    list_args: ListArgs,
+    json_updates: Vec<JsonUpdate>,
+    nested_mutactions: NestedMutactions,
+}
+
+pub(crate) struct MutationBuilder<'a> {
+    schema: SchemaRef,
+    field: &'a gql::query::Field,
+}
+
+impl<'a> MutationBuilder<'a> {
+    pub fn new(schema: SchemaRef, field: &'a gql::query::Field) -> Self {
+        Self { schema, field }
+    }
+
+    pub fn build(self) -> CoreResult<WriteQuery> {
+        let name = self.field.alias.as_ref().unwrap_or(&self.field.name).clone();
+
+        let mutation_type = self
+            .schema
+            .models()
+            .iter()
+            .filter_map(|model| MutationType::infer_root(model, self.field))
+            .nth(0)
+            .ok_or_else(|| CoreError::QueryValidationError(format!("Model not found for mutation {}", name)))?;
+
+        let (model, mutaction) = match mutation_type {
+            MutationType::Create(model) => {
+                let create = self.create_node(Arc::clone(&model), self.required_argument("data")?)?;
+                (model, TopLevelDatabaseMutaction::CreateNode(create))
+            }
+            MutationType::Update(model) => {
+                let where_ = self.node_selector(Arc::clone(&model), self.required_argument("where")?)?;
+                let update = self.update_node(Arc::clone(&model), where_, self.required_argument("data")?)?;
+
+                (model, TopLevelDatabaseMutaction::UpdateNode(update))
+            }
+            MutationType::Upsert(model) => {
+                let where_ = self.node_selector(Arc::clone(&model), self.required_argument("where")?)?;
+                let create = self.create_node(Arc::clone(&model), self.required_argument("create")?)?;
+                let update = self.update_node(Arc::clone(&model), where_.clone(), self.required_argument("update")?)?;
+
+                let upsert = UpsertNode { where_, create, update };
+                (model, TopLevelDatabaseMutaction::UpsertNode(upsert))
+            }
+            MutationType::Delete(model) => {
+                let where_ = self.node_selector(Arc::clone(&model), self.required_argument("where")?)?;
+                (model, TopLevelDatabaseMutaction::DeleteNode(DeleteNode { where_ }))
+            }
+            MutationType::UpdateMany(model) => {
+                let (non_list_args, list_args) =
+                    self.extract_batch_data(Arc::clone(&model), self.required_argument("data")?)?;
+                let filter = filters::extract_optional_filter(Arc::clone(&model), self.argument("where"))?;
+
+                let update_nodes = UpdateNodes {
+                    model: Arc::clone(&model),
+                    filter,
+                    non_list_args,
+                    list_args,
+                };
+
+                (model, TopLevelDatabaseMutaction::UpdateNodes(update_nodes))
+            }
+            MutationType::DeleteMany(model) => {
+                let filter = filters::extract_optional_filter(Arc::clone(&model), self.argument("where"))?;
+                let delete_nodes = DeleteNodes {
+                    model: Arc::clone(&model),
+                    filter,
+                };
+
+                (model, TopLevelDatabaseMutaction::DeleteNodes(delete_nodes))
+            }
+        };
+
+        // Batch mutations only return a count, there is no record to select from.
+        let (selected_fields, nested) = match mutaction {
+            TopLevelDatabaseMutaction::UpdateNodes(_) | TopLevelDatabaseMutaction::DeleteNodes(_) => {
+                (SelectedFields::default(), vec![])
+            }
+            _ => query_ast::build_selection(Arc::clone(&self.schema), self.field, Arc::clone(&model))?,
+        };
+
+        Ok(WriteQuery {
+            name,
+            model,
+            mutaction,
+            selected_fields,
+            nested,
+        })
+    }
+
+    fn argument(&self, name: &str) -> Option<&'a Value> {
+        self.field.arguments.iter().find(|(k, _)| k == name).map(|(_, v)| v)
+    }
+
+    fn required_argument(&self, name: &str) -> CoreResult<&'a Value> {
+        self.argument(name).ok_or_else(|| {
+            CoreError::QueryValidationError(format!(
+                "Required argument `{}` missing on mutation `{}`",
+                name, self.field.name
+            ))
+        })
+    }
+
+    fn create_node(&self, model: ModelRef, data: &Value) -> CoreResult<CreateNode> {
+        let args = self.extract_data(Arc::clone(&model), data, true)?;
+
+        Ok(CreateNode {
+            model,
+            non_list_args: args.non_list_args,
+            list_args: args.list_args,
+            nested_mutactions: args.nested_mutactions,
+        })
+    }
+
+    fn update_node(&self, model: ModelRef, where_: NodeSelector, data: &Value) -> CoreResult<UpdateNode> {
+        let args = self.extract_data(model, data, false)?;
+
+        Ok(UpdateNode {
+            where_,
+            non_list_args: args.non_list_args,
+            list_args: args.list_args,
+            json_updates: args.json_updates,
+            nested_mutactions: args.nested_mutactions,
+        })
+    }
+
+    /// Reads the `data` of a batch update, which can't contain nested writes.
+    fn extract_batch_data(&self, model: ModelRef, data: &Value) -> CoreResult<(PrismaArgs, ListArgs)> {
+        let args = self.extract_data(model, data, false)?;
+
+        if !args.nested_mutactions.mutactions().is_empty() {
+            Err(CoreError::QueryValidationError(
+                "Nested writes are not allowed in batch updates".into(),
+            ))
+        } else if !args.json_updates.is_empty() {
+            Err(CoreError::QueryValidationError(
+                "JSON fields can't be partially updated in batch updates".into(),
+            ))
+        } else {
+            Ok((args.non_list_args, args.list_args))
+        }
+    }
+
+    /// Splits a `data` input object into scalar arguments, scalar list arguments and nested writes.
+    /// `top_is_create` denotes whether the record owning `data` is being created.
+    fn extract_data(&self, model: ModelRef, data: &Value, top_is_create: bool) -> CoreResult<WriteArgs> {
+        let object = Self::object(data, "data")?;
+        let mut args = WriteArgs::default();
+
+        for (name, input) in object {
+            match model.fields().find_from_all(name) {
+                Ok(ModelField::Scalar(field)) if field.is_list => {
+                    // Scalar lists are written as `{ set: [...] }`
+                    let values = match input {
+                        Value::Object(obj) => match obj.get("set") {
+                            Some(values) => value::to_prisma_list_value(field, values)?,
+                            None => vec![],
+                        },
+                        other => value::to_prisma_list_value(field, other)?,
+                    };
+
+                    args.list_args.push((field.name.clone(), values));
+                }
+                Ok(ModelField::Scalar(field)) => {
+                    args.non_list_args
+                        .insert(field.name.clone(), value::to_prisma_value(field, input)?);
+                }
+                Ok(ModelField::Relation(field)) => {
+                    self.extract_nested(Arc::clone(field), input, top_is_create, &mut args.nested_mutactions)?;
+                }
+                Err(_) if name.ends_with(JSON_UPDATE_SUFFIX) && !top_is_create => {
+                    let field_name = &name[..name.len() - JSON_UPDATE_SUFFIX.len()];
+                    let field = model
+                        .fields()
+                        .find_from_scalar(field_name)
+                        .ok()
+                        .filter(|field| !field.is_list && field.type_identifier == TypeIdentifier::Json)
+                        .ok_or_else(|| {
+                            CoreError::QueryValidationError(format!(
+                                "Field `{}` not found on model {}",
+                                name, model.name
+                            ))
+                        })?;
+
+                    args.json_updates.append(&mut Self::extract_json_updates(field, input)?);
+                }
+                Err(_) => {
+                    return Err(CoreError::QueryValidationError(format!(
+                        "Field `{}` not found on model {}",
+                        name, model.name
+                    )));
+                }
+            }
+        }
+
+        // Setting a field while partially updating it is ambiguous.
+        if let Some(update) = args
+            .json_updates
+            .iter()
+            .find(|update| args.non_list_args.has_arg_for(&update.field.name))
+        {
+            return Err(CoreError::QueryValidationError(format!(
+                "Field `{}` can't be set and partially updated at once",
+                update.field.name
+            )));
+        }
+
+        Ok(args)
+    }
+
+    /// Parses `meta_json: {set_path: {path: ["a", "b"], value: 3}, merge: {c: true}}`. Paths are set
+    /// before merging.
+    fn extract_json_updates(field: ScalarFieldRef, input: &Value) -> CoreResult<Vec<JsonUpdate>> {
+        let object = Self::object(input, &format!("{}{}", field.name, JSON_UPDATE_SUFFIX))?;
+        let mut updates = vec![];
+
+        if let Some(set_path) = object.get("set_path") {
+            let set_path = Self::object(set_path, "set_path")?;
+
+            updates.push(JsonUpdate {
+                field: Arc::clone(&field),
+                operation: JsonOperation::SetPath {
+                    path: value::to_json_path(Self::key(set_path, "path")?)?,
+                    value: value::to_json(Self::key(set_path, "value")?)?,
+                },
+            });
+        }
+
+        if let Some(merge) = object.get("merge") {
+            updates.push(JsonUpdate {
+                field: Arc::clone(&field),
+                operation: JsonOperation::Merge(value::to_json(merge)?),
+            });
+        }
+
+        match object
+            .keys()
+            .find(|key| key.as_str() != "set_path" && key.as_str() != "merge")
+        {
+            Some(key) => Err(CoreError::QueryValidationError(format!(
+                "Unknown operation `{}` on `{}{}`",
+                key, field.name, JSON_UPDATE_SUFFIX
+            ))),
+            None => Ok(updates),
+        }
+    }
+
+    /// Collects the nested writes on a relation field, e.g. `posts: { create: [...], connect: [...] }`.
+    /// To-many relations take a list (or a single object) of inputs, to-one relations take a single input
+    /// and use `true` to `disconnect` or `delete` the related record.
+    fn extract_nested(
+        &self,
+        field: RelationFieldRef,
+        input: &Value,
+        top_is_create: bool,
+        nested: &mut NestedMutactions,
+    ) -> CoreResult<()> {
+        let related_model = field.related_model();
+
+        for (action, input) in Self::object(input, &field.name)? {
+            match action.as_str() {
+                "create" => {
+                    for data in Self::many(input) {
+                        let args = self.extract_data(Arc::clone(&related_model), data, true)?;
+
+                        nested.creates.push(NestedCreateNode {
+                            relation_field: Arc::clone(&field),
+                            non_list_args: args.non_list_args,
+                            list_args: args.list_args,
+                            top_is_create,
+                            nested_mutactions: args.nested_mutactions,
+                        });
+                    }
+                }
+                "connect" => {
+                    for where_ in Self::many(input) {
+                        nested.connects.push(NestedConnect {
+                            relation_field: Arc::clone(&field),
+                            where_: self.node_selector(Arc::clone(&related_model), where_)?,
+                            top_is_create,
+                        });
+                    }
+                }
+                "disconnect" => {
+                    for where_ in self.nested_wheres(&field, input)? {
+                        nested.disconnects.push(NestedDisconnect {
+                            relation_field: Arc::clone(&field),
+                            where_,
+                        });
+                    }
+                }
+                "set" => {
+                    let wheres = Self::many(input)
+                        .into_iter()
+                        .map(|where_| self.node_selector(Arc::clone(&related_model), where_))
+                        .collect::<CoreResult<Vec<NodeSelector>>>()?;
+
+                    nested.sets.push(NestedSet {
+                        relation_field: Arc::clone(&field),
+                        wheres,
+                    });
+                }
+                "update" => {
+                    for update in Self::many(input) {
+                        let (where_, data) = if field.is_list {
+                            let update = Self::object(update, "update")?;
+                            let where_ = self.node_selector(Arc::clone(&related_model), Self::key(update, "where")?)?;
+
+                            (Some(where_), Self::key(update, "data")?)
+                        } else {
+                            (None, update)
+                        };
+
+                        let args = self.extract_data(Arc::clone(&related_model), data, false)?;
+
+                        nested.updates.push(NestedUpdateNode {
+                            relation_field: Arc::clone(&field),
+                            where_,
+                            non_list_args: args.non_list_args,
+                            list_args: args.list_args,
+                            json_updates: args.json_updates,
+                            nested_mutactions: args.nested_mutactions,
+                        });
+                    }
+                }
+                "upsert" => {
+                    for upsert in Self::many(input) {
+                        let upsert = Self::object(upsert, "upsert")?;
+                        let where_ = if field.is_list {
+                            Some(self.node_selector(Arc::clone(&related_model), Self::key(upsert, "where")?)?)
+                        } else {
+                            None
+                        };
+
+                        let create =
+                            self.extract_data(Arc::clone(&related_model), Self::key(upsert, "create")?, true)?;
+                        let update =
+                            self.extract_data(Arc::clone(&related_model), Self::key(upsert, "update")?, false)?;
+
+                        nested.upserts.push(NestedUpsertNode {
+                            relation_field: Arc::clone(&field),
+                            where_: where_.clone(),
+                            create: NestedCreateNode {
+                                relation_field: Arc::clone(&field),
+                                non_list_args: create.non_list_args,
+                                list_args: create.list_args,
+                                top_is_create,
+                                nested_mutactions: create.nested_mutactions,
+                            },
+                            update: NestedUpdateNode {
+                                relation_field: Arc::clone(&field),
+                                where_,
+                                non_list_args: update.non_list_args,
+                                list_args: update.list_args,
+                                json_updates: update.json_updates,
+                                nested_mutactions: update.nested_mutactions,
+                            },
+                        });
+                    }
+                }
+                "delete" => {
+                    for where_ in self.nested_wheres(&field, input)? {
+                        nested.deletes.push(NestedDeleteNode {
+                            relation_field: Arc::clone(&field),
+                            where_,
+                        });
+                    }
+                }
+                "updateMany" => {
+                    for update in Self::many(input) {
+                        let update = Self::object(update, "updateMany")?;
+                        let filter = filters::extract_optional_filter(Arc::clone(&related_model), update.get("where"))?;
+                        let (non_list_args, list_args) =
+                            self.extract_batch_data(Arc::clone(&related_model), Self::key(update, "data")?)?;
+
+                        nested.update_manys.push(NestedUpdateNodes {
+                            relation_field: Arc::clone(&field),
+                            filter: Some(filter),
+                            non_list_args,
+                            list_args,
+                        });
+                    }
+                }
+                "deleteMany" => {
+                    for where_ in Self::many(input) {
+                        let filter = filters::extract_optional_filter(Arc::clone(&related_model), Some(where_))?;
+
+                        nested.delete_manys.push(NestedDeleteNodes {
+                            relation_field: Arc::clone(&field),
+                            filter: Some(filter),
+                        });
+                    }
+                }
+                other => {
+                    return Err(CoreError::QueryValidationError(format!(
+                        "Unknown nested write `{}` on relation field `{}`",
+                        other, field.name
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Selectors for `disconnect` and `delete`: unique selectors on to-many relations,
+    /// or `true` on to-one relations, which don't need one.
+    fn nested_wheres(&self, field: &RelationFieldRef, input: &Value) -> CoreResult<Vec<Option<NodeSelector>>> {
+        match (field.is_list, input) {
+            (true, input) => Self::many(input)
+                .into_iter()
+                .map(|where_| self.node_selector(field.related_model(), where_).map(Some))
+                .collect(),
+            (false, Value::Boolean(true)) => Ok(vec![None]),
+            (false, Value::Boolean(false)) => Ok(vec![]),
+            (false, _) => Err(CoreError::QueryValidationError(format!(
+                "Expected a boolean for to-one relation field `{}`",
+                field.name
+            ))),
+        }
+    }
+
+    /// Reads a `where` input object selecting a record by a single unique field.
+    fn node_selector(&self, model: ModelRef, where_: &Value) -> CoreResult<NodeSelector> {
+        let object = Self::object(where_, "where")?;
+
+        match object.iter().next() {
+            Some((field_name, value)) if object.len() == 1 => {
+                let field = model.fields().find_from_scalar(field_name).map_err(|_| {
+                    CoreError::QueryValidationError(format!("Field `{}` not found on model {}", field_name, model.name))
+                })?;
+
+                if !(field.is_unique || field.is_id()) {
+                    return Err(CoreError::QueryValidationError(format!(
+                        "Field `{}` of model {} is not unique",
+                        field_name, model.name
+                    )));
+                }
+
+                let value = value::to_prisma_value(&field, value)?;
+                Ok(NodeSelector::new(field, value))
+            }
+            _ => Err(CoreError::QueryValidationError(
+                "Exactly one unique field must be provided in `where`".into(),
+            )),
+        }
+    }
+
+    fn key<'v>(object: &'v BTreeMap<String, Value>, key: &str) -> CoreResult<&'v Value> {
+        object
+            .get(key)
+            .ok_or_else(|| CoreError::QueryValidationError(format!("Required field `{}` missing", key)))
+    }
+
+    /// A list input, or a single value treated as a list of one.
+    fn many(value: &Value) -> Vec<&Value> {
+        match value {
+            Value::List(values) => values.iter().collect(),
+            single => vec![single],
+        }
+    }
+
+    fn object<'v>(value: &'v Value, name: &str) -> CoreResult<&'v BTreeMap<String, Value>> {
+        match value {
+            Value::Object(obj) => Ok(obj),
+            _ => Err(CoreError::QueryValidationError(format!(
+                "Argument `{}` must be an object",
+                name
+            ))),
+        }
+    }
+}
@@ -0,0 +1,257 @@
+//! Normalization of a GraphQL document before building queries
+//!
+//! Selects the operation to execute, substitutes variables into argument values,
+//! drops selections excluded by `@skip` and `@include` and expands named and inline
+//! fragments, so that the builders only ever see plain fields with literal arguments.
+
+use crate::{CoreError, CoreResult};
+use graphql_parser::query::*;
+use std::collections::{BTreeMap, HashMap};
+
+/// Finds the operation to execute. Without an operation name, the document must contain exactly one operation.
+pub fn find_operation<'a>(document: &'a Document, name: Option<&str>) -> CoreResult<&'a OperationDefinition> {
+    let operations: Vec<&OperationDefinition> = document
+        .definitions
+        .iter()
+        .filter_map(|d| match d {
+            Definition::Operation(op) => Some(op),
+            Definition::Fragment(_) => None,
+        })
+        .collect();
+
+    match name {
+        Some(name) => operations
+            .into_iter()
+            .find(|op| operation_name(op) == Some(name))
+            .ok_or_else(|| CoreError::QueryValidationError(format!("Unknown operation named `{}`", name))),
+        None if operations.len() == 1 => Ok(operations[0]),
+        None if operations.is_empty() => Err(CoreError::QueryValidationError(
+            "The document does not contain any operation".into(),
+        )),
+        None => Err(CoreError::QueryValidationError(
+            "Must provide an operation name if the document contains multiple operations".into(),
+        )),
+    }
+}
+
+fn operation_name(op: &OperationDefinition) -> Option<&str> {
+    match op {
+        OperationDefinition::SelectionSet(_) => None,
+        OperationDefinition::Query(q) => q.name.as_ref().map(String::as_str),
+        OperationDefinition::Mutation(m) => m.name.as_ref().map(String::as_str),
+        OperationDefinition::Subscription(s) => s.name.as_ref().map(String::as_str),
+    }
+}
+
+/// An operation of a document with fragments expanded and variables substituted,
+/// normalized once and shared by the validation and the query builders.
+pub struct NormalizedOperation<'a> {
+    /// The operation as written in the document.
+    pub definition: &'a OperationDefinition,
+    /// The fields selected by the operation.
+    pub selection_set: SelectionSet,
+}
+
+impl<'a> NormalizedOperation<'a> {
+    /// Normalizes the operation of `document` selected by `name`.
+    pub fn new(document: &'a Document, name: Option<&str>, variables: &BTreeMap<String, Value>) -> CoreResult<Self> {
+        let definition = find_operation(document, name)?;
+        let normalizer = Normalizer::new(document, definition, variables)?;
+
+        let selection_set = match definition {
+            OperationDefinition::SelectionSet(set) => normalizer.selection_set(set)?,
+            OperationDefinition::Query(q) => normalizer.selection_set(&q.selection_set)?,
+            OperationDefinition::Mutation(m) => normalizer.selection_set(&m.selection_set)?,
+            OperationDefinition::Subscription(_) => {
+                return Err(CoreError::QueryValidationError(
+                    "Subscriptions are not supported".into(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            definition,
+            selection_set,
+        })
+    }
+}
+
+pub struct Normalizer<'a> {
+    fragments: HashMap<&'a str, &'a FragmentDefinition>,
+    variables: BTreeMap<String, Value>,
+}
+
+impl<'a> Normalizer<'a> {
+    /// Prepares the normalization of `operation`. Variables missing from `variables` fall back to
+    /// their default value in the operation, or `null` unless they're declared non-null.
+    pub fn new(
+        document: &'a Document,
+        operation: &'a OperationDefinition,
+        variables: &BTreeMap<String, Value>,
+    ) -> CoreResult<Self> {
+        let fragments = document
+            .definitions
+            .iter()
+            .filter_map(|d| match d {
+                Definition::Fragment(f) => Some((f.name.as_str(), f)),
+                Definition::Operation(_) => None,
+            })
+            .collect();
+
+        let definitions = match operation {
+            OperationDefinition::SelectionSet(_) => &[][..],
+            OperationDefinition::Query(q) => &q.variable_definitions[..],
+            OperationDefinition::Mutation(m) => &m.variable_definitions[..],
+            OperationDefinition::Subscription(s) => &s.variable_definitions[..],
+        };
+
+        let variables = definitions
+            .iter()
+            .map(|def| {
+                let value = variables
+                    .get(&def.name)
+                    .or_else(|| def.default_value.as_ref())
+                    .cloned()
+                    .unwrap_or(Value::Null);
+
+                let is_required = match def.var_type {
+                    Type::NonNullType(_) => true,
+                    _ => false,
+                };
+
+                if is_required && value == Value::Null {
+                    return Err(CoreError::QueryValidationError(format!(
+                        "Variable `${}` is required, but it was not provided",
+                        def.name
+                    )));
+                }
+
+                Ok((def.name.clone(), value))
+            })
+            .collect::<CoreResult<_>>()?;
+
+        Ok(Self { fragments, variables })
+    }
+
+    /// Returns the fields of the selection set, with fragments expanded and variables substituted.
+    pub fn selection_set(&self, set: &'a SelectionSet) -> CoreResult<SelectionSet> {
+        let mut items = vec![];
+        self.collect_fields(set, &mut vec![], &mut items)?;
+
+        Ok(SelectionSet { span: set.span, items })
+    }
+
+    fn collect_fields(
+        &self,
+        set: &'a SelectionSet,
+        visited: &mut Vec<&'a str>,
+        items: &mut Vec<Selection>,
+    ) -> CoreResult<()> {
+        for selection in &set.items {
+            let directives = match selection {
+                Selection::Field(field) => &field.directives,
+                Selection::InlineFragment(fragment) => &fragment.directives,
+                Selection::FragmentSpread(spread) => &spread.directives,
+            };
+
+            if !self.is_included(directives)? {
+                continue;
+            }
+
+            match selection {
+                Selection::Field(field) => items.push(Selection::Field(self.field(field)?)),
+                Selection::InlineFragment(fragment) => self.collect_fields(&fragment.selection_set, visited, items)?,
+                Selection::FragmentSpread(spread) => {
+                    let name = spread.fragment_name.as_str();
+                    let fragment = self
+                        .fragments
+                        .get(name)
+                        .ok_or_else(|| CoreError::QueryValidationError(format!("Unknown fragment `{}`", name)))?;
+
+                    if visited.contains(&name) {
+                        return Err(CoreError::QueryValidationError(format!(
+                            "Fragment `{}` can't spread itself",
+                            name
+                        )));
+                    }
+
+                    visited.push(name);
+                    self.collect_fields(&fragment.selection_set, visited, items)?;
+                    visited.pop();
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether the `@skip` and `@include` directives of a selection keep it.
+    fn is_included(&self, directives: &[Directive]) -> CoreResult<bool> {
+        for directive in directives {
+            let included = match directive.name.as_str() {
+                "include" => self.directive_condition(directive)?,
+                "skip" => !self.directive_condition(directive)?,
+                _ => true,
+            };
+
+            if !included {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn directive_condition(&self, directive: &Directive) -> CoreResult<bool> {
+        let condition = directive
+            .arguments
+            .iter()
+            .find(|(name, _)| name == "if")
+            .map(|(_, value)| self.value(value))
+            .transpose()?;
+
+        match condition {
+            Some(Value::Boolean(condition)) => Ok(condition),
+            _ => Err(CoreError::QueryValidationError(format!(
+                "Directive `@{}` requires a Boolean argument `if`",
+                directive.name
+            ))),
+        }
+    }
+
+    fn field(&self, field: &'a Field) -> CoreResult<Field> {
+        let arguments = field
+            .arguments
+            .iter()
+            .map(|(name, value)| Ok((name.clone(), self.value(value)?)))
+            .collect::<CoreResult<Vec<(String, Value)>>>()?;
+
+        Ok(Field {
+            arguments,
+            selection_set: self.selection_set(&field.selection_set)?,
+            ..field.clone()
+        })
+    }
+
+    fn value(&self, value: &Value) -> CoreResult<Value> {
+        match value {
+            Value::Variable(name) => self
+                .variables
+                .get(name)
+                .cloned()
+                .ok_or_else(|| CoreError::QueryValidationError(format!("Variable `${}` is not defined", name))),
+            Value::List(values) => Ok(Value::List(
+                values
+                    .iter()
+                    .map(|v| self.value(v))
+                    .collect::<CoreResult<Vec<Value>>>()?,
+            )),
+            Value::Object(obj) => Ok(Value::Object(
+                obj.iter()
+                    .map(|(k, v)| Ok((k.clone(), self.value(v)?)))
+                    .collect::<CoreResult<BTreeMap<String, Value>>>()?,
+            )),
+            other => Ok(other.clone()),
+        }
+    }
+}
@@ -1,11 +1,22 @@
 //! Prisma query AST module
 
-use crate::{CoreError, CoreResult};
+use crate::{
+    aggregation::AggregateQuery,
+    connection::{ConnectionSelection, CONNECTION_SUFFIX},
+    filters,
+    mutation_ast::MutationBuilder,
+    normalize::NormalizedOperation,
+    schema::{IntrospectionQuery, MUTATION_TYPE, QUERY_TYPE},
+    value, CoreError, CoreResult, WriteQuery,
+};
 use connector::{filter::NodeSelector, QueryArguments};
 use graphql_parser::{self as gql, query::*};
 use inflector::Inflector;
 use prisma_models::{Field as ModelField, *};
-use std::sync::Arc;
+use std::{collections::BTreeMap, convert::TryFrom, sync::Arc};
+
+/// The sort key of `orderBy` sorting by the relevance of records to a full-text search.
+pub const RELEVANCE: &str = "_relevance";
 
 #[derive(Debug, Clone)]
 pub enum PrismaQuery {
@@ -13,6 +24,19 @@ pub enum PrismaQuery {
     MultiRecordQuery(MultiRecordQuery),
     RelatedRecordQuery(RelatedRecordQuery),
     MultiRelatedRecordQuery(MultiRelatedRecordQuery),
+    AggregateQuery(AggregateQuery),
+}
+
+impl PrismaQuery {
+    pub fn name(&self) -> &str {
+        match self {
+            PrismaQuery::RecordQuery(q) => &q.name,
+            PrismaQuery::MultiRecordQuery(q) => &q.name,
+            PrismaQuery::RelatedRecordQuery(q) => &q.name,
+            PrismaQuery::MultiRelatedRecordQuery(q) => &q.name,
+            PrismaQuery::AggregateQuery(q) => &q.name,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -30,6 +54,9 @@ pub struct MultiRecordQuery {
     pub args: QueryArguments,
     pub selected_fields: SelectedFields,
     pub nested: Vec<PrismaQuery>,
+
+    /// Set if the records are queried through a connection field
+    pub connection: Option<ConnectionSelection>,
 }
 
 #[derive(Debug, Clone)]
@@ -48,12 +75,36 @@ pub struct MultiRelatedRecordQuery {
     pub args: QueryArguments,
     pub selected_fields: SelectedFields,
     pub nested: Vec<PrismaQuery>,
+
+    /// Set if the records are queried through a connection field
+    pub connection: Option<ConnectionSelection>,
+}
+
+/// A root field of a GraphQL operation, lowered to either a read, a write
+/// or an introspection of the GraphQL schema.
+#[derive(Debug, Clone)]
+pub enum Operation {
+    Read(PrismaQuery),
+    Write(WriteQuery),
+    Introspection(IntrospectionQuery),
+}
+
+impl Operation {
+    /// The response key of the root field.
+    pub fn name(&self) -> &str {
+        match self {
+            Operation::Read(query) => query.name(),
+            Operation::Write(query) => &query.name,
+            Operation::Introspection(query) => query.name(),
+        }
+    }
 }
 
 pub struct RootQueryBuilder {
     pub query: Document,
     pub schema: SchemaRef,
     pub operation_name: Option<String>,
+    pub variables: BTreeMap<String, Value>,
 }
 
 #[derive(Debug)]
@@ -76,6 +127,15 @@ impl QueryType {
         }
     }
 
+    /// Infers the query type for root connection fields like `usersConnection`
+    fn infer_root_connection(model: &ModelRef, field: &gql::query::Field) -> Option<Self> {
+        if format!("{}{}", model.name.to_camel_case().to_plural(), CONNECTION_SUFFIX) == field.name {
+            Some(QueryType::Multiple(Arc::clone(&model)))
+        } else {
+            None
+        }
+    }
+
     fn model(&self) -> ModelRef {
         match self {
             QueryType::Single(m) => Arc::clone(m),
@@ -99,6 +159,7 @@ struct QueryBuilder<'a> {
     args: BuilderResult<QueryArguments>,
     parent_field: Option<RelationFieldRef>,
     nested: BuilderResult<Vec<QueryBuilder<'a>>>,
+    connection: Option<ConnectionSelection>,
 }
 
 impl<'a> QueryBuilder<'a> {
@@ -113,9 +174,16 @@ impl<'a> QueryBuilder<'a> {
             args: None,
             parent_field: None,
             nested: None,
+            connection: None,
         }
     }
 
+    /// Builds the query for a connection field, which selects its records in `edges { node }`.
+    fn connection(mut self) -> Self {
+        self.connection = Some(ConnectionSelection::new(self.field));
+        self
+    }
+
     /// Finds the model and infers the query type for the given GraphQL field.
     fn infer_query_type(mut self, parent: Option<RelationFieldRef>) -> Self {
         self.parent_field = parent;
@@ -135,6 +203,24 @@ impl<'a> QueryBuilder<'a> {
                 .filter_map(|model| QueryType::infer_root(model, self.field))
                 .nth(0);
 
+            let qt = match qt {
+                Some(qt) => Some(qt),
+                None => {
+                    let qt = self
+                        .schema
+                        .models()
+                        .iter()
+                        .filter_map(|model| QueryType::infer_root_connection(model, self.field))
+                        .nth(0);
+
+                    if qt.is_some() {
+                        self.connection = Some(ConnectionSelection::new(self.field));
+                    }
+
+                    qt
+                }
+            };
+
             Some(match qt {
                 Some(model_type) => Ok(model_type),
                 None => Err(CoreError::QueryValidationError(format!(
@@ -182,66 +268,119 @@ impl<'a> QueryBuilder<'a> {
     }
 
     fn extract_query_args(&self, model: ModelRef) -> CoreResult<QueryArguments> {
-        self.field
-            .arguments
-            .iter()
-            .fold(Ok(QueryArguments::default()), |result, (k, v)| {
-                if let Ok(res) = result {
-                    #[cfg_attr(rustfmt, rustfmt_skip)]
-                    match (k.as_str(), v) {
-                        ("skip", Value::Int(num)) => match num.as_i64() {
-                            Some(num) => Ok(QueryArguments { skip: Some(num as u32), ..res }),
-                            None => Err(CoreError::QueryValidationError("Invalid number povided".into())),
-                        },
-                        ("first", Value::Int(num)) => match num.as_i64() {
-                            Some(num) => Ok(QueryArguments { first: Some(num as u32), ..res }),
-                            None => Err(CoreError::QueryValidationError("Invalid number povided".into())),
-                        },
-                        ("last", Value::Int(num)) => match num.as_i64() {
-                            Some(num) => Ok(QueryArguments { first: Some(num as u32), ..res }),
-                            None => Err(CoreError::QueryValidationError("Invalid number povided".into())),
-                        },
-                        //("after", Value::String(s)) if s.is_uuid() => Ok(QueryArguments { after: Some(UuidString(s.clone()).into()), ..res }),
-                        ("after", Value::String(s)) => Ok(QueryArguments { after: Some(s.clone().into()), ..res }),
-                        ("after", Value::Int(num)) => match num.as_i64() {
-                            Some(num) => Ok(QueryArguments { first: Some(num as u32), ..res }),
-                            None => Err(CoreError::QueryValidationError("Invalid number povided".into())),
-                        },
-                        //("before", Value::String(s)) if s.is_uuid() => Ok(QueryArguments { before: Some(UuidString(s.clone()).into()), ..res }),
-                        ("before", Value::String(s)) => Ok(QueryArguments { before: Some(s.clone().into()), ..res }),
-                        ("before", Value::Int(num)) => match num.as_i64() {
-                            Some(num) => Ok(QueryArguments { first: Some(num as u32), ..res }),
-                            None => Err(CoreError::QueryValidationError("Invalid number povided".into())),
-                        },
-                        ("orderby", Value::Enum(name)) => {
-                            let vec = name.split("_").collect::<Vec<&str>>();
-                            if vec.len() == 2 {
-                                model
-                                    .fields()
-                                    .find_from_scalar(vec[0])
-                                    .map(|val| QueryArguments {
-                                        order_by: Some(OrderBy {
-                                            field: Arc::clone(&val),
-                                            sort_order: match vec[1] {
-                                                "ASC" => SortOrder::Ascending,
-                                                "DESC" => SortOrder::Descending,
-                                                _ => unreachable!(),
-                                            },
-                                        }),
-                                        ..res
-                                    })
-                                    .map_err(|_| CoreError::QueryValidationError(format!("Unknown field `{}`", vec[0])))
-                            } else {
-                                Err(CoreError::QueryValidationError("...".into()))
-                            }
-                        }
-                        ("where", _) => panic!("lolnope"),
-                        (name, _) => Err(CoreError::QueryValidationError(format!("Unknown key: `{}`", name))),
-                    }
-                } else {
-                    result
-                }
+        extract_query_args(model, &self.field.arguments)
+    }
+
+    /// Parses `orderBy: [{name: asc}, {author: {name: desc}}]`, where fields of to-one related models
+    /// are nested in their relation field. A single sort key may be given without the list.
+    fn extract_order_by(model: &ModelRef, value: &Value) -> CoreResult<Vec<OrderBy>> {
+        let keys = match value {
+            Value::List(keys) => keys.iter().collect(),
+            key => vec![key],
+        };
+
+        keys.into_iter()
+            .map(|key| match key {
+                Value::Object(obj) => Self::extract_sort_key(model, vec![], obj),
+                _ => Err(CoreError::QueryValidationError(format!(
+                    "Invalid entry `{}` of `orderBy`",
+                    key
+                ))),
             })
+            .collect()
+    }
+
+    fn extract_sort_key(
+        model: &ModelRef,
+        mut path: Vec<RelationFieldRef>,
+        obj: &BTreeMap<String, Value>,
+    ) -> CoreResult<OrderBy> {
+        // Objects are unordered, so every key has to be a separate object.
+        if obj.len() != 1 {
+            return Err(CoreError::QueryValidationError(
+                "Every entry of `orderBy` has to sort by exactly one field".into(),
+            ));
+        }
+
+        let (name, value) = obj.iter().next().unwrap();
+
+        if name == RELEVANCE && path.is_empty() {
+            return Self::extract_relevance(model, value);
+        }
+
+        let sort_order = match value {
+            Value::Enum(o) | Value::String(o) if o == "asc" => Some(SortOrder::Ascending),
+            Value::Enum(o) | Value::String(o) if o == "desc" => Some(SortOrder::Descending),
+            _ => None,
+        };
+
+        match (model.fields().find_from_all(name), sort_order, value) {
+            (Ok(ModelField::Scalar(field)), Some(sort_order), _) => Ok(OrderBy {
+                path,
+                field: Arc::clone(field),
+                sort_order,
+                search: None,
+            }),
+            (Ok(ModelField::Relation(field)), None, Value::Object(nested)) if !field.is_list => {
+                path.push(Arc::clone(field));
+                Self::extract_sort_key(&field.related_model(), path, nested)
+            }
+            _ => Err(CoreError::QueryValidationError(format!(
+                "Cannot order `{}` by `{}: {}`",
+                model.name, name, value
+            ))),
+        }
+    }
+
+    /// Parses `_relevance: {field: name, search: "...", sort: desc}`, which sorts by how well the
+    /// records match a full-text search on a string field. Descending relevance puts the best
+    /// matches first.
+    fn extract_relevance(model: &ModelRef, value: &Value) -> CoreResult<OrderBy> {
+        let invalid = || {
+            CoreError::QueryValidationError(format!(
+                "Invalid `{}: {}` of `orderBy`, expected `{{field, search, sort}}`",
+                RELEVANCE, value
+            ))
+        };
+
+        let obj = match value {
+            Value::Object(obj) if obj.len() == 3 => obj,
+            _ => return Err(invalid()),
+        };
+
+        let field = match obj.get("field") {
+            Some(Value::Enum(name)) | Some(Value::String(name)) => model
+                .fields()
+                .find_from_scalar(name)
+                .ok()
+                .filter(|field| !field.is_list && field.type_identifier == TypeIdentifier::String)
+                .ok_or_else(|| {
+                    CoreError::QueryValidationError(format!(
+                        "Field `{}` of model {} can't be searched",
+                        name, model.name
+                    ))
+                })?,
+            _ => return Err(invalid()),
+        };
+
+        let search = match obj.get("search") {
+            Some(Value::String(search)) => search.clone(),
+            _ => return Err(invalid()),
+        };
+
+        // Better matches rank lower, so the most relevant records come first by ascending rank.
+        let sort_order = match obj.get("sort") {
+            Some(Value::Enum(o)) | Some(Value::String(o)) if o == "desc" => SortOrder::Ascending,
+            Some(Value::Enum(o)) | Some(Value::String(o)) if o == "asc" => SortOrder::Descending,
+            _ => return Err(invalid()),
+        };
+
+        Ok(OrderBy {
+            path: vec![],
+            field,
+            sort_order,
+            search: Some(search),
+        })
     }
 
     // Todo: From trait somewhere?
@@ -257,9 +396,7 @@ impl<'a> QueryBuilder<'a> {
         if let Some(Ok(ref qt)) = self.query_type {
             let model = qt.model();
             let selected_fields = self
-                .field
-                .selection_set
-                .items
+                .record_selection()
                 .iter()
                 .filter_map(|i| {
                     if let Selection::Field(f) = i {
@@ -272,6 +409,7 @@ impl<'a> QueryBuilder<'a> {
                             }))),
                             // Relation fields are not handled here, but in nested queries
                             Ok(ModelField::Relation(_field)) => None,
+                            _ if Self::connection_relation(&model, f).is_some() => None,
                             _ => Some(Err(CoreError::QueryValidationError(format!(
                                 "Selected field {} not found on model {}",
                                 f.name, model.name,
@@ -305,9 +443,7 @@ impl<'a> QueryBuilder<'a> {
             let model = qt.model();
 
             let nested_queries: CoreResult<Vec<QueryBuilder>> = self
-                .field
-                .selection_set
-                .items
+                .record_selection()
                 .iter()
                 .filter_map(|i| {
                     if let Selection::Field(f) = i {
@@ -325,10 +461,22 @@ impl<'a> QueryBuilder<'a> {
 
                                 Some(Ok(qb))
                             }
-                            _ => Some(Err(CoreError::QueryValidationError(format!(
-                                "Selected field {} not found on model {}",
-                                f.name, model.name,
-                            )))),
+                            _ => match Self::connection_relation(&model, f) {
+                                Some(field) => {
+                                    let qb = QueryBuilder::new(Arc::clone(&self.schema), f)
+                                        .connection()
+                                        .infer_query_type(Some(field))
+                                        .process_arguments()
+                                        .map_selected_scalar_fields()
+                                        .collect_nested_queries();
+
+                                    Some(Ok(qb))
+                                }
+                                None => Some(Err(CoreError::QueryValidationError(format!(
+                                    "Selected field {} not found on model {}",
+                                    f.name, model.name,
+                                )))),
+                            },
                         }
                     } else {
                         // Todo: We only support selecting fields at the moment.
@@ -343,6 +491,46 @@ impl<'a> QueryBuilder<'a> {
         self
     }
 
+    /// The selection of the records, which is nested in `edges { node }` for connection fields.
+    fn record_selection(&self) -> &'a [Selection] {
+        match self.connection {
+            Some(_) => ConnectionSelection::node_selection(self.field),
+            None => &self.field.selection_set.items,
+        }
+    }
+
+    /// The to-many relation field a nested connection field like `postsConnection` is based on.
+    fn connection_relation(model: &ModelRef, field: &gql::query::Field) -> Option<RelationFieldRef> {
+        if !field.name.ends_with(CONNECTION_SUFFIX) {
+            return None;
+        }
+
+        let name = &field.name[..field.name.len() - CONNECTION_SUFFIX.len()];
+        match model.fields().find_from_all(name) {
+            Ok(ModelField::Relation(field)) if field.is_list => Some(Arc::clone(field)),
+            _ => None,
+        }
+    }
+
+    /// Collects the selected fields and nested queries of a field returning a single record of `model`,
+    /// as used for mutation payloads.
+    fn get_selection(self) -> CoreResult<(SelectedFields, Vec<PrismaQuery>)> {
+        let selected_fields = self.selected_fields.unwrap_or(Err(CoreError::QueryValidationError(
+            "Selected fields required but not found".into(),
+        )))?;
+
+        let nested_queries = self
+            .nested
+            .unwrap_or(Err(CoreError::QueryValidationError(
+                "Required nested queries not found".into(),
+            )))?
+            .into_iter()
+            .map(|qb| qb.get())
+            .collect::<CoreResult<Vec<PrismaQuery>>>()?;
+
+        Ok((selected_fields, nested_queries))
+    }
+
     // Q: Wouldn't it make more sense to just call that one from the outside and not the other ones?
     fn get(self) -> CoreResult<PrismaQuery> {
         dbg!(&self);
@@ -389,6 +577,7 @@ impl<'a> QueryBuilder<'a> {
                         model,
                         selected_fields,
                         nested: nested_queries,
+                        connection: self.connection,
                     }))
                 }
                 QueryType::OneRelation(_model) => {
@@ -429,6 +618,7 @@ impl<'a> QueryBuilder<'a> {
                         selected_fields: selected_fields,
                         args: args,
                         nested: nested_queries,
+                        connection: self.connection,
                     }))
                 }
             },
@@ -438,56 +628,177 @@ impl<'a> QueryBuilder<'a> {
 }
 
 impl RootQueryBuilder {
-    // FIXME: Find op name and only execute op!
-    pub fn build(self) -> CoreResult<Vec<PrismaQuery>> {
-        self.query
-            .definitions
-            .iter()
-            .map(|d| match d {
-                // Query without the explicit "query" before the selection set
-                Definition::Operation(OperationDefinition::SelectionSet(SelectionSet { span: _, items })) => {
-                    self.build_query(&items)
-                }
+    /// Builds the operation selected by `operation_name`, with variables and fragments resolved.
+    pub fn build(self) -> CoreResult<Vec<Operation>> {
+        let operation = self.normalize()?;
+        self.build_normalized(&operation)
+    }
 
-                // Regular query
-                Definition::Operation(OperationDefinition::Query(Query {
-                    position: _,
-                    name: _,
-                    variable_definitions: _,
-                    directives: _,
-                    selection_set,
-                })) => self.build_query(&selection_set.items),
-                _ => unimplemented!(),
-            })
-            .collect::<CoreResult<Vec<Vec<PrismaQuery>>>>() // Collect all the "query trees"
-            .map(|v| v.into_iter().flatten().collect())
+    /// Normalizes the operation selected by `operation_name`.
+    pub fn normalize(&self) -> CoreResult<NormalizedOperation> {
+        NormalizedOperation::new(
+            &self.query,
+            self.operation_name.as_ref().map(String::as_str),
+            &self.variables,
+        )
     }
 
-    fn build_query(&self, root_fields: &Vec<Selection>) -> CoreResult<Vec<PrismaQuery>> {
+    /// Builds an operation of the document that was already normalized.
+    pub fn build_normalized(&self, operation: &NormalizedOperation) -> CoreResult<Vec<Operation>> {
+        match operation.definition {
+            // Queries with or without the explicit "query" before the selection set
+            OperationDefinition::SelectionSet(_) | OperationDefinition::Query(_) => {
+                self.build_query(&operation.selection_set.items)
+            }
+
+            OperationDefinition::Mutation(_) => self.build_mutation(&operation.selection_set.items),
+
+            OperationDefinition::Subscription(_) => Err(CoreError::QueryValidationError(
+                "Subscriptions are not supported".into(),
+            )),
+        }
+    }
+
+    fn build_query(&self, root_fields: &Vec<Selection>) -> CoreResult<Vec<Operation>> {
         root_fields
             .iter()
             .map(|item| {
                 // First query-level fields map to a model in our schema, either a plural or singular
                 match item {
-                    Selection::Field(root_field) => QueryBuilder::new(Arc::clone(&self.schema), root_field)
-                        .infer_query_type(None)
-                        .process_arguments()
-                        .map_selected_scalar_fields()
-                        .collect_nested_queries()
-                        .get(), // Q: Since we never really give any args, and we always have to call these fns, we should just to it internally and call .get
+                    Selection::Field(root_field) if IntrospectionQuery::is_introspection(root_field) => {
+                        Ok(Self::introspection(QUERY_TYPE, root_field))
+                    }
+                    Selection::Field(root_field) => match AggregateQuery::build(&self.schema, root_field) {
+                        Some(query) => query.map(|query| Operation::Read(PrismaQuery::AggregateQuery(query))),
+                        None => QueryBuilder::new(Arc::clone(&self.schema), root_field)
+                            .infer_query_type(None)
+                            .process_arguments()
+                            .map_selected_scalar_fields()
+                            .collect_nested_queries()
+                            .get() // Q: Since we never really give any args, and we always have to call these fns, we should just to it internally and call .get
+                            .map(Operation::Read),
+                    },
                     _ => unimplemented!(),
                 }
             })
             .collect()
     }
-}
 
-trait UuidCheck {
-    fn is_uuid(&self) -> bool;
+    fn build_mutation(&self, root_fields: &Vec<Selection>) -> CoreResult<Vec<Operation>> {
+        root_fields
+            .iter()
+            .map(|item| match item {
+                Selection::Field(root_field) if IntrospectionQuery::is_introspection(root_field) => {
+                    Ok(Self::introspection(MUTATION_TYPE, root_field))
+                }
+                Selection::Field(root_field) => MutationBuilder::new(Arc::clone(&self.schema), root_field)
+                    .build()
+                    .map(Operation::Write),
+                _ => unimplemented!(),
+            })
+            .collect()
+    }
+
+    fn introspection(root_type: &str, field: &gql::query::Field) -> Operation {
+        Operation::Introspection(IntrospectionQuery {
+            root_type: root_type.into(),
+            field: field.clone(),
+        })
+    }
 }
 
-impl UuidCheck for String {
-    fn is_uuid(&self) -> bool {
-        false
+/// Parses the arguments of fields returning many records of `model`.
+pub(crate) fn extract_query_args<'a, I>(model: ModelRef, arguments: I) -> CoreResult<QueryArguments>
+where
+    I: IntoIterator<Item = &'a (String, Value)>,
+{
+    let id_field = model.fields().id();
+    let args = arguments
+        .into_iter()
+        .fold(Ok(QueryArguments::default()), |result, (k, v)| {
+            if let Ok(res) = result {
+                #[cfg_attr(rustfmt, rustfmt_skip)]
+                match (k.as_str(), v) {
+                    ("skip", Value::Int(num)) => extract_count("skip", num)
+                        .map(|skip| QueryArguments { skip: Some(skip), ..res }),
+                    ("first", Value::Int(num)) => extract_count("first", num)
+                        .map(|first| QueryArguments { first: Some(first), ..res }),
+                    ("last", Value::Int(num)) => extract_count("last", num)
+                        .map(|last| QueryArguments { last: Some(last), ..res }),
+                    ("after", Value::Null) | ("before", Value::Null) => Ok(res),
+                    ("after", cursor) => value::to_cursor(&id_field, cursor)
+                        .map(|id| QueryArguments { after: Some(id), ..res }),
+                    ("before", cursor) => value::to_cursor(&id_field, cursor)
+                        .map(|id| QueryArguments { before: Some(id), ..res }),
+                    ("distinct", Value::Null) => Ok(res),
+                    ("distinct", value) => extract_scalar_fields(&model, "distinct", value)
+                        .map(|distinct| QueryArguments { distinct, ..res }),
+                    ("orderBy", Value::Null) => Ok(res),
+                    ("orderBy", value) => QueryBuilder::extract_order_by(&model, value)
+                        .map(|order_by| QueryArguments { order_by, ..res }),
+                    ("where", where_) => filters::extract_filter(Arc::clone(&model), where_)
+                        .map(|filter| QueryArguments { filter: Some(filter), ..res }),
+                    (name, _) => Err(CoreError::QueryValidationError(format!("Unknown key: `{}`", name))),
+                }
+            } else {
+                result
+            }
+        })?;
+
+    if args.first.is_some() && args.last.is_some() {
+        return Err(CoreError::QueryValidationError(
+            "Combining `first` and `last` is not supported".into(),
+        ));
     }
+
+    Ok(args)
+}
+
+/// Parses the number of records given to `argument`, which can't be negative.
+fn extract_count(argument: &str, num: &Number) -> CoreResult<u32> {
+    num.as_i64()
+        .and_then(|num| u32::try_from(num).ok())
+        .ok_or_else(|| CoreError::QueryValidationError(format!("`{}` must be a non-negative number", argument)))
+}
+
+/// Parses a list of scalar fields of `model` given to `argument`, like `distinct: [name, email]`.
+/// A single field may be given without the list.
+pub(crate) fn extract_scalar_fields(
+    model: &ModelRef,
+    argument: &str,
+    value: &Value,
+) -> CoreResult<Vec<ScalarFieldRef>> {
+    let names = match value {
+        Value::List(names) => names.iter().collect(),
+        name => vec![name],
+    };
+
+    names
+        .into_iter()
+        .map(|name| match name {
+            Value::Enum(name) | Value::String(name) => match model.fields().find_from_scalar(name) {
+                Ok(ref field) if !field.is_list => Ok(Arc::clone(field)),
+                _ => Err(CoreError::QueryValidationError(format!(
+                    "Invalid field `{}` of model {} in `{}`",
+                    name, model.name, argument
+                ))),
+            },
+            _ => Err(CoreError::QueryValidationError(format!(
+                "Invalid entry `{}` of `{}`",
+                name, argument
+            ))),
+        })
+        .collect()
+}
+
+/// Builds the selected fields and nested queries for a field that returns a single record of `model`.
+pub(crate) fn build_selection(
+    schema: SchemaRef,
+    field: &gql::query::Field,
+    model: ModelRef,
+) -> CoreResult<(SelectedFields, Vec<PrismaQuery>)> {
+    let mut qb = QueryBuilder::new(schema, field);
+    qb.query_type = Some(Ok(QueryType::Single(model)));
+
+    qb.map_selected_scalar_fields().collect_nested_queries().get_selection()
 }
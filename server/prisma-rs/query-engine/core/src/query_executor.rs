@@ -1,13 +1,21 @@
-use crate::{query_ast, CoreResult};
-use connector::DataResolver;
-use prisma_models::{GraphqlId, ManyNodes, SelectedFields, SingleNode};
+use crate::{
+    query_ast, AggregateQueryResult, ConnectionPage, ConnectionResult, ConnectionSelection, CoreError, CoreResult,
+    WriteQuery,
+};
+use connector::{filter::NodeSelector, mutaction::*, DataResolver, DatabaseMutactionExecutor, QueryArguments};
+use prisma_models::{GraphqlId, ManyNodes, ModelRef, Node, PrismaValue, RelationFieldRef, SelectedFields, SingleNode};
 use query_ast::*;
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, HashSet},
+    iter,
+    sync::Arc,
+};
 
 #[derive(Debug)]
 pub enum PrismaQueryResult {
     Single(SinglePrismaQueryResult),
     Multi(MultiPrismaQueryResult),
+    Aggregate(AggregateQueryResult),
 }
 
 #[derive(Debug)]
@@ -16,16 +24,32 @@ pub struct SinglePrismaQueryResult {
     pub result: Option<SingleNode>,
     pub nested: Vec<PrismaQueryResult>,
 
+    /// Id of the result node, to associate nested results with it
+    pub id: Option<GraphqlId>,
+
     /// Used for filtering implicit fields in result node
     selected_fields: SelectedFields,
 }
 
+/// The result of a query for many records.
+///
+/// Nested queries are executed once for all parents. Their results hold the related records
+/// of every parent, and each node carries the id of its parent in `Node::parent_id`.
 #[derive(Debug)]
 pub struct MultiPrismaQueryResult {
     pub name: String,
     pub result: ManyNodes,
     pub nested: Vec<PrismaQueryResult>,
 
+    /// Ids of the result nodes, in node order, to associate nested results with their parents
+    pub ids: Vec<GraphqlId>,
+
+    /// The relation field the records were loaded through, for nested results
+    pub parent_field: Option<RelationFieldRef>,
+
+    /// Page info and counts of connection queries
+    pub connection: Option<ConnectionResult>,
+
     /// Used for filtering implicit fields in result nodes
     selected_fields: SelectedFields,
 }
@@ -36,6 +60,7 @@ impl PrismaQueryResult {
         match self {
             PrismaQueryResult::Single(s) => PrismaQueryResult::Single(s.filter()),
             PrismaQueryResult::Multi(m) => PrismaQueryResult::Multi(m.filter()),
+            PrismaQueryResult::Aggregate(a) => PrismaQueryResult::Aggregate(a),
         }
     }
 }
@@ -68,6 +93,11 @@ impl SinglePrismaQueryResult {
 }
 
 impl MultiPrismaQueryResult {
+    /// Nested to-one relations are loaded like lists, but hold at most one record per parent.
+    pub fn is_to_one(&self) -> bool {
+        self.parent_field.as_ref().map(|field| !field.is_list).unwrap_or(false)
+    }
+
     /// Filters implicitly selected fields in-place in the result nodes and field names.
     /// Traverses nested result tree.
     pub fn filter(mut self) -> Self {
@@ -108,126 +138,403 @@ impl MultiPrismaQueryResult {
 
 pub struct QueryExecutor {
     pub data_resolver: Arc<DataResolver + Send + Sync + 'static>,
+    pub write_executor: Arc<DatabaseMutactionExecutor + Send + Sync + 'static>,
 }
 
 impl QueryExecutor {
     // WIP
     pub fn execute(&self, queries: &[PrismaQuery]) -> CoreResult<Vec<PrismaQueryResult>> {
-        self.execute_internal(queries, vec![])
+        self.execute_internal(queries)
     }
 
-    #[allow(unused_variables)]
-    fn execute_internal(
-        &self,
-        queries: &[PrismaQuery],
-        parent_ids: Vec<GraphqlId>,
-    ) -> CoreResult<Vec<PrismaQueryResult>> {
+    /// Executes a top-level write and reads back the affected record with the selection of the query.
+    /// Batch writes return a single `count` field instead.
+    pub fn execute_write(&self, query: &WriteQuery) -> CoreResult<Vec<PrismaQueryResult>> {
+        let db_name = query.model.schema().db_name.clone();
+        let mutaction = DatabaseMutaction::TopLevel(query.mutaction.clone());
+
+        match query.mutaction {
+            // The relations and scalar lists of the record are gone after the delete, so they are
+            // read beforehand. The values of the record itself are the ones the delete read in its
+            // transaction, as they may have changed in the meantime.
+            TopLevelDatabaseMutaction::DeleteNode(ref delete) => {
+                let mut results = self.read_written(query, delete.where_.clone())?;
+                let result = Self::pop_result(self.write_executor.execute_with_nested(db_name, mutaction, None)?)?;
+
+                let deleted = match result.identifier {
+                    Identifier::Node(ref node) => node,
+                    _ => return Err(Self::unexpected_result("expected the deleted record")),
+                };
+
+                if let Some(PrismaQueryResult::Single(ref mut single)) = results.first_mut() {
+                    if let Some(ref mut record) = single.result {
+                        Self::take_values(record, deleted);
+                    }
+                }
+
+                Ok(results)
+            }
+            TopLevelDatabaseMutaction::UpdateNodes(_) | TopLevelDatabaseMutaction::DeleteNodes(_) => {
+                let result = Self::pop_result(self.write_executor.execute_with_nested(db_name, mutaction, None)?)?;
+
+                let count = match result.identifier {
+                    Identifier::Count(count) => count,
+                    _ => return Err(Self::unexpected_result("expected a count")),
+                };
+
+                let node = Node::new(vec![PrismaValue::Int(count as i32)]);
+
+                Ok(vec![PrismaQueryResult::Single(SinglePrismaQueryResult {
+                    name: query.name.clone(),
+                    result: Some(SingleNode::new(node, vec![String::from("count")])),
+                    nested: vec![],
+                    id: None,
+                    selected_fields: query.selected_fields.clone(),
+                })])
+            }
+            _ => {
+                let result = Self::pop_result(self.write_executor.execute_with_nested(db_name, mutaction, None)?)?;
+
+                let id = match result.identifier {
+                    Identifier::Id(ref id) => id.clone(),
+                    _ => return Err(Self::unexpected_result("expected the id of the written record")),
+                };
+
+                let selector = NodeSelector::new(query.model.fields().id(), id);
+
+                self.read_written(query, selector)
+            }
+        }
+    }
+
+    fn read_written(&self, query: &WriteQuery, selector: NodeSelector) -> CoreResult<Vec<PrismaQueryResult>> {
+        let read = PrismaQuery::RecordQuery(RecordQuery {
+            name: query.name.clone(),
+            selector,
+            selected_fields: query.selected_fields.clone(),
+            nested: query.nested.clone(),
+        });
+
+        self.execute(&[read])
+    }
+
+    fn pop_result(mut results: DatabaseMutactionResults) -> CoreResult<DatabaseMutactionResult> {
+        results
+            .pop()
+            .ok_or_else(|| Self::unexpected_result("no results returned"))
+    }
+
+    fn unexpected_result(message: &str) -> CoreError {
+        CoreError::MutactionResultError(message.to_string())
+    }
+
+    /// Replaces the values of the read record with the values of the same fields in `other`.
+    fn take_values(record: &mut SingleNode, other: &SingleNode) {
+        for (value, name) in record.node.values.iter_mut().zip(&record.field_names) {
+            if let Some(position) = other.field_names.iter().position(|other_name| other_name == name) {
+                *value = other.node.values[position].clone();
+            }
+        }
+    }
+
+    fn execute_internal(&self, queries: &[PrismaQuery]) -> CoreResult<Vec<PrismaQueryResult>> {
         let mut results = vec![];
         for query in queries {
             match query {
                 PrismaQuery::RecordQuery(query) => {
-                    let selected_fields = Self::inject_required_fields(query.selected_fields.clone());
+                    let model = query.selector.field.model();
+                    let selected_fields =
+                        Self::inject_required_fields(query.selected_fields.clone(), Arc::clone(&model));
 
-                    let result = self
+                    let mut result = self
                         .data_resolver
                         .get_node_by_where(&query.selector, &selected_fields)?;
 
-                    match result {
-                        Some(ref node) => {
-                            let model = Arc::clone(&query.selector.field.model());
+                    let (id, nested) = match result {
+                        Some(ref mut node) => {
                             let ids = vec![node.get_id_value(model)?.clone()];
 
-                            let nested = self.execute_internal(&query.nested, ids)?;
-                            let result = SinglePrismaQueryResult {
-                                name: query.name.clone(),
-                                result,
-                                nested,
-                                selected_fields,
-                            };
-                            results.push(PrismaQueryResult::Single(result));
+                            self.resolve_scalar_lists(
+                                std::slice::from_mut(&mut node.node),
+                                &mut node.field_names,
+                                &ids,
+                                &selected_fields,
+                            )?;
+
+                            let nested = self.execute_nested(&query.nested, &ids)?;
+                            (ids.into_iter().next(), nested)
                         }
-                        None => (),
-                    }
+                        None => (None, vec![]),
+                    };
+
+                    results.push(PrismaQueryResult::Single(SinglePrismaQueryResult {
+                        name: query.name.clone(),
+                        result,
+                        nested,
+                        id,
+                        selected_fields,
+                    }));
                 }
                 PrismaQuery::MultiRecordQuery(query) => {
-                    let selected_fields = Self::inject_required_fields(query.selected_fields.clone());
+                    let selected_fields =
+                        Self::inject_required_fields(query.selected_fields.clone(), Arc::clone(&query.model));
 
-                    let result =
+                    let mut result =
                         self.data_resolver
                             .get_nodes(Arc::clone(&query.model), query.args.clone(), &selected_fields)?;
 
-                    // FIXME: Rewrite to not panic and also in a more functional way!
+                    let exceeding = Self::paginate(&mut result.nodes, &query.args);
                     let ids = result.get_id_values(Arc::clone(&query.model))?;
-                    let nested = result.nodes.iter().fold(vec![], |mut vec, node| {
-                        vec.append(&mut self.execute_internal(&query.nested, ids.clone()).unwrap());
-                        vec
-                    });
+                    self.resolve_scalar_lists(&mut result.nodes, &mut result.field_names, &ids, &selected_fields)?;
+                    let nested = self.execute_nested(&query.nested, &ids)?;
+
+                    let connection = match query.connection {
+                        Some(ref selection) => Some(self.connection_result(
+                            selection,
+                            Arc::clone(&query.model),
+                            &query.args,
+                            None,
+                            &exceeding,
+                        )?),
+                        None => None,
+                    };
 
                     results.push(PrismaQueryResult::Multi(MultiPrismaQueryResult {
                         name: query.name.clone(),
                         result,
                         nested,
+                        ids,
+                        parent_field: None,
+                        connection,
                         selected_fields,
                     }));
                 }
-                PrismaQuery::RelatedRecordQuery(query) => {
-                    let selected_fields = Self::inject_required_fields(query.selected_fields.clone());
-
-                    let result = self.data_resolver.get_related_nodes(
-                        Arc::clone(&query.parent_field),
-                        &parent_ids,
+                PrismaQuery::AggregateQuery(query) => {
+                    let rows = self.data_resolver.aggregate(
+                        Arc::clone(&query.model),
                         query.args.clone(),
-                        &selected_fields,
+                        query.selection.clone(),
                     )?;
 
-                    // FIXME: Required fields need to return Errors, non-required can be ignored!
-                    if let Some(node) = result.into_single_node() {
-                        let ids = vec![node.get_id_value(query.parent_field.related_model())?.clone()];
-                        let nested = self.execute_internal(&query.nested, ids)?;
-                        let result = SinglePrismaQueryResult {
-                            name: query.name.clone(),
-                            result: Some(node),
-                            nested,
-                            selected_fields,
-                        };
-                        results.push(PrismaQueryResult::Single(result));
-                    }
+                    results.push(PrismaQueryResult::Aggregate(AggregateQueryResult {
+                        name: query.name.clone(),
+                        fields: query.fields.clone(),
+                        grouped: query.grouped,
+                        rows,
+                    }));
+                }
+                PrismaQuery::RelatedRecordQuery(_) | PrismaQuery::MultiRelatedRecordQuery(_) => {
+                    unreachable!("Related record queries are always nested")
                 }
-                PrismaQuery::MultiRelatedRecordQuery(query) => {
-                    let selected_fields = Self::inject_required_fields(query.selected_fields.clone());
+            }
+        }
 
-                    let result = self.data_resolver.get_related_nodes(
-                        Arc::clone(&query.parent_field),
-                        &parent_ids,
-                        query.args.clone(),
-                        &selected_fields,
-                    )?;
+        Ok(results)
+    }
 
-                    // FIXME: Rewrite to not panic and also in a more functional way!
-                    let ids = result.get_id_values(Arc::clone(&query.parent_field.related_model()))?;
-                    let nested = result.nodes.iter().fold(vec![], |mut vec, node| {
-                        vec.append(&mut self.execute_internal(&query.nested, ids.clone()).unwrap());
-                        vec
-                    });
+    /// Executes the nested queries of one level with a single query each, for all parents at once.
+    /// The related nodes of every result carry the id of their parent.
+    fn execute_nested(&self, queries: &[PrismaQuery], parent_ids: &[GraphqlId]) -> CoreResult<Vec<PrismaQueryResult>> {
+        let mut results = vec![];
 
-                    results.push(PrismaQueryResult::Multi(MultiPrismaQueryResult {
-                        name: query.name.clone(),
-                        result,
-                        nested,
-                        selected_fields,
-                    }));
+        for query in queries {
+            let (name, parent_field, args, selected_fields, nested_queries, connection) = match query {
+                PrismaQuery::RelatedRecordQuery(q) => {
+                    (&q.name, &q.parent_field, &q.args, &q.selected_fields, &q.nested, &None)
                 }
-            }
+                PrismaQuery::MultiRelatedRecordQuery(q) => (
+                    &q.name,
+                    &q.parent_field,
+                    &q.args,
+                    &q.selected_fields,
+                    &q.nested,
+                    &q.connection,
+                ),
+                _ => unreachable!("Nested queries are always related record queries"),
+            };
+
+            let selected_fields = Self::inject_required_fields(selected_fields.clone(), parent_field.related_model());
+            let mut result = if parent_ids.is_empty() {
+                ManyNodes {
+                    nodes: vec![],
+                    field_names: selected_fields.names(),
+                }
+            } else {
+                self.data_resolver.get_related_nodes(
+                    Arc::clone(parent_field),
+                    parent_ids,
+                    args.clone(),
+                    &selected_fields,
+                )?
+            };
+
+            let exceeding = Self::paginate(&mut result.nodes, args);
+            let ids = result.get_id_values(parent_field.related_model())?;
+            self.resolve_scalar_lists(&mut result.nodes, &mut result.field_names, &ids, &selected_fields)?;
+
+            let nested = self.execute_nested(nested_queries, &ids)?;
+
+            let connection = match connection {
+                Some(selection) => Some(self.connection_result(
+                    selection,
+                    parent_field.related_model(),
+                    args,
+                    Some((parent_field, parent_ids)),
+                    &exceeding,
+                )?),
+                None => None,
+            };
+
+            results.push(PrismaQueryResult::Multi(MultiPrismaQueryResult {
+                name: name.clone(),
+                result,
+                nested,
+                ids,
+                parent_field: Some(Arc::clone(parent_field)),
+                connection,
+                selected_fields,
+            }));
         }
 
         Ok(results)
     }
 
+    /// Reads the values of the selected scalar list fields, which live in their own tables,
+    /// and appends them to the nodes.
+    fn resolve_scalar_lists(
+        &self,
+        nodes: &mut [Node],
+        field_names: &mut Vec<String>,
+        ids: &[GraphqlId],
+        selected_fields: &SelectedFields,
+    ) -> CoreResult<()> {
+        let list_fields = selected_fields.scalar_lists();
+
+        if list_fields.is_empty() || nodes.is_empty() {
+            return Ok(());
+        }
+
+        // Related nodes don't hold values for the relation columns at the end of the field names.
+        field_names.truncate(selected_fields.type_identifiers().len());
+
+        for field in list_fields {
+            let values: HashMap<GraphqlId, Vec<PrismaValue>> = self
+                .data_resolver
+                .get_scalar_list_values_by_node_ids(Arc::clone(&field), ids.to_vec())?
+                .into_iter()
+                .map(|list| (list.node_id, list.values))
+                .collect();
+
+            field_names.push(field.name.clone());
+
+            // The same record may be related to several parents, so the values are kept for every one.
+            for (node, id) in nodes.iter_mut().zip(ids) {
+                node.values
+                    .push(PrismaValue::List(values.get(id).cloned().unwrap_or_default()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Drops the additional record fetched per parent to see if there's more data,
+    /// and returns the parents (`None` for top-level queries) that have more records.
+    /// Pages requested with `last` are read in reverse and put back in order here.
+    fn paginate(nodes: &mut Vec<Node>, args: &QueryArguments) -> HashSet<Option<GraphqlId>> {
+        let mut exceeding = HashSet::new();
+
+        if let Some(limit) = args.last.or(args.first) {
+            let mut counts: HashMap<Option<GraphqlId>, u32> = HashMap::new();
+
+            nodes.retain(|node| {
+                let count = counts.entry(node.parent_id.clone()).or_insert(0);
+                *count += 1;
+
+                if *count > limit {
+                    exceeding.insert(node.parent_id.clone());
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+
+        if args.last.is_some() {
+            nodes.reverse();
+        }
+
+        exceeding
+    }
+
+    /// Builds the pages of a connection, one per parent for nested connections.
+    /// The total count is only queried if `aggregate` is selected, for all pages at once.
+    fn connection_result(
+        &self,
+        selection: &ConnectionSelection,
+        model: ModelRef,
+        args: &QueryArguments,
+        parent: Option<(&RelationFieldRef, &[GraphqlId])>,
+        exceeding: &HashSet<Option<GraphqlId>>,
+    ) -> CoreResult<ConnectionResult> {
+        let parent_ids: Vec<Option<GraphqlId>> = match parent {
+            Some((_, ids)) => ids.iter().cloned().map(Some).collect(),
+            None => vec![None],
+        };
+
+        let counts: Option<HashMap<Option<GraphqlId>, usize>> = if selection.selects_aggregate() {
+            let count_args = QueryArguments {
+                filter: args.filter.clone(),
+                distinct: args.distinct.clone(),
+                ..QueryArguments::default()
+            };
+
+            // Related records are counted per parent, in one query grouped by parent.
+            let counts = match parent {
+                Some((parent_field, ids)) => self
+                    .data_resolver
+                    .count_related_nodes(Arc::clone(parent_field), ids, count_args)?
+                    .into_iter()
+                    .map(|related| (Some(related.parent_id), related.count))
+                    .collect(),
+                None => {
+                    iter::once((None, self.data_resolver.count_by_model(Arc::clone(&model), count_args)?)).collect()
+                }
+            };
+
+            Some(counts)
+        } else {
+            None
+        };
+
+        let mut pages = HashMap::new();
+        for parent_id in parent_ids {
+            let count = counts
+                .as_ref()
+                .map(|counts| counts.get(&parent_id).cloned().unwrap_or(0));
+
+            let has_more = exceeding.contains(&parent_id);
+            pages.insert(
+                parent_id,
+                ConnectionPage {
+                    has_next_page: args.last.is_none() && has_more,
+                    has_previous_page: args.last.is_some() && has_more,
+                    count,
+                },
+            );
+        }
+
+        Ok(ConnectionResult {
+            selection: selection.clone(),
+            model_name: model.name.clone(),
+            pages,
+        })
+    }
+
     /// Injects fields required for querying, if they're not already in the selection set.
     /// Currently, required fields for every query are:
     /// - ID field
-    fn inject_required_fields(mut selected_fields: SelectedFields) -> SelectedFields {
-        let id_field = selected_fields.model().fields().id();
+    fn inject_required_fields(mut selected_fields: SelectedFields, model: ModelRef) -> SelectedFields {
+        let id_field = model.fields().id();
         if let None = selected_fields.scalar.iter().find(|f| f.field.name == id_field.name) {
             selected_fields.add_scalar(id_field.into(), true);
         };
@@ -0,0 +1,834 @@
+use super::*;
+use crate::{
+    aggregation::field_aggregation, aggregation_type_name, filters::FILTER_MODE, AGGREGATE_PREFIX, CONNECTION_SUFFIX,
+    FIELD_AGGREGATIONS, GROUP_BY_PREFIX, HAVING_SUFFIXES, RELEVANCE,
+};
+use connector::Aggregation;
+use inflector::Inflector;
+use prisma_models::{Field as ModelField, *};
+use std::{iter, sync::Arc};
+
+const BUILT_IN_SCALARS: &[&str] = &["ID", "String", "Int", "Float", "Boolean", "DateTime", "Json", "UUID"];
+const BATCH_PAYLOAD: &str = "BatchPayload";
+const PAGE_INFO: &str = "PageInfo";
+const SORT_ORDER: &str = "SortOrder";
+const QUERY_MODE: &str = "QueryMode";
+const JSON_FILTER: &str = "JsonFilter";
+const JSON_UPDATE_INPUT: &str = "JsonUpdateInput";
+const JSON_SET_PATH_INPUT: &str = "JsonSetPathInput";
+
+impl GraphQlSchema {
+    /// Builds the GraphQL schema exposed for the given data model.
+    pub fn build(data_model: &Schema) -> Self {
+        let mut builder = SchemaBuilder::default();
+
+        builder.push_root_types(data_model);
+
+        for model in data_model.models() {
+            builder.push_model_types(model);
+        }
+
+        for prisma_enum in data_model.enums.iter() {
+            builder.types.push(TypeDef::Enum {
+                name: prisma_enum.name.clone(),
+                values: prisma_enum.values.clone(),
+            });
+        }
+
+        builder.types.push(TypeDef::Object {
+            name: BATCH_PAYLOAD.into(),
+            fields: vec![FieldDef::new("count", vec![], TypeRef::named("Int").non_null())],
+        });
+
+        builder.types.push(TypeDef::Enum {
+            name: SORT_ORDER.into(),
+            values: vec!["asc".into(), "desc".into()],
+        });
+
+        builder.types.push(TypeDef::Enum {
+            name: QUERY_MODE.into(),
+            values: vec!["default".into(), "insensitive".into()],
+        });
+
+        builder.push_json_inputs();
+
+        builder.types.push(TypeDef::Object {
+            name: PAGE_INFO.into(),
+            fields: vec![
+                FieldDef::new("hasNextPage", vec![], TypeRef::named("Boolean").non_null()),
+                FieldDef::new("hasPreviousPage", vec![], TypeRef::named("Boolean").non_null()),
+                FieldDef::new("startCursor", vec![], TypeRef::named("String")),
+                FieldDef::new("endCursor", vec![], TypeRef::named("String")),
+            ],
+        });
+
+        for scalar in BUILT_IN_SCALARS {
+            builder.types.push(TypeDef::Scalar {
+                name: scalar.to_string(),
+            });
+        }
+
+        Self { types: builder.types }
+    }
+}
+
+#[derive(Default)]
+struct SchemaBuilder {
+    types: Vec<TypeDef>,
+}
+
+impl SchemaBuilder {
+    fn push_root_types(&mut self, data_model: &Schema) {
+        let mut queries = vec![];
+        let mut mutations = vec![];
+
+        for model in data_model.models() {
+            let name = &model.name;
+            let plural = model.name.to_plural();
+            let record = || TypeRef::named(name.as_str());
+            let batch = || TypeRef::named(BATCH_PAYLOAD).non_null();
+
+            queries.push(FieldDef::new(
+                model.name.to_camel_case().to_singular(),
+                vec![where_unique_arg(name)],
+                record(),
+            ));
+            queries.push(FieldDef::new(
+                model.name.to_camel_case().to_plural(),
+                many_args(name),
+                record().non_null().list().non_null(),
+            ));
+            queries.push(FieldDef::new(
+                format!("{}{}", model.name.to_camel_case().to_plural(), CONNECTION_SUFFIX),
+                many_args(name),
+                connection_type(name),
+            ));
+            queries.push(FieldDef::new(
+                format!("{}{}", AGGREGATE_PREFIX, name),
+                many_args(name),
+                TypeRef::named(format!("{}AggregateResult", name)).non_null(),
+            ));
+            queries.push(FieldDef::new(
+                format!("{}{}", GROUP_BY_PREFIX, name),
+                vec![
+                    InputValue::new(
+                        "by",
+                        TypeRef::named(format!("{}ScalarField", name))
+                            .non_null()
+                            .list()
+                            .non_null(),
+                    ),
+                    InputValue::new("where", input(name, "WhereInput")),
+                    InputValue::new("having", input(name, "HavingInput")),
+                ],
+                TypeRef::named(format!("{}GroupByResult", name))
+                    .non_null()
+                    .list()
+                    .non_null(),
+            ));
+
+            mutations.push(FieldDef::new(
+                format!("create{}", name),
+                vec![InputValue::new("data", input(name, "CreateInput").non_null())],
+                record().non_null(),
+            ));
+            mutations.push(FieldDef::new(
+                format!("update{}", name),
+                vec![
+                    InputValue::new("data", input(name, "UpdateInput").non_null()),
+                    where_unique_arg(name),
+                ],
+                record(),
+            ));
+            mutations.push(FieldDef::new(
+                format!("upsert{}", name),
+                vec![
+                    where_unique_arg(name),
+                    InputValue::new("create", input(name, "CreateInput").non_null()),
+                    InputValue::new("update", input(name, "UpdateInput").non_null()),
+                ],
+                record().non_null(),
+            ));
+            mutations.push(FieldDef::new(
+                format!("delete{}", name),
+                vec![where_unique_arg(name)],
+                record(),
+            ));
+            mutations.push(FieldDef::new(
+                format!("updateMany{}", plural),
+                vec![
+                    InputValue::new("data", input(name, "UpdateManyMutationInput").non_null()),
+                    InputValue::new("where", input(name, "WhereInput")),
+                ],
+                batch(),
+            ));
+            mutations.push(FieldDef::new(
+                format!("deleteMany{}", plural),
+                vec![InputValue::new("where", input(name, "WhereInput"))],
+                batch(),
+            ));
+        }
+
+        self.types.push(TypeDef::Object {
+            name: QUERY_TYPE.into(),
+            fields: queries,
+        });
+
+        self.types.push(TypeDef::Object {
+            name: MUTATION_TYPE.into(),
+            fields: mutations,
+        });
+    }
+
+    fn push_model_types(&mut self, model: &ModelRef) {
+        let name = &model.name;
+
+        self.push_object_type(model);
+        self.push_connection_types(model);
+        self.push_aggregate_types(model);
+        self.push_scalar_field_enum(model);
+        self.push_where_input(model);
+        self.push_where_unique_input(model);
+        self.push_order_by_input(model);
+        self.push_create_input(model);
+        self.push_update_input(model);
+        self.push_update_many_mutation_input(model);
+        self.push_nested_inputs(model);
+
+        self.push_input(
+            format!("{}UpdateWithWhereUniqueNestedInput", name),
+            vec![
+                where_unique_arg(name),
+                InputValue::new("data", input(name, "UpdateInput").non_null()),
+            ],
+        );
+
+        self.push_input(
+            format!("{}UpdateManyWithWhereNestedInput", name),
+            vec![
+                InputValue::new("where", input(name, "WhereInput")),
+                InputValue::new("data", input(name, "UpdateManyMutationInput").non_null()),
+            ],
+        );
+    }
+
+    fn push_object_type(&mut self, model: &ModelRef) {
+        let mut fields = vec![];
+
+        for field in visible_fields(model) {
+            match field {
+                ModelField::Scalar(sf) => fields.push(FieldDef::new(sf.name.as_str(), vec![], output_scalar_type(sf))),
+                ModelField::Relation(rf) => {
+                    let related = rf.related_model();
+                    let related_type = TypeRef::named(related.name.as_str());
+
+                    if rf.is_list {
+                        fields.push(FieldDef::new(
+                            rf.name.as_str(),
+                            many_args(&related.name),
+                            related_type.non_null().list(),
+                        ));
+                        fields.push(FieldDef::new(
+                            format!("{}{}", rf.name, CONNECTION_SUFFIX),
+                            many_args(&related.name),
+                            connection_type(&related.name),
+                        ));
+                    } else if rf.is_required {
+                        fields.push(FieldDef::new(rf.name.as_str(), vec![], related_type.non_null()));
+                    } else {
+                        fields.push(FieldDef::new(rf.name.as_str(), vec![], related_type));
+                    }
+                }
+            }
+        }
+
+        self.types.push(TypeDef::Object {
+            name: model.name.clone(),
+            fields,
+        });
+    }
+
+    /// The Relay connection of the model, with its edges and the aggregate.
+    fn push_connection_types(&mut self, model: &ModelRef) {
+        let name = &model.name;
+
+        self.types.push(TypeDef::Object {
+            name: format!("{}{}", name, CONNECTION_SUFFIX),
+            fields: vec![
+                FieldDef::new("pageInfo", vec![], TypeRef::named(PAGE_INFO).non_null()),
+                FieldDef::new(
+                    "edges",
+                    vec![],
+                    TypeRef::named(format!("{}Edge", name)).list().non_null(),
+                ),
+                FieldDef::new(
+                    "aggregate",
+                    vec![],
+                    TypeRef::named(format!("Aggregate{}", name)).non_null(),
+                ),
+            ],
+        });
+
+        self.types.push(TypeDef::Object {
+            name: format!("{}Edge", name),
+            fields: vec![
+                FieldDef::new("node", vec![], TypeRef::named(name.as_str()).non_null()),
+                FieldDef::new("cursor", vec![], TypeRef::named("String").non_null()),
+            ],
+        });
+
+        self.types.push(TypeDef::Object {
+            name: format!("Aggregate{}", name),
+            fields: vec![FieldDef::new("count", vec![], TypeRef::named("Int").non_null())],
+        });
+    }
+
+    /// The results of `aggregate` and `groupBy` and the `having` conditions on groups.
+    /// Aggregations of fields are only exposed if the model has fields supporting them.
+    fn push_aggregate_types(&mut self, model: &ModelRef) {
+        let name = &model.name;
+        let scalar_fields: Vec<ScalarFieldRef> = model
+            .fields()
+            .scalar_non_list()
+            .into_iter()
+            .filter(|sf| !sf.is_hidden)
+            .collect();
+
+        let mut aggregations = vec![FieldDef::new("_count", vec![], TypeRef::named("Int").non_null())];
+        let mut having: Vec<InputValue> = iter::once(&"")
+            .chain(HAVING_SUFFIXES)
+            .map(|suffix| InputValue::new(format!("_count{}", suffix), TypeRef::named("Int")))
+            .collect();
+
+        for aggregation_name in FIELD_AGGREGATIONS {
+            let aggregated: Vec<Aggregation> = scalar_fields
+                .iter()
+                .map(|sf| field_aggregation(aggregation_name, Arc::clone(sf)))
+                .filter(Aggregation::is_supported)
+                .collect();
+
+            if aggregated.is_empty() {
+                continue;
+            }
+
+            let mut fields = vec![];
+            for aggregation in aggregated {
+                let field_name = &aggregation.field().unwrap().name;
+                let typ = || TypeRef::named(scalar_type_name(aggregation.type_identifier()));
+
+                fields.push(FieldDef::new(field_name.as_str(), vec![], typ()));
+
+                for suffix in iter::once(&"").chain(HAVING_SUFFIXES) {
+                    having.push(InputValue::new(
+                        format!("{}{}{}", field_name, aggregation_name, suffix),
+                        typ(),
+                    ));
+                }
+            }
+
+            let type_name = aggregation_type_name(name, aggregation_name);
+            aggregations.push(FieldDef::new(
+                *aggregation_name,
+                vec![],
+                TypeRef::named(type_name.as_str()),
+            ));
+
+            self.types.push(TypeDef::Object {
+                name: type_name,
+                fields,
+            });
+        }
+
+        self.types.push(TypeDef::Object {
+            name: format!("{}AggregateResult", name),
+            fields: aggregations.clone(),
+        });
+
+        // Grouped fields are null in groups of records without a value.
+        let grouped_fields = scalar_fields
+            .iter()
+            .map(|sf| FieldDef::new(sf.name.as_str(), vec![], scalar_type(sf)));
+
+        self.types.push(TypeDef::Object {
+            name: format!("{}GroupByResult", name),
+            fields: grouped_fields.chain(aggregations).collect(),
+        });
+
+        self.push_input(format!("{}HavingInput", name), having);
+    }
+
+    /// The scalar fields of the model, for `distinct` and `by`.
+    fn push_scalar_field_enum(&mut self, model: &ModelRef) {
+        let values = model
+            .fields()
+            .scalar_non_list()
+            .into_iter()
+            .filter(|sf| !sf.is_hidden)
+            .map(|sf| sf.name.clone())
+            .collect();
+
+        self.types.push(TypeDef::Enum {
+            name: format!("{}ScalarField", model.name),
+            values,
+        });
+    }
+
+    fn push_where_input(&mut self, model: &ModelRef) {
+        let where_input = || input(&model.name, "WhereInput");
+        let mut fields = vec![
+            InputValue::new("AND", where_input().non_null().list()),
+            InputValue::new("OR", where_input().non_null().list()),
+            InputValue::new("NOT", where_input().non_null().list()),
+        ];
+
+        let has_strings = visible_fields(model).any(|field| match field {
+            ModelField::Scalar(sf) => !sf.is_list && sf.type_identifier == TypeIdentifier::String,
+            _ => false,
+        });
+
+        // Makes the string filters of the object ignore case.
+        if has_strings {
+            fields.push(InputValue::new(FILTER_MODE, TypeRef::named(QUERY_MODE)));
+        }
+
+        for field in visible_fields(model) {
+            match field {
+                ModelField::Scalar(sf) if !sf.is_list => {
+                    let typ = || scalar_type(sf);
+                    let name = &sf.name;
+
+                    fields.push(InputValue::new(name.as_str(), typ()));
+                    fields.push(InputValue::new(format!("{}_not", name), typ()));
+                    fields.push(InputValue::new(format!("{}_in", name), typ().non_null().list()));
+                    fields.push(InputValue::new(format!("{}_not_in", name), typ().non_null().list()));
+
+                    if is_comparable(sf.type_identifier) {
+                        for suffix in &["_lt", "_lte", "_gt", "_gte"] {
+                            fields.push(InputValue::new(format!("{}{}", name, suffix), typ()));
+                        }
+                    }
+
+                    if is_textual(sf.type_identifier) {
+                        for suffix in &[
+                            "_contains",
+                            "_not_contains",
+                            "_starts_with",
+                            "_not_starts_with",
+                            "_ends_with",
+                            "_not_ends_with",
+                        ] {
+                            fields.push(InputValue::new(format!("{}{}", name, suffix), typ()));
+                        }
+                    }
+
+                    if sf.type_identifier == TypeIdentifier::String {
+                        fields.push(InputValue::new(format!("{}_search", name), typ()));
+                    }
+
+                    if sf.type_identifier == TypeIdentifier::Json {
+                        fields.push(InputValue::new(format!("{}_json", name), TypeRef::named(JSON_FILTER)));
+                    }
+                }
+                ModelField::Scalar(sf) => {
+                    let typ = || scalar_type(sf);
+                    let name = &sf.name;
+
+                    fields.push(InputValue::new(format!("{}_contains", name), typ()));
+                    fields.push(InputValue::new(
+                        format!("{}_contains_every", name),
+                        typ().non_null().list(),
+                    ));
+                    fields.push(InputValue::new(
+                        format!("{}_contains_some", name),
+                        typ().non_null().list(),
+                    ));
+                }
+                ModelField::Relation(rf) => {
+                    let related = input(&rf.related_model().name, "WhereInput");
+
+                    if rf.is_list {
+                        for suffix in &["_every", "_some", "_none"] {
+                            fields.push(InputValue::new(format!("{}{}", rf.name, suffix), related.clone()));
+                        }
+                    } else {
+                        fields.push(InputValue::new(rf.name.as_str(), related));
+                    }
+                }
+            }
+        }
+
+        self.push_input(format!("{}WhereInput", model.name), fields);
+    }
+
+    fn push_where_unique_input(&mut self, model: &ModelRef) {
+        let fields = model
+            .fields()
+            .scalar_non_list()
+            .into_iter()
+            .filter(|sf| !sf.is_hidden && (sf.is_unique || sf.is_id()))
+            .map(|sf| InputValue::new(sf.name.as_str(), scalar_type(&sf)))
+            .collect();
+
+        self.push_input(format!("{}WhereUniqueInput", model.name), fields);
+    }
+
+    /// A sort key of `orderBy`, which takes a list of them in order of precedence.
+    /// Fields of to-one related models are nested in their relation field.
+    fn push_order_by_input(&mut self, model: &ModelRef) {
+        let mut fields: Vec<InputValue> = visible_fields(model)
+            .filter_map(|field| match field {
+                ModelField::Scalar(sf) if !sf.is_list => {
+                    Some(InputValue::new(sf.name.as_str(), TypeRef::named(SORT_ORDER)))
+                }
+                ModelField::Relation(rf) if !rf.is_list => Some(InputValue::new(
+                    rf.name.as_str(),
+                    input(&rf.related_model().name, "OrderByInput"),
+                )),
+                _ => None,
+            })
+            .collect();
+
+        // Sorting by the relevance of the records to a full-text search on a string field.
+        if !model.full_text_search_table().fields().is_empty() {
+            fields.push(InputValue::new(RELEVANCE, input(&model.name, "RelevanceInput")));
+
+            self.push_input(
+                format!("{}RelevanceInput", model.name),
+                vec![
+                    InputValue::new("field", input(&model.name, "ScalarField").non_null()),
+                    InputValue::new("search", TypeRef::named("String").non_null()),
+                    InputValue::new("sort", TypeRef::named(SORT_ORDER).non_null()),
+                ],
+            );
+        }
+
+        self.push_input(format!("{}OrderByInput", model.name), fields);
+    }
+
+    fn push_create_input(&mut self, model: &ModelRef) {
+        let fields = self.create_fields(model, None);
+        self.push_input(format!("{}CreateInput", model.name), fields);
+    }
+
+    /// The fields of a create input, leaving out the given relation field.
+    fn create_fields(&mut self, model: &ModelRef, without: Option<&RelationField>) -> Vec<InputValue> {
+        let mut fields = vec![];
+
+        for field in visible_fields(model) {
+            match field {
+                // Ids are generated by the engine.
+                ModelField::Scalar(sf) if sf.is_id() => (),
+                ModelField::Scalar(sf) if sf.is_list => {
+                    let list_input = format!("{}Create{}Input", model.name, sf.name);
+
+                    // The inputs leaving out a relation field share the list inputs of the create input.
+                    if without.is_none() {
+                        self.push_scalar_list_input(list_input.clone(), sf);
+                    }
+
+                    fields.push(InputValue::new(sf.name.as_str(), TypeRef::named(list_input)));
+                }
+                ModelField::Scalar(sf) => {
+                    let typ = scalar_type(sf);
+                    let typ = if sf.is_required && !sf.is_auto_generated {
+                        typ.non_null()
+                    } else {
+                        typ
+                    };
+
+                    fields.push(InputValue::new(sf.name.as_str(), typ));
+                }
+                ModelField::Relation(rf) if without.map(|w| w.name == rf.name).unwrap_or(false) => (),
+                ModelField::Relation(rf) => {
+                    let typ = if rf.is_list {
+                        nested_input(rf, "CreateMany")
+                    } else if rf.is_required {
+                        nested_input(rf, "CreateOne").non_null()
+                    } else {
+                        nested_input(rf, "CreateOne")
+                    };
+
+                    fields.push(InputValue::new(rf.name.as_str(), typ));
+                }
+            }
+        }
+
+        fields
+    }
+
+    fn push_update_input(&mut self, model: &ModelRef) {
+        let mut fields = vec![];
+
+        for field in visible_fields(model) {
+            match field {
+                ModelField::Scalar(sf) if sf.is_id() => (),
+                ModelField::Scalar(sf) if sf.is_list => {
+                    let list_input = format!("{}Update{}Input", model.name, sf.name);
+                    self.push_scalar_list_input(list_input.clone(), sf);
+                    fields.push(InputValue::new(sf.name.as_str(), TypeRef::named(list_input)));
+                }
+                ModelField::Scalar(sf) => {
+                    fields.push(InputValue::new(sf.name.as_str(), scalar_type(sf)));
+
+                    if sf.type_identifier == TypeIdentifier::Json {
+                        fields.push(InputValue::new(
+                            format!("{}_json", sf.name),
+                            TypeRef::named(JSON_UPDATE_INPUT),
+                        ));
+                    }
+                }
+                ModelField::Relation(rf) => {
+                    let typ = if rf.is_list {
+                        nested_input(rf, "UpdateMany")
+                    } else {
+                        nested_input(rf, "UpdateOne")
+                    };
+
+                    fields.push(InputValue::new(rf.name.as_str(), typ));
+                }
+            }
+        }
+
+        self.push_input(format!("{}UpdateInput", model.name), fields);
+    }
+
+    /// The inputs of nested mutations through the relation fields of the model. Records created
+    /// through a relation field are connected to the parent, so the create inputs leave out the
+    /// other side of the relation, like `SiteCreateWithoutUserInput` for the sites of a user.
+    fn push_nested_inputs(&mut self, model: &ModelRef) {
+        for field in visible_fields(model) {
+            let rf = match field {
+                ModelField::Relation(rf) => rf,
+                ModelField::Scalar(_) => continue,
+            };
+
+            let related = rf.related_model();
+            let name = &related.name;
+            let create = || nested_input(rf, "Create");
+
+            let create_fields = self.create_fields(&related, Some(rf.related_field().as_ref()));
+            self.push_input(nested_input_name(rf, "Create"), create_fields);
+
+            if rf.is_list {
+                self.push_input(
+                    nested_input_name(rf, "CreateMany"),
+                    vec![
+                        InputValue::new("create", create().non_null().list()),
+                        InputValue::new("connect", input(name, "WhereUniqueInput").non_null().list()),
+                    ],
+                );
+
+                self.push_input(
+                    nested_input_name(rf, "UpdateMany"),
+                    vec![
+                        InputValue::new("create", create().non_null().list()),
+                        InputValue::new("connect", input(name, "WhereUniqueInput").non_null().list()),
+                        InputValue::new("disconnect", input(name, "WhereUniqueInput").non_null().list()),
+                        InputValue::new("set", input(name, "WhereUniqueInput").non_null().list()),
+                        InputValue::new(
+                            "update",
+                            input(name, "UpdateWithWhereUniqueNestedInput").non_null().list(),
+                        ),
+                        InputValue::new("upsert", nested_input(rf, "UpsertWithWhereUnique").non_null().list()),
+                        InputValue::new("delete", input(name, "WhereUniqueInput").non_null().list()),
+                        InputValue::new(
+                            "updateMany",
+                            input(name, "UpdateManyWithWhereNestedInput").non_null().list(),
+                        ),
+                        InputValue::new("deleteMany", input(name, "WhereInput").non_null().list()),
+                    ],
+                );
+
+                self.push_input(
+                    nested_input_name(rf, "UpsertWithWhereUnique"),
+                    vec![
+                        where_unique_arg(name),
+                        InputValue::new("create", create().non_null()),
+                        InputValue::new("update", input(name, "UpdateInput").non_null()),
+                    ],
+                );
+            } else {
+                self.push_input(
+                    nested_input_name(rf, "CreateOne"),
+                    vec![
+                        InputValue::new("create", create()),
+                        InputValue::new("connect", input(name, "WhereUniqueInput")),
+                    ],
+                );
+
+                self.push_input(
+                    nested_input_name(rf, "UpdateOne"),
+                    vec![
+                        InputValue::new("create", create()),
+                        InputValue::new("connect", input(name, "WhereUniqueInput")),
+                        InputValue::new("disconnect", TypeRef::named("Boolean")),
+                        InputValue::new("update", input(name, "UpdateInput")),
+                        InputValue::new("upsert", nested_input(rf, "Upsert")),
+                        InputValue::new("delete", TypeRef::named("Boolean")),
+                    ],
+                );
+
+                self.push_input(
+                    nested_input_name(rf, "Upsert"),
+                    vec![
+                        InputValue::new("create", create().non_null()),
+                        InputValue::new("update", input(name, "UpdateInput").non_null()),
+                    ],
+                );
+            }
+        }
+    }
+
+    /// Batch updates only take scalar fields.
+    fn push_update_many_mutation_input(&mut self, model: &ModelRef) {
+        let fields = model
+            .fields()
+            .scalar_non_list()
+            .into_iter()
+            .filter(|sf| !sf.is_hidden && !sf.is_id())
+            .map(|sf| InputValue::new(sf.name.as_str(), scalar_type(&sf)))
+            .collect();
+
+        self.push_input(format!("{}UpdateManyMutationInput", model.name), fields);
+    }
+
+    /// Filtering and partially updating JSON fields at a path of object keys and array indices.
+    fn push_json_inputs(&mut self) {
+        let path = || TypeRef::named("String").non_null().list();
+
+        self.push_input(
+            JSON_FILTER.into(),
+            vec![
+                InputValue::new("path", path()),
+                InputValue::new("equals", TypeRef::named("Json")),
+                InputValue::new("array_contains", TypeRef::named("Json")),
+                InputValue::new("string_contains", TypeRef::named("String")),
+            ],
+        );
+
+        self.push_input(
+            JSON_UPDATE_INPUT.into(),
+            vec![
+                InputValue::new("set_path", TypeRef::named(JSON_SET_PATH_INPUT)),
+                InputValue::new("merge", TypeRef::named("Json")),
+            ],
+        );
+
+        self.push_input(
+            JSON_SET_PATH_INPUT.into(),
+            vec![
+                InputValue::new("path", path().non_null()),
+                InputValue::new("value", TypeRef::named("Json").non_null()),
+            ],
+        );
+    }
+
+    fn push_scalar_list_input(&mut self, name: String, field: &ScalarField) {
+        let typ = scalar_type(field);
+        self.push_input(name, vec![InputValue::new("set", typ.non_null().list())]);
+    }
+
+    fn push_input(&mut self, name: String, fields: Vec<InputValue>) {
+        self.types.push(TypeDef::InputObject { name, fields });
+    }
+}
+
+fn visible_fields(model: &ModelRef) -> impl Iterator<Item = &ModelField> {
+    model.fields().all.iter().filter(|field| match field {
+        ModelField::Scalar(sf) => !sf.is_hidden,
+        ModelField::Relation(rf) => !rf.is_hidden,
+    })
+}
+
+fn input(model_name: &str, suffix: &str) -> TypeRef {
+    TypeRef::named(format!("{}{}", model_name, suffix))
+}
+
+/// An input of nested mutations through a relation field, like `SiteCreateManyWithoutUserInput`
+/// for the sites of a user.
+fn nested_input(field: &RelationField, kind: &str) -> TypeRef {
+    TypeRef::named(nested_input_name(field, kind))
+}
+
+fn nested_input_name(field: &RelationField, kind: &str) -> String {
+    format!(
+        "{}{}Without{}Input",
+        field.related_model().name,
+        kind,
+        field.related_field().name.to_pascal_case()
+    )
+}
+
+fn connection_type(model_name: &str) -> TypeRef {
+    TypeRef::named(format!("{}{}", model_name, CONNECTION_SUFFIX)).non_null()
+}
+
+fn where_unique_arg(model_name: &str) -> InputValue {
+    InputValue::new("where", input(model_name, "WhereUniqueInput").non_null())
+}
+
+/// The arguments of fields returning a list of records.
+fn many_args(model_name: &str) -> Vec<InputValue> {
+    vec![
+        InputValue::new("where", input(model_name, "WhereInput")),
+        InputValue::new("orderBy", input(model_name, "OrderByInput").non_null().list()),
+        InputValue::new("distinct", input(model_name, "ScalarField").non_null().list()),
+        InputValue::new("skip", TypeRef::named("Int")),
+        InputValue::new("after", TypeRef::named("ID")),
+        InputValue::new("before", TypeRef::named("ID")),
+        InputValue::new("first", TypeRef::named("Int")),
+        InputValue::new("last", TypeRef::named("Int")),
+    ]
+}
+
+fn output_scalar_type(field: &ScalarField) -> TypeRef {
+    let typ = scalar_type(field);
+
+    match (field.is_list, field.is_required) {
+        (true, _) => typ.non_null().list().non_null(),
+        (false, true) => typ.non_null(),
+        (false, false) => typ,
+    }
+}
+
+/// Enum fields are typed by their enum.
+fn scalar_type(field: &ScalarField) -> TypeRef {
+    match field.internal_enum {
+        Some(ref prisma_enum) => TypeRef::named(prisma_enum.name.as_str()),
+        None => TypeRef::named(scalar_type_name(field.type_identifier)),
+    }
+}
+
+/// Enum fields without an enum in the data model are exposed as strings.
+fn scalar_type_name(type_identifier: TypeIdentifier) -> &'static str {
+    match type_identifier {
+        TypeIdentifier::String | TypeIdentifier::Enum => "String",
+        TypeIdentifier::Float => "Float",
+        TypeIdentifier::Boolean => "Boolean",
+        TypeIdentifier::Json => "Json",
+        TypeIdentifier::DateTime => "DateTime",
+        TypeIdentifier::GraphQLID => "ID",
+        TypeIdentifier::UUID => "UUID",
+        TypeIdentifier::Int => "Int",
+        TypeIdentifier::Relation => unreachable!("Relation fields are not scalars"),
+    }
+}
+
+fn is_comparable(type_identifier: TypeIdentifier) -> bool {
+    match type_identifier {
+        TypeIdentifier::String
+        | TypeIdentifier::Float
+        | TypeIdentifier::Int
+        | TypeIdentifier::DateTime
+        | TypeIdentifier::GraphQLID
+        | TypeIdentifier::UUID => true,
+        _ => false,
+    }
+}
+
+fn is_textual(type_identifier: TypeIdentifier) -> bool {
+    match type_identifier {
+        TypeIdentifier::String | TypeIdentifier::GraphQLID | TypeIdentifier::UUID => true,
+        _ => false,
+    }
+}
@@ -0,0 +1,183 @@
+//! Resolution of the introspection fields `__schema`, `__type` and `__typename`
+
+use super::*;
+use crate::{CoreError, CoreResult};
+use graphql_parser::query::{Field, Selection, SelectionSet, Value};
+use serde_json::{Map, Value as JsonValue};
+
+type JsonMap = Map<String, JsonValue>;
+
+/// An introspection field selected on a root type.
+#[derive(Debug, Clone)]
+pub struct IntrospectionQuery {
+    pub root_type: String,
+    pub field: Field,
+}
+
+impl IntrospectionQuery {
+    /// Introspection fields are the ones reserved by the GraphQL spec, prefixed with `__`.
+    pub fn is_introspection(field: &Field) -> bool {
+        field.name.starts_with("__")
+    }
+
+    /// The response key of the field.
+    pub fn name(&self) -> &str {
+        self.field.alias.as_ref().unwrap_or(&self.field.name)
+    }
+
+    pub fn resolve(&self, schema: &GraphQlSchema) -> CoreResult<JsonValue> {
+        let resolver = Resolver { schema };
+
+        match self.field.name.as_str() {
+            "__typename" => Ok(self.root_type.clone().into()),
+            "__schema" => resolver.schema(&self.field.selection_set),
+            "__type" => match self.field.arguments.iter().find(|(name, _)| name == "name") {
+                Some((_, Value::String(name))) => match schema.find_type(name) {
+                    Some(typ) => resolver.type_def(typ, &self.field.selection_set),
+                    None => Ok(JsonValue::Null),
+                },
+                _ => Err(CoreError::QueryValidationError(
+                    "Field `__type` requires a `name` argument of type String".into(),
+                )),
+            },
+            name => Err(CoreError::QueryValidationError(format!(
+                "Unknown introspection field `{}`",
+                name
+            ))),
+        }
+    }
+}
+
+struct Resolver<'a> {
+    schema: &'a GraphQlSchema,
+}
+
+impl<'a> Resolver<'a> {
+    fn schema(&self, selection: &SelectionSet) -> CoreResult<JsonValue> {
+        self.object("__Schema", selection, |field| match field.name.as_str() {
+            "types" => self.list(self.schema.types.iter(), |typ| self.type_def(typ, &field.selection_set)),
+            "queryType" => self.type_def(self.schema.query_type(), &field.selection_set),
+            "mutationType" => self.type_def(self.schema.mutation_type(), &field.selection_set),
+            "subscriptionType" => Ok(JsonValue::Null),
+            "directives" => Ok(JsonValue::Array(vec![])),
+            _ => Err(unknown_field("__Schema", field)),
+        })
+    }
+
+    /// Resolves a (possibly wrapped) type reference.
+    fn type_ref(&self, typ: &TypeRef, selection: &SelectionSet) -> CoreResult<JsonValue> {
+        let (kind, inner) = match typ {
+            TypeRef::Named(name) => {
+                return match self.schema.find_type(name) {
+                    Some(typ) => self.type_def(typ, selection),
+                    None => Err(CoreError::QueryValidationError(format!("Unknown type `{}`", name))),
+                };
+            }
+            TypeRef::List(inner) => ("LIST", inner),
+            TypeRef::NonNull(inner) => ("NON_NULL", inner),
+        };
+
+        self.object("__Type", selection, |field| match field.name.as_str() {
+            "kind" => Ok(kind.into()),
+            "ofType" => self.type_ref(inner, &field.selection_set),
+            "name" | "description" | "fields" | "interfaces" | "possibleTypes" | "enumValues" | "inputFields" => {
+                Ok(JsonValue::Null)
+            }
+            _ => Err(unknown_field("__Type", field)),
+        })
+    }
+
+    fn type_def(&self, typ: &TypeDef, selection: &SelectionSet) -> CoreResult<JsonValue> {
+        let kind = match typ {
+            TypeDef::Scalar { .. } => "SCALAR",
+            TypeDef::Object { .. } => "OBJECT",
+            TypeDef::InputObject { .. } => "INPUT_OBJECT",
+            TypeDef::Enum { .. } => "ENUM",
+        };
+
+        self.object("__Type", selection, |field| match (field.name.as_str(), typ) {
+            ("kind", _) => Ok(kind.into()),
+            ("name", _) => Ok(typ.name().into()),
+            ("description", _) | ("ofType", _) => Ok(JsonValue::Null),
+            ("fields", TypeDef::Object { fields, .. }) => {
+                self.list(fields.iter(), |f| self.field_def(f, &field.selection_set))
+            }
+            ("interfaces", TypeDef::Object { .. }) => Ok(JsonValue::Array(vec![])),
+            ("inputFields", TypeDef::InputObject { fields, .. }) => {
+                self.list(fields.iter(), |f| self.input_value(f, &field.selection_set))
+            }
+            ("enumValues", TypeDef::Enum { values, .. }) => {
+                self.list(values.iter(), |v| self.enum_value(v, &field.selection_set))
+            }
+            ("fields", _) | ("interfaces", _) | ("possibleTypes", _) | ("inputFields", _) | ("enumValues", _) => {
+                Ok(JsonValue::Null)
+            }
+            _ => Err(unknown_field("__Type", field)),
+        })
+    }
+
+    fn field_def(&self, def: &FieldDef, selection: &SelectionSet) -> CoreResult<JsonValue> {
+        self.object("__Field", selection, |field| match field.name.as_str() {
+            "name" => Ok(def.name.as_str().into()),
+            "description" | "deprecationReason" => Ok(JsonValue::Null),
+            "isDeprecated" => Ok(false.into()),
+            "args" => self.list(def.args.iter(), |arg| self.input_value(arg, &field.selection_set)),
+            "type" => self.type_ref(&def.typ, &field.selection_set),
+            _ => Err(unknown_field("__Field", field)),
+        })
+    }
+
+    fn input_value(&self, value: &InputValue, selection: &SelectionSet) -> CoreResult<JsonValue> {
+        self.object("__InputValue", selection, |field| match field.name.as_str() {
+            "name" => Ok(value.name.as_str().into()),
+            "description" | "defaultValue" => Ok(JsonValue::Null),
+            "type" => self.type_ref(&value.typ, &field.selection_set),
+            _ => Err(unknown_field("__InputValue", field)),
+        })
+    }
+
+    fn enum_value(&self, value: &str, selection: &SelectionSet) -> CoreResult<JsonValue> {
+        self.object("__EnumValue", selection, |field| match field.name.as_str() {
+            "name" => Ok(value.into()),
+            "description" | "deprecationReason" => Ok(JsonValue::Null),
+            "isDeprecated" => Ok(false.into()),
+            _ => Err(unknown_field("__EnumValue", field)),
+        })
+    }
+
+    /// Resolves every field of the (normalized) selection set, keyed by alias.
+    fn object<F>(&self, type_name: &str, selection: &SelectionSet, resolve: F) -> CoreResult<JsonValue>
+    where
+        F: Fn(&Field) -> CoreResult<JsonValue>,
+    {
+        let mut map = JsonMap::new();
+
+        for item in selection.items.iter() {
+            if let Selection::Field(field) = item {
+                let key = field.alias.as_ref().unwrap_or(&field.name).clone();
+                let value = match field.name.as_str() {
+                    "__typename" => type_name.into(),
+                    _ => resolve(field)?,
+                };
+
+                map.insert(key, value);
+            }
+        }
+
+        Ok(JsonValue::Object(map))
+    }
+
+    fn list<'b, T: 'b, I, F>(&self, items: I, resolve: F) -> CoreResult<JsonValue>
+    where
+        I: Iterator<Item = &'b T>,
+        F: Fn(&'b T) -> CoreResult<JsonValue>,
+    {
+        Ok(JsonValue::Array(
+            items.map(resolve).collect::<CoreResult<Vec<JsonValue>>>()?,
+        ))
+    }
+}
+
+fn unknown_field(type_name: &str, field: &Field) -> CoreError {
+    CoreError::QueryValidationError(format!("Unknown field `{}` on type {}", field.name, type_name))
+}
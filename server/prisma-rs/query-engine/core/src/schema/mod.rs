@@ -0,0 +1,154 @@
+//! GraphQL schema generated from the Prisma data model
+//!
+//! The schema mirrors what the engine understands: root query and mutation fields per model,
+//! the `where`, `data` and nested write input types, and enums. It's the basis for the
+//! SDL served over HTTP, for introspection and for validating incoming documents.
+
+mod builder;
+mod introspection;
+mod sdl;
+mod validation;
+
+pub use introspection::*;
+pub use validation::*;
+
+use std::fmt;
+
+pub const QUERY_TYPE: &str = "Query";
+pub const MUTATION_TYPE: &str = "Mutation";
+
+/// A reference to a type, possibly wrapped in list and non-null modifiers.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeRef {
+    Named(String),
+    List(Box<TypeRef>),
+    NonNull(Box<TypeRef>),
+}
+
+impl TypeRef {
+    pub fn named<S: Into<String>>(name: S) -> Self {
+        TypeRef::Named(name.into())
+    }
+
+    pub fn non_null(self) -> Self {
+        TypeRef::NonNull(Box::new(self))
+    }
+
+    pub fn list(self) -> Self {
+        TypeRef::List(Box::new(self))
+    }
+
+    /// The name of the innermost named type.
+    pub fn name(&self) -> &str {
+        match self {
+            TypeRef::Named(name) => name,
+            TypeRef::List(inner) | TypeRef::NonNull(inner) => inner.name(),
+        }
+    }
+
+    pub fn is_non_null(&self) -> bool {
+        match self {
+            TypeRef::NonNull(_) => true,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for TypeRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TypeRef::Named(name) => write!(f, "{}", name),
+            TypeRef::List(inner) => write!(f, "[{}]", inner),
+            TypeRef::NonNull(inner) => write!(f, "{}!", inner),
+        }
+    }
+}
+
+/// An argument or a field of an input object.
+#[derive(Debug, Clone)]
+pub struct InputValue {
+    pub name: String,
+    pub typ: TypeRef,
+}
+
+impl InputValue {
+    pub fn new<S: Into<String>>(name: S, typ: TypeRef) -> Self {
+        Self { name: name.into(), typ }
+    }
+}
+
+/// A field of an object type.
+#[derive(Debug, Clone)]
+pub struct FieldDef {
+    pub name: String,
+    pub args: Vec<InputValue>,
+    pub typ: TypeRef,
+}
+
+impl FieldDef {
+    pub fn new<S: Into<String>>(name: S, args: Vec<InputValue>, typ: TypeRef) -> Self {
+        Self {
+            name: name.into(),
+            args,
+            typ,
+        }
+    }
+
+    pub fn find_arg(&self, name: &str) -> Option<&InputValue> {
+        self.args.iter().find(|arg| arg.name == name)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum TypeDef {
+    Scalar { name: String },
+    Object { name: String, fields: Vec<FieldDef> },
+    InputObject { name: String, fields: Vec<InputValue> },
+    Enum { name: String, values: Vec<String> },
+}
+
+impl TypeDef {
+    pub fn name(&self) -> &str {
+        match self {
+            TypeDef::Scalar { name } => name,
+            TypeDef::Object { name, .. } => name,
+            TypeDef::InputObject { name, .. } => name,
+            TypeDef::Enum { name, .. } => name,
+        }
+    }
+
+    /// Finds a field on an object type.
+    pub fn find_field(&self, name: &str) -> Option<&FieldDef> {
+        match self {
+            TypeDef::Object { fields, .. } => fields.iter().find(|f| f.name == name),
+            _ => None,
+        }
+    }
+
+    /// Finds a field on an input object type.
+    pub fn find_input_field(&self, name: &str) -> Option<&InputValue> {
+        match self {
+            TypeDef::InputObject { fields, .. } => fields.iter().find(|f| f.name == name),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GraphQlSchema {
+    pub types: Vec<TypeDef>,
+}
+
+impl GraphQlSchema {
+    pub fn find_type(&self, name: &str) -> Option<&TypeDef> {
+        self.types.iter().find(|t| t.name() == name)
+    }
+
+    pub fn query_type(&self) -> &TypeDef {
+        self.find_type(QUERY_TYPE).expect("Query type must exist")
+    }
+
+    pub fn mutation_type(&self) -> &TypeDef {
+        self.find_type(MUTATION_TYPE).expect("Mutation type must exist")
+    }
+}
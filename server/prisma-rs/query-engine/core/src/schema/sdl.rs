@@ -0,0 +1,60 @@
+use super::*;
+use std::fmt::Write;
+
+/// Scalars every GraphQL implementation knows, which are not declared in the SDL.
+const SPEC_SCALARS: &[&str] = &["ID", "String", "Int", "Float", "Boolean"];
+
+impl GraphQlSchema {
+    /// Renders the schema in the GraphQL schema definition language.
+    pub fn to_sdl(&self) -> String {
+        let mut sdl = String::new();
+
+        for typ in self.types.iter() {
+            match typ {
+                TypeDef::Scalar { name } if SPEC_SCALARS.contains(&name.as_str()) => continue,
+                TypeDef::Scalar { name } => writeln!(sdl, "scalar {}", name),
+                TypeDef::Object { name, fields } => {
+                    writeln!(sdl, "type {} {{", name).unwrap();
+
+                    for field in fields {
+                        writeln!(sdl, "  {}{}: {}", field.name, render_args(&field.args), field.typ).unwrap();
+                    }
+
+                    writeln!(sdl, "}}")
+                }
+                TypeDef::InputObject { name, fields } => {
+                    writeln!(sdl, "input {} {{", name).unwrap();
+
+                    for field in fields {
+                        writeln!(sdl, "  {}: {}", field.name, field.typ).unwrap();
+                    }
+
+                    writeln!(sdl, "}}")
+                }
+                TypeDef::Enum { name, values } => {
+                    writeln!(sdl, "enum {} {{", name).unwrap();
+
+                    for value in values {
+                        writeln!(sdl, "  {}", value).unwrap();
+                    }
+
+                    writeln!(sdl, "}}")
+                }
+            }
+            .unwrap();
+
+            sdl.push('\n');
+        }
+
+        sdl
+    }
+}
+
+fn render_args(args: &[InputValue]) -> String {
+    if args.is_empty() {
+        String::new()
+    } else {
+        let args: Vec<String> = args.iter().map(|arg| format!("{}: {}", arg.name, arg.typ)).collect();
+        format!("({})", args.join(", "))
+    }
+}
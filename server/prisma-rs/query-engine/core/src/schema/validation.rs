@@ -0,0 +1,305 @@
+//! Validation of GraphQL documents against the generated schema
+//!
+//! Runs before the query builders, so that they only ever see documents selecting
+//! existing fields with well-typed arguments.
+
+use super::*;
+use crate::{CoreError, NormalizedOperation};
+use graphql_parser::{
+    query::{Definition, Document, Field, OperationDefinition, Selection, SelectionSet, TypeCondition, Value},
+    Pos,
+};
+
+/// A validation error as described by the GraphQL spec.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    pub message: String,
+    pub locations: Vec<Pos>,
+    pub path: Vec<String>,
+}
+
+impl ValidationError {
+    fn new<S: Into<String>>(message: S) -> Self {
+        Self {
+            message: message.into(),
+            locations: vec![],
+            path: vec![],
+        }
+    }
+}
+
+impl From<CoreError> for ValidationError {
+    fn from(e: CoreError) -> Self {
+        match e {
+            CoreError::QueryValidationError(message) => Self::new(message),
+            other => Self::new(format!("{}", other)),
+        }
+    }
+}
+
+impl GraphQlSchema {
+    /// Validates a normalized operation of `document` against the schema.
+    pub fn validate(&self, document: &Document, operation: &NormalizedOperation) -> Result<(), Vec<ValidationError>> {
+        let (root_type, selection_set) = match operation.definition {
+            OperationDefinition::SelectionSet(set) => (self.query_type(), set),
+            OperationDefinition::Query(q) => (self.query_type(), &q.selection_set),
+            OperationDefinition::Mutation(m) => (self.mutation_type(), &m.selection_set),
+            OperationDefinition::Subscription(_) => {
+                return Err(vec![ValidationError::new("Subscriptions are not supported")]);
+            }
+        };
+
+        let mut validator = Validator {
+            schema: self,
+            document,
+            path: vec![],
+            errors: vec![],
+        };
+
+        validator.fragment_conditions(root_type, selection_set);
+        validator.selection_set(root_type, &operation.selection_set, true);
+
+        if validator.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(validator.errors)
+        }
+    }
+}
+
+struct Validator<'a> {
+    schema: &'a GraphQlSchema,
+    document: &'a Document,
+    path: Vec<String>,
+    errors: Vec<ValidationError>,
+}
+
+impl<'a> Validator<'a> {
+    fn selection_set(&mut self, parent: &TypeDef, set: &SelectionSet, is_root: bool) {
+        for selection in set.items.iter() {
+            if let Selection::Field(field) = selection {
+                self.path.push(field.alias.as_ref().unwrap_or(&field.name).clone());
+
+                // Introspection is resolved against the schema itself and validated while resolving.
+                let is_introspection =
+                    field.name == "__typename" || (is_root && IntrospectionQuery::is_introspection(field));
+
+                match parent.find_field(&field.name) {
+                    Some(def) => self.field(field, def),
+                    None if is_introspection => (),
+                    None => self.error(
+                        field,
+                        format!("Cannot query field \"{}\" on type \"{}\".", field.name, parent.name()),
+                    ),
+                }
+
+                self.path.pop();
+            }
+        }
+    }
+
+    /// Checks the type conditions of the fragments in the selection set, before they are expanded.
+    /// There are no interfaces or unions, so a fragment only applies to the type it's declared on.
+    fn fragment_conditions(&mut self, parent: &TypeDef, set: &SelectionSet) {
+        for selection in set.items.iter() {
+            match selection {
+                Selection::Field(field) => {
+                    let schema = self.schema;
+                    let typ = parent
+                        .find_field(&field.name)
+                        .and_then(|def| schema.find_type(def.typ.name()));
+
+                    if let Some(typ @ TypeDef::Object { .. }) = typ {
+                        self.fragment_conditions(typ, &field.selection_set);
+                    }
+                }
+                Selection::InlineFragment(fragment) => {
+                    if let Some(TypeCondition::On(ref condition)) = fragment.type_condition {
+                        if condition != parent.name() {
+                            self.error_at(
+                                fragment.position,
+                                format!(
+                                    "Fragment cannot be spread here as objects of type \"{}\" can never be of type \"{}\".",
+                                    parent.name(),
+                                    condition
+                                ),
+                            );
+                        }
+                    }
+
+                    self.fragment_conditions(parent, &fragment.selection_set);
+                }
+                Selection::FragmentSpread(spread) => {
+                    // Unknown fragments and cycles are rejected while normalizing.
+                    let document = self.document;
+                    let fragment = document.definitions.iter().find_map(|d| match d {
+                        Definition::Fragment(f) if f.name == spread.fragment_name => Some(f),
+                        _ => None,
+                    });
+
+                    if let Some(fragment) = fragment {
+                        let TypeCondition::On(ref condition) = fragment.type_condition;
+
+                        if condition != parent.name() {
+                            self.error_at(
+                                spread.position,
+                                format!(
+                                    "Fragment \"{}\" cannot be spread here as objects of type \"{}\" can never be of type \"{}\".",
+                                    fragment.name,
+                                    parent.name(),
+                                    condition
+                                ),
+                            );
+                        } else {
+                            self.fragment_conditions(parent, &fragment.selection_set);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn field(&mut self, field: &Field, def: &FieldDef) {
+        for (name, value) in field.arguments.iter() {
+            match def.find_arg(name) {
+                Some(arg) => {
+                    if let Err(message) = self.value(&arg.typ, value) {
+                        self.error(
+                            field,
+                            format!("Argument \"{}\" has invalid value {}: {}", name, value, message),
+                        );
+                    }
+                }
+                None => self.error(
+                    field,
+                    format!("Unknown argument \"{}\" on field \"{}\".", name, field.name),
+                ),
+            }
+        }
+
+        for arg in def.args.iter().filter(|arg| arg.typ.is_non_null()) {
+            let provided = field
+                .arguments
+                .iter()
+                .any(|(name, value)| name == &arg.name && value != &Value::Null);
+
+            if !provided {
+                self.error(
+                    field,
+                    format!(
+                        "Field \"{}\" argument \"{}\" of type \"{}\" is required, but it was not provided.",
+                        field.name, arg.name, arg.typ
+                    ),
+                );
+            }
+        }
+
+        let schema = self.schema;
+        match schema.find_type(def.typ.name()) {
+            Some(typ @ TypeDef::Object { .. }) => {
+                if field.selection_set.items.is_empty() {
+                    self.error(
+                        field,
+                        format!(
+                            "Field \"{}\" of type \"{}\" must have a selection of subfields.",
+                            field.name, def.typ
+                        ),
+                    );
+                } else {
+                    self.selection_set(typ, &field.selection_set, false);
+                }
+            }
+            _ if !field.selection_set.items.is_empty() => self.error(
+                field,
+                format!(
+                    "Field \"{}\" must not have a selection since type \"{}\" has no subfields.",
+                    field.name, def.typ
+                ),
+            ),
+            _ => (),
+        }
+    }
+
+    /// Checks that an input value can be coerced to the given type.
+    fn value(&self, typ: &TypeRef, value: &Value) -> Result<(), String> {
+        match (typ, value) {
+            (TypeRef::NonNull(_), Value::Null) => Err(format!("Expected \"{}\", found null.", typ)),
+            (TypeRef::NonNull(inner), _) => self.value(inner, value),
+            (_, Value::Null) => Ok(()),
+            (TypeRef::List(inner), Value::List(values)) => values.iter().map(|v| self.value(inner, v)).collect(),
+            // A single value is coerced into a list of one value.
+            (TypeRef::List(inner), _) => self.value(inner, value),
+            (TypeRef::Named(name), _) => match self.schema.find_type(name) {
+                Some(TypeDef::Scalar { name }) => scalar_value(name, value),
+                Some(TypeDef::Enum { name, values }) => enum_value(name, values, value),
+                Some(TypeDef::InputObject { name, fields }) => self.input_object(name, fields, value),
+                _ => Err(format!("Type \"{}\" is not an input type.", name)),
+            },
+        }
+    }
+
+    fn input_object(&self, type_name: &str, fields: &[InputValue], value: &Value) -> Result<(), String> {
+        let object = match value {
+            Value::Object(obj) => obj,
+            _ => return Err(format!("Expected type \"{}\", found {}.", type_name, value)),
+        };
+
+        for (key, value) in object.iter() {
+            match fields.iter().find(|f| &f.name == key) {
+                Some(field) => self
+                    .value(&field.typ, value)
+                    .map_err(|message| format!("In field \"{}\": {}", key, message))?,
+                None => return Err(format!("Field \"{}\" is not defined by type \"{}\".", key, type_name)),
+            }
+        }
+
+        match fields
+            .iter()
+            .find(|f| f.typ.is_non_null() && !object.contains_key(&f.name))
+        {
+            Some(missing) => Err(format!(
+                "Field \"{}.{}\" of required type \"{}\" was not provided.",
+                type_name, missing.name, missing.typ
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn error(&mut self, field: &Field, message: String) {
+        self.error_at(field.position, message);
+    }
+
+    fn error_at(&mut self, position: Pos, message: String) {
+        self.errors.push(ValidationError {
+            message,
+            locations: vec![position],
+            path: self.path.clone(),
+        });
+    }
+}
+
+fn scalar_value(name: &str, value: &Value) -> Result<(), String> {
+    let valid = match (name, value) {
+        ("Int", Value::Int(_)) => true,
+        ("Float", Value::Int(_)) | ("Float", Value::Float(_)) => true,
+        ("Boolean", Value::Boolean(_)) => true,
+        ("ID", Value::String(_)) | ("ID", Value::Int(_)) => true,
+        ("String", Value::String(_)) => true,
+        ("DateTime", Value::String(_)) | ("Json", Value::String(_)) | ("UUID", Value::String(_)) => true,
+        _ => false,
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Expected type \"{}\", found {}.", name, value))
+    }
+}
+
+fn enum_value(name: &str, values: &[String], value: &Value) -> Result<(), String> {
+    match value {
+        Value::Enum(v) | Value::String(v) if values.contains(v) => Ok(()),
+        Value::Enum(v) | Value::String(v) => Err(format!("Value \"{}\" does not exist in \"{}\" enum.", v, name)),
+        _ => Err(format!("Expected type \"{}\", found {}.", name, value)),
+    }
+}
@@ -0,0 +1,121 @@
+//! Coercion of GraphQL input values into `PrismaValue`s
+
+use crate::{CoreError, CoreResult};
+use chrono::{DateTime, Utc};
+use graphql_parser::query::Value;
+use prisma_models::{GraphqlId, PrismaValue, ScalarField, TypeIdentifier};
+use std::convert::TryFrom;
+use uuid::Uuid;
+
+/// Coerces a GraphQL input value into a `PrismaValue` matching the type of the given field.
+pub fn to_prisma_value(field: &ScalarField, value: &Value) -> CoreResult<PrismaValue> {
+    let invalid = || {
+        CoreError::QueryValidationError(format!(
+            "Invalid value `{}` for field `{}` of type {}",
+            value,
+            field.name,
+            field.type_identifier.user_friendly_type_name()
+        ))
+    };
+
+    coerce(field.type_identifier, value).ok_or_else(invalid)
+}
+
+/// Coerces a GraphQL input value into a `PrismaValue` of the given type, if it's valid for the type.
+/// Integers out of the range of the type are invalid.
+pub fn coerce(type_identifier: TypeIdentifier, value: &Value) -> Option<PrismaValue> {
+    #[cfg_attr(rustfmt, rustfmt_skip)]
+    let prisma_value = match (type_identifier, value) {
+        (_, Value::Null) => PrismaValue::Null,
+        (TypeIdentifier::String, Value::String(s)) => PrismaValue::String(s.clone()),
+        (TypeIdentifier::Json, Value::String(s)) => PrismaValue::Json(s.clone()),
+        (TypeIdentifier::Enum, Value::Enum(e)) => PrismaValue::Enum(e.clone()),
+        (TypeIdentifier::Enum, Value::String(e)) => PrismaValue::Enum(e.clone()),
+        (TypeIdentifier::Boolean, Value::Boolean(b)) => PrismaValue::Boolean(*b),
+        (TypeIdentifier::Float, Value::Float(f)) => PrismaValue::Float(*f),
+        (TypeIdentifier::Float, Value::Int(i)) => PrismaValue::Float(i.as_i64()? as f64),
+        (TypeIdentifier::Int, Value::Int(i)) => PrismaValue::Int(i32::try_from(i.as_i64()?).ok()?),
+        (TypeIdentifier::GraphQLID, Value::String(s)) => PrismaValue::GraphqlId(GraphqlId::String(s.clone())),
+        (TypeIdentifier::GraphQLID, Value::Int(i)) => PrismaValue::GraphqlId(GraphqlId::Int(usize::try_from(i.as_i64()?).ok()?)),
+        (TypeIdentifier::UUID, Value::String(s)) => PrismaValue::Uuid(Uuid::parse_str(s).ok()?),
+        (TypeIdentifier::DateTime, Value::String(s)) => PrismaValue::DateTime(s.parse::<DateTime<Utc>>().ok()?),
+        _ => return None,
+    };
+
+    Some(prisma_value)
+}
+
+/// Coerces a GraphQL list value into a list of `PrismaValue`s for the given scalar list field.
+pub fn to_prisma_list_value(field: &ScalarField, value: &Value) -> CoreResult<Vec<PrismaValue>> {
+    match value {
+        Value::List(values) => values.iter().map(|v| to_prisma_value(field, v)).collect(),
+        Value::Null => Ok(vec![]),
+        single => Ok(vec![to_prisma_value(field, single)?]),
+    }
+}
+
+/// Converts a GraphQL input value into the JSON value it denotes, as taken by filters and updates
+/// at a path of a JSON field.
+pub fn to_json(value: &Value) -> CoreResult<serde_json::Value> {
+    let invalid = || CoreError::QueryValidationError(format!("Invalid JSON value `{}`", value));
+
+    let json = match value {
+        Value::Null => serde_json::Value::Null,
+        Value::Boolean(b) => serde_json::Value::Bool(*b),
+        Value::Int(i) => serde_json::Value::from(i.as_i64().ok_or_else(invalid)?),
+        Value::Float(f) => serde_json::Number::from_f64(*f)
+            .map(serde_json::Value::Number)
+            .ok_or_else(invalid)?,
+        Value::String(s) | Value::Enum(s) => serde_json::Value::String(s.clone()),
+        Value::List(values) => serde_json::Value::Array(values.iter().map(to_json).collect::<CoreResult<_>>()?),
+        Value::Object(obj) => serde_json::Value::Object(
+            obj.iter()
+                .map(|(key, value)| Ok((key.clone(), to_json(value)?)))
+                .collect::<CoreResult<_>>()?,
+        ),
+        Value::Variable(_) => return Err(invalid()),
+    };
+
+    Ok(json)
+}
+
+/// Reads a path into a JSON document, a list of object keys and array indices.
+pub fn to_json_path(value: &Value) -> CoreResult<Vec<String>> {
+    let invalid = || CoreError::QueryValidationError(format!("Invalid JSON path `{}`", value));
+
+    match value {
+        Value::Null => Ok(vec![]),
+        Value::List(segments) => segments
+            .iter()
+            .map(|segment| match segment {
+                Value::String(key) => Ok(key.clone()),
+                Value::Int(index) => index
+                    .as_i64()
+                    .filter(|i| *i >= 0)
+                    .map(|i| i.to_string())
+                    .ok_or_else(invalid),
+                _ => Err(invalid()),
+            })
+            .collect(),
+        _ => Err(invalid()),
+    }
+}
+
+/// Coerces a cursor given to `after` or `before` into an id of the model of `id_field`.
+/// Cursors are record ids, with integer ids also accepted as strings.
+pub fn to_cursor(id_field: &ScalarField, value: &Value) -> CoreResult<GraphqlId> {
+    let invalid =
+        || CoreError::QueryValidationError(format!("Invalid cursor `{}` for field `{}`", value, id_field.name));
+
+    match (id_field.type_identifier, value) {
+        (TypeIdentifier::UUID, Value::String(s)) => Uuid::parse_str(s).map(GraphqlId::UUID).map_err(|_| invalid()),
+        (TypeIdentifier::Int, Value::String(s)) => s.parse::<usize>().map(GraphqlId::Int).map_err(|_| invalid()),
+        (_, Value::Int(i)) => i
+            .as_i64()
+            .and_then(|i| usize::try_from(i).ok())
+            .map(GraphqlId::Int)
+            .ok_or_else(invalid),
+        (_, Value::String(s)) => Ok(GraphqlId::String(s.clone())),
+        _ => Err(invalid()),
+    }
+}
@@ -0,0 +1,73 @@
+//! Queries built from GraphQL documents against the test schema of the SQLite connector, with
+//! users having many sites.
+#![allow(dead_code)]
+
+use connector::{filter::*, QueryArguments};
+use core::{CoreResult, Operation, PrismaQuery, RootQueryBuilder};
+use graphql_parser::query::Value;
+use prisma_models::*;
+use std::{collections::BTreeMap, fs::File};
+
+pub fn schema() -> SchemaRef {
+    let template: SchemaTemplate =
+        serde_json::from_reader(File::open("../connectors/sqlite-connector/test_schema.json").unwrap()).unwrap();
+
+    template.build(String::from("test"))
+}
+
+/// Builds the root fields of the document.
+pub fn build(schema: &SchemaRef, query: &str) -> CoreResult<Vec<Operation>> {
+    build_with_variables(schema, query, BTreeMap::new())
+}
+
+pub fn build_with_variables(
+    schema: &SchemaRef,
+    query: &str,
+    variables: BTreeMap<String, Value>,
+) -> CoreResult<Vec<Operation>> {
+    let builder = RootQueryBuilder {
+        query: graphql_parser::parse_query(query).unwrap(),
+        schema: schema.clone(),
+        operation_name: None,
+        variables,
+    };
+
+    builder.build()
+}
+
+/// The arguments of the list query in the first root field of the document.
+pub fn query_arguments(schema: &SchemaRef, query: &str) -> CoreResult<QueryArguments> {
+    match build(schema, query)?.into_iter().next() {
+        Some(Operation::Read(PrismaQuery::MultiRecordQuery(query))) => Ok(query.args),
+        _ => panic!("Expected a list query in `{}`", query),
+    }
+}
+
+/// Renders a filter with field names instead of whole fields, to compare it to the expected one.
+pub fn describe(filter: &Filter) -> String {
+    let describe_all = |filters: &Vec<Box<Filter>>| {
+        let filters: Vec<String> = filters.iter().map(|filter| describe(filter)).collect();
+        filters.join(", ")
+    };
+
+    match filter {
+        Filter::And(filters) => format!("AND({})", describe_all(filters)),
+        Filter::Or(filters) => format!("OR({})", describe_all(filters)),
+        Filter::Not(filters) => format!("NOT({})", describe_all(filters)),
+        Filter::Scalar(filter) if filter.mode == StringMode::Insensitive => {
+            format!("{} {:?} insensitive", filter.field.name, filter.condition)
+        }
+        Filter::Scalar(filter) => format!("{} {:?}", filter.field.name, filter.condition),
+        Filter::ScalarList(filter) => format!("{} {:?}", filter.field.name, filter.condition),
+        Filter::OneRelationIsNull(filter) => format!("{} IS NULL", filter.field.name),
+        Filter::Relation(filter) => format!(
+            "{} {:?}({})",
+            filter.field.name,
+            filter.condition,
+            describe(&filter.nested_filter)
+        ),
+        Filter::Json(filter) => format!("{}{:?} {:?}", filter.field.name, filter.path, filter.condition),
+        Filter::NodeSubscription => String::from("NodeSubscription"),
+        Filter::BoolFilter(b) => b.to_string(),
+    }
+}
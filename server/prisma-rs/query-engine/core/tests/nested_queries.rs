@@ -0,0 +1,193 @@
+mod common;
+
+use common::*;
+use connector::{
+    filter::NodeSelector, mutaction::*, AggregationRow, AggregationSelection, ConnectorResult, DataResolver,
+    DatabaseMutactionExecutor, QueryArguments, RelatedNodesCount, ScalarListValues,
+};
+use core::{Operation, PrismaQuery, PrismaQueryResult, QueryExecutor};
+use prisma_models::*;
+use std::sync::{Arc, Mutex};
+
+/// Returns three users and one related record per parent, recording the related queries.
+#[derive(Default)]
+struct RecordingResolver {
+    related_queries: Mutex<Vec<(String, Vec<GraphqlId>)>>,
+}
+
+impl RecordingResolver {
+    fn node(id: String, parent_id: Option<GraphqlId>, selected_fields: &SelectedFields) -> Node {
+        let values = selected_fields
+            .names()
+            .into_iter()
+            .map(|name| match name.as_str() {
+                "id" => PrismaValue::GraphqlId(GraphqlId::String(id.clone())),
+                _ => PrismaValue::String(format!("{} of {}", name, id)),
+            })
+            .collect();
+
+        Node { values, parent_id }
+    }
+}
+
+impl DataResolver for RecordingResolver {
+    fn get_node_by_where(&self, _: &NodeSelector, _: &SelectedFields) -> ConnectorResult<Option<SingleNode>> {
+        unimplemented!()
+    }
+
+    fn get_nodes(
+        &self,
+        _: ModelRef,
+        _: QueryArguments,
+        selected_fields: &SelectedFields,
+    ) -> ConnectorResult<ManyNodes> {
+        Ok(ManyNodes {
+            nodes: (1..=3)
+                .map(|i| Self::node(format!("user{}", i), None, selected_fields))
+                .collect(),
+            field_names: selected_fields.names(),
+        })
+    }
+
+    fn get_related_nodes(
+        &self,
+        from_field: RelationFieldRef,
+        from_node_ids: &[GraphqlId],
+        _: QueryArguments,
+        selected_fields: &SelectedFields,
+    ) -> ConnectorResult<ManyNodes> {
+        self.related_queries
+            .lock()
+            .unwrap()
+            .push((from_field.name.clone(), from_node_ids.to_vec()));
+
+        let nodes = from_node_ids
+            .iter()
+            .map(|parent_id| {
+                let id = match parent_id {
+                    GraphqlId::String(id) => format!("{}/{}", id, from_field.name),
+                    _ => unreachable!(),
+                };
+
+                Self::node(id, Some(parent_id.clone()), selected_fields)
+            })
+            .collect();
+
+        Ok(ManyNodes {
+            nodes,
+            field_names: selected_fields.names(),
+        })
+    }
+
+    fn get_scalar_list_values_by_node_ids(
+        &self,
+        _: ScalarFieldRef,
+        _: Vec<GraphqlId>,
+    ) -> ConnectorResult<Vec<ScalarListValues>> {
+        Ok(vec![])
+    }
+
+    fn count_by_model(&self, _: ModelRef, _: QueryArguments) -> ConnectorResult<usize> {
+        unimplemented!()
+    }
+
+    fn count_related_nodes(
+        &self,
+        _: RelationFieldRef,
+        _: &[GraphqlId],
+        _: QueryArguments,
+    ) -> ConnectorResult<Vec<RelatedNodesCount>> {
+        unimplemented!()
+    }
+
+    fn count_by_table(&self, _: &str, _: &str) -> ConnectorResult<usize> {
+        unimplemented!()
+    }
+
+    fn aggregate(
+        &self,
+        _: ModelRef,
+        _: QueryArguments,
+        _: AggregationSelection,
+    ) -> ConnectorResult<Vec<AggregationRow>> {
+        unimplemented!()
+    }
+}
+
+struct NoWrites;
+
+impl DatabaseMutactionExecutor for NoWrites {
+    fn execute_raw(&self, _: String) -> ConnectorResult<serde_json::Value> {
+        unimplemented!()
+    }
+
+    fn execute(
+        &self,
+        _: String,
+        _: DatabaseMutaction,
+        _: Option<GraphqlId>,
+    ) -> ConnectorResult<DatabaseMutactionResults> {
+        unimplemented!()
+    }
+
+    fn execute_with_nested(
+        &self,
+        _: String,
+        _: DatabaseMutaction,
+        _: Option<GraphqlId>,
+    ) -> ConnectorResult<DatabaseMutactionResults> {
+        unimplemented!()
+    }
+}
+
+fn ids(ids: &[&str]) -> Vec<GraphqlId> {
+    ids.iter().map(|id| GraphqlId::String(id.to_string())).collect()
+}
+
+#[test]
+fn nested_levels_are_loaded_with_one_query_each() {
+    let schema = schema();
+    let resolver = Arc::new(RecordingResolver::default());
+    let executor = QueryExecutor {
+        data_resolver: resolver.clone(),
+        write_executor: Arc::new(NoWrites),
+    };
+
+    let queries: Vec<PrismaQuery> = build(&schema, r#"{ users { id sites { id user { name } } } }"#)
+        .unwrap()
+        .into_iter()
+        .map(|operation| match operation {
+            Operation::Read(query) => query,
+            _ => panic!("Expected a read"),
+        })
+        .collect();
+
+    let results = executor.execute(&queries).unwrap();
+
+    assert_eq!(
+        vec![
+            ("sites".to_string(), ids(&["user1", "user2", "user3"])),
+            ("user".to_string(), ids(&["user1/sites", "user2/sites", "user3/sites"])),
+        ],
+        *resolver.related_queries.lock().unwrap()
+    );
+
+    // The related records of all parents are returned together, each one carrying its parent.
+    let sites = match &results[0] {
+        PrismaQueryResult::Multi(users) => match &users.nested[0] {
+            PrismaQueryResult::Multi(sites) => sites,
+            _ => panic!("Expected the sites of the users"),
+        },
+        _ => panic!("Expected a list of users"),
+    };
+
+    let parents: Vec<Option<GraphqlId>> = sites.result.nodes.iter().map(|node| node.parent_id.clone()).collect();
+    assert_eq!(
+        ids(&["user1", "user2", "user3"])
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>(),
+        parents
+    );
+    assert_eq!(ids(&["user1/sites", "user2/sites", "user3/sites"]), sites.ids);
+}
@@ -0,0 +1,43 @@
+mod common;
+
+use common::*;
+
+/// The sort keys of the list query in the document, with their field names.
+fn sort_keys(query: &str) -> Vec<String> {
+    query_arguments(&schema(), query)
+        .unwrap()
+        .order_by
+        .iter()
+        .map(|oby| format!("{} {:?}", oby.field.name, oby.sort_order))
+        .collect()
+}
+
+#[test]
+fn sort_keys_keep_their_order() {
+    assert_eq!(
+        vec!["name Ascending", "id Descending"],
+        sort_keys(r#"{ users(orderBy: [{ name: asc }, { id: desc }]) { id } }"#)
+    );
+    assert_eq!(
+        vec!["id Descending", "name Ascending"],
+        sort_keys(r#"{ users(orderBy: [{ id: desc }, { name: asc }]) { id } }"#)
+    );
+}
+
+#[test]
+fn a_single_sort_key_may_be_given_without_a_list() {
+    assert_eq!(
+        vec!["name Descending"],
+        sort_keys(r#"{ users(orderBy: { name: desc }) { id } }"#)
+    );
+    assert!(sort_keys(r#"{ users { id } }"#).is_empty());
+}
+
+#[test]
+fn every_sort_key_has_one_field() {
+    let schema = schema();
+
+    assert!(query_arguments(&schema, r#"{ users(orderBy: [{ name: asc, id: desc }]) { id } }"#).is_err());
+    assert!(query_arguments(&schema, r#"{ users(orderBy: [{ name: up }]) { id } }"#).is_err());
+    assert!(query_arguments(&schema, r#"{ users(orderBy: [{ nickname: asc }]) { id } }"#).is_err());
+}
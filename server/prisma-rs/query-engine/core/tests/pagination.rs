@@ -0,0 +1,54 @@
+mod common;
+
+use common::*;
+use core::CoreError;
+use prisma_models::*;
+
+#[test]
+fn last_paginates_backwards() {
+    let args = query_arguments(&schema(), r#"{ users(last: 2, before: "user4") { id } }"#).unwrap();
+
+    assert_eq!(Some(2), args.last);
+    assert_eq!(None, args.first);
+    assert_eq!(Some(GraphqlId::from("user4")), args.before);
+    assert_eq!(None, args.after);
+}
+
+#[test]
+fn cursors_are_record_ids() {
+    let schema = schema();
+
+    let args = query_arguments(&schema, r#"{ users(first: 2, after: "user1") { id } }"#).unwrap();
+    assert_eq!(Some(GraphqlId::from("user1")), args.after);
+    assert_eq!(Some(2), args.first);
+
+    let args = query_arguments(&schema, r#"{ users(after: 7) { id } }"#).unwrap();
+    assert_eq!(Some(GraphqlId::Int(7)), args.after);
+    assert_eq!(None, args.first);
+
+    let args = query_arguments(&schema, r#"{ users(before: null) { id } }"#).unwrap();
+    assert_eq!(None, args.before);
+
+    assert!(query_arguments(&schema, r#"{ users(before: -1) { id } }"#).is_err());
+}
+
+#[test]
+fn counts_cannot_be_negative() {
+    let schema = schema();
+
+    for query in &[
+        r#"{ users(first: -1) { id } }"#,
+        r#"{ users(last: -1) { id } }"#,
+        r#"{ users(skip: -1) { id } }"#,
+    ] {
+        match query_arguments(&schema, query) {
+            Err(CoreError::QueryValidationError(_)) => (),
+            other => panic!("Expected a validation error for {}, got {:?}", query, other),
+        }
+    }
+}
+
+#[test]
+fn first_and_last_cannot_be_combined() {
+    assert!(query_arguments(&schema(), r#"{ users(first: 2, last: 2) { id } }"#).is_err());
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use common::*;
+use core::{GraphQlSchema, NormalizedOperation, ValidationError};
+use prisma_models::*;
+use serde_json::json;
+use std::{collections::BTreeMap, fs::File};
+
+fn input_field_type(schema: &GraphQlSchema, input: &str, field: &str) -> Option<String> {
+    schema
+        .find_type(input)
+        .unwrap_or_else(|| panic!("Unknown type `{}`", input))
+        .find_input_field(field)
+        .map(|field| field.typ.to_string())
+}
+
+/// The test schema with a `role` field on users, typed by the `Role` enum.
+fn schema_with_enum() -> SchemaRef {
+    let mut template: serde_json::Value =
+        serde_json::from_reader(File::open("../connectors/sqlite-connector/test_schema.json").unwrap()).unwrap();
+    let role = json!({ "name": "Role", "values": ["ADMIN", "USER"] });
+
+    template["enums"] = json!([role.clone()]);
+    template["models"][0]["fields"].as_array_mut().unwrap().push(json!({
+        "name": "role",
+        "typeIdentifier": "Enum",
+        "isRequired": true,
+        "isList": false,
+        "isUnique": false,
+        "isHidden": false,
+        "isReadonly": false,
+        "isAutoGenerated": false,
+        "enum": role
+    }));
+
+    let template: SchemaTemplate = serde_json::from_value(template).unwrap();
+    template.build(String::from("test"))
+}
+
+#[test]
+fn nested_creates_leave_out_the_back_relation() {
+    let graphql_schema = GraphQlSchema::build(&schema());
+
+    assert_eq!(
+        Some("SiteCreateOneWithoutUserInput".to_string()),
+        input_field_type(&graphql_schema, "UserCreateInput", "sites")
+    );
+    assert_eq!(
+        Some("SiteCreateWithoutUserInput".to_string()),
+        input_field_type(&graphql_schema, "SiteCreateOneWithoutUserInput", "create")
+    );
+    assert_eq!(
+        Some("String!".to_string()),
+        input_field_type(&graphql_schema, "SiteCreateWithoutUserInput", "name")
+    );
+    assert_eq!(
+        None,
+        input_field_type(&graphql_schema, "SiteCreateWithoutUserInput", "user")
+    );
+
+    // Top level creates still take both sides of the relation.
+    assert!(input_field_type(&graphql_schema, "SiteCreateInput", "user").is_some());
+
+    let validate = |query: &str| {
+        let document = graphql_parser::parse_query(query).unwrap();
+        let operation =
+            NormalizedOperation::new(&document, None, &BTreeMap::new()).map_err(|e| vec![ValidationError::from(e)])?;
+
+        graphql_schema.validate(&document, &operation)
+    };
+
+    assert!(
+        validate(r#"mutation { createUser(data: { name: "Bob", sites: { create: { name: "Blog" } } }) { id } }"#)
+            .is_ok()
+    );
+    assert!(validate(
+        r#"mutation {
+            createUser(data: { name: "Bob", sites: { create: { name: "Blog", user: { connect: { id: "1" } } } } }) { id }
+        }"#
+    )
+    .is_err());
+}
+
+#[test]
+fn enum_fields_are_typed_by_their_enum() {
+    let graphql_schema = GraphQlSchema::build(&schema_with_enum());
+
+    assert_eq!(
+        Some("Role!".to_string()),
+        input_field_type(&graphql_schema, "UserCreateInput", "role")
+    );
+    assert_eq!(
+        Some("[Role!]".to_string()),
+        input_field_type(&graphql_schema, "UserWhereInput", "role_in")
+    );
+    assert_eq!(
+        Some("Role!".to_string()),
+        graphql_schema
+            .find_type("User")
+            .and_then(|user| user.find_field("role"))
+            .map(|field| field.typ.to_string())
+    );
+
+    let validate = |query: &str| {
+        let document = graphql_parser::parse_query(query).unwrap();
+        let operation =
+            NormalizedOperation::new(&document, None, &BTreeMap::new()).map_err(|e| vec![ValidationError::from(e)])?;
+
+        graphql_schema.validate(&document, &operation)
+    };
+
+    assert!(validate(r#"{ users(where: { role: ADMIN }) { id role } }"#).is_ok());
+    assert!(validate(r#"{ users(where: { role: OWNER }) { id } }"#).is_err());
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use common::*;
+use core::{GraphQlSchema, NormalizedOperation, ValidationError};
+use graphql_parser::Pos;
+use std::collections::BTreeMap;
+
+fn validate(query: &str) -> Result<(), Vec<ValidationError>> {
+    let graphql_schema = GraphQlSchema::build(&schema());
+    let document = graphql_parser::parse_query(query).unwrap();
+
+    let operation =
+        NormalizedOperation::new(&document, None, &BTreeMap::new()).map_err(|e| vec![ValidationError::from(e)])?;
+
+    graphql_schema.validate(&document, &operation)
+}
+
+/// The only error of an invalid query.
+fn error(query: &str) -> ValidationError {
+    let mut errors = validate(query).unwrap_err();
+    assert_eq!(1, errors.len(), "{:?}", errors);
+
+    errors.remove(0)
+}
+
+fn path(fields: &[&str]) -> Vec<String> {
+    fields.iter().map(|field| field.to_string()).collect()
+}
+
+#[test]
+fn valid_queries() {
+    assert!(validate(r#"{ users(where: { name_contains: "o" }, first: 10) { id name } }"#).is_ok());
+    assert!(validate(r#"{ user(where: { id: "1" }) { id sites { name } } }"#).is_ok());
+    assert!(validate(r#"mutation { deleteManyUsers(where: { name: "Bob" }) { count } }"#).is_ok());
+}
+
+#[test]
+fn unknown_fields() {
+    assert_eq!(
+        ValidationError {
+            message: r#"Cannot query field "nickname" on type "User"."#.into(),
+            locations: vec![Pos { line: 1, column: 14 }],
+            path: path(&["users", "nickname"]),
+        },
+        error(r#"{ users { id nickname } }"#)
+    );
+
+    // Aliased fields are reported under their alias.
+    assert_eq!(
+        path(&["people", "site", "title"]),
+        error(r#"{ people: users { site: sites { title } } }"#).path
+    );
+}
+
+#[test]
+fn wrong_argument_types() {
+    assert_eq!(
+        r#"Argument "first" has invalid value "ten": Expected type "Int", found "ten"."#,
+        error(r#"{ users(first: "ten") { id } }"#).message
+    );
+
+    assert_eq!(
+        r#"Argument "where" has invalid value {nickname: "Bob"}: Field "nickname" is not defined by type "UserWhereInput"."#,
+        error(r#"{ users(where: { nickname: "Bob" }) { id } }"#).message
+    );
+
+    // Enum literals are not strings.
+    assert_eq!(
+        r#"Argument "where" has invalid value {name: Bob}: In field "name": Expected type "String", found Bob."#,
+        error(r#"{ users(where: { name: Bob }) { id } }"#).message
+    );
+
+    assert_eq!(
+        r#"Unknown argument "limit" on field "users"."#,
+        error(r#"{ users(limit: 10) { id } }"#).message
+    );
+}
+
+#[test]
+fn missing_required_arguments() {
+    assert_eq!(
+        r#"Field "user" argument "where" of type "UserWhereUniqueInput!" is required, but it was not provided."#,
+        error(r#"{ user { id } }"#).message
+    );
+
+    assert_eq!(
+        r#"Argument "data" has invalid value {}: Field "UserCreateInput.name" of required type "String!" was not provided."#,
+        error(r#"mutation { createUser(data: {}) { id } }"#).message
+    );
+}
+
+#[test]
+fn selections_must_match_leaf_types() {
+    assert_eq!(
+        r#"Field "name" must not have a selection since type "String!" has no subfields."#,
+        error(r#"{ users { name { length } } }"#).message
+    );
+
+    assert_eq!(
+        r#"Field "users" of type "[User!]!" must have a selection of subfields."#,
+        error(r#"{ users }"#).message
+    );
+}
+
+#[test]
+fn unknown_enum_values() {
+    assert_eq!(
+        r#"Argument "orderBy" has invalid value {name: up}: In field "name": Value "up" does not exist in "SortOrder" enum."#,
+        error(r#"{ users(orderBy: { name: up }) { id } }"#).message
+    );
+}
+
+#[test]
+fn all_errors_are_reported() {
+    let errors = validate(r#"{ users(limit: 10) { id nickname } sites { title } }"#).unwrap_err();
+    let paths: Vec<Vec<String>> = errors.into_iter().map(|error| error.path).collect();
+
+    assert_eq!(
+        vec![
+            path(&["users"]),
+            path(&["users", "nickname"]),
+            path(&["sites", "title"])
+        ],
+        paths
+    );
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use common::*;
+use core::{GraphQlSchema, NormalizedOperation, Operation, PrismaQuery, ValidationError};
+use graphql_parser::query::Value;
+use std::collections::BTreeMap;
+
+fn variables(values: Vec<(&str, Value)>) -> BTreeMap<String, Value> {
+    values
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect()
+}
+
+/// The columns selected by the list query in the first root field of the document.
+fn selected(query: &str, variables: BTreeMap<String, Value>) -> Vec<String> {
+    let schema = schema();
+
+    match build_with_variables(&schema, query, variables)
+        .unwrap()
+        .into_iter()
+        .next()
+    {
+        Some(Operation::Read(PrismaQuery::MultiRecordQuery(query))) => query.selected_fields.names(),
+        _ => panic!("Expected a list query"),
+    }
+}
+
+#[test]
+fn required_variables_must_be_provided() {
+    let schema = schema();
+    let query = r#"query($name: String!) { users(where: { name: $name }) { id } }"#;
+
+    assert!(build_with_variables(&schema, query, BTreeMap::new()).is_err());
+    assert!(build_with_variables(&schema, query, variables(vec![("name", Value::Null)])).is_err());
+    assert!(build_with_variables(&schema, query, variables(vec![("name", Value::String("Bob".into()))])).is_ok());
+
+    // Optional variables and variables with a default value may be left out.
+    let query = r#"query($name: String = "Bob", $first: Int) { users(where: { name: $name }) { id } }"#;
+    let args = query_arguments(&schema, query).unwrap();
+
+    assert_eq!(r#"name Equals(String("Bob"))"#, describe(&args.filter.unwrap()));
+}
+
+#[test]
+fn include_and_skip_directives() {
+    let query = r#"query($withName: Boolean!) {
+        users {
+            id
+            name @include(if: $withName)
+        }
+    }"#;
+
+    assert!(selected(query, variables(vec![("withName", Value::Boolean(true))])).contains(&"name".to_string()));
+    assert!(!selected(query, variables(vec![("withName", Value::Boolean(false))])).contains(&"name".to_string()));
+
+    let query = r#"{
+        users {
+            id
+            ... on User @skip(if: true) { name }
+        }
+    }"#;
+
+    assert!(!selected(query, BTreeMap::new()).contains(&"name".to_string()));
+
+    let schema = schema();
+    let query = r#"{ users { id name @include(if: "yes") } }"#;
+
+    assert!(build_with_variables(&schema, query, BTreeMap::new()).is_err());
+}
+
+#[test]
+fn fragments_must_match_the_type_they_are_spread_in() {
+    let schema = schema();
+    let graphql_schema = GraphQlSchema::build(&schema);
+
+    let validate = |query: &str| {
+        let document = graphql_parser::parse_query(query).unwrap();
+        let operation =
+            NormalizedOperation::new(&document, None, &BTreeMap::new()).map_err(|e| vec![ValidationError::from(e)])?;
+
+        graphql_schema.validate(&document, &operation)
+    };
+
+    assert!(validate(r#"{ users { ...userFields } } fragment userFields on User { id name }"#).is_ok());
+    assert!(validate(r#"{ users { ... on User { id } } }"#).is_ok());
+
+    let errors = validate(r#"{ users { ...siteFields } } fragment siteFields on Site { id }"#).unwrap_err();
+    assert_eq!(
+        r#"Fragment "siteFields" cannot be spread here as objects of type "User" can never be of type "Site"."#,
+        errors[0].message
+    );
+
+    assert!(validate(r#"{ users { ... on Site { id } } }"#).is_err());
+}
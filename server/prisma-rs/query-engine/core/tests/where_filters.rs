@@ -0,0 +1,141 @@
+mod common;
+
+use common::*;
+
+fn filter(query: &str) -> String {
+    let schema = schema();
+    let args = query_arguments(&schema, query).unwrap();
+
+    describe(&args.filter.expect("no filter parsed"))
+}
+
+#[test]
+fn scalar_filters() {
+    assert_eq!(
+        r#"name Equals(String("Bob"))"#,
+        filter(r#"{ users(where: { name: "Bob" }) { id } }"#)
+    );
+
+    assert_eq!(
+        r#"name NotStartsWith(String("B"))"#,
+        filter(r#"{ users(where: { name_not_starts_with: "B" }) { id } }"#)
+    );
+
+    assert_eq!(
+        r#"name NotIn([String("Alice"), String("Bob")])"#,
+        filter(r#"{ users(where: { name_not_in: ["Alice", "Bob"] }) { id } }"#)
+    );
+}
+
+#[test]
+fn entries_are_combined_with_and() {
+    assert_eq!(
+        r#"AND(id NotEquals(GraphqlId(String("1"))), name Contains(String("o")))"#,
+        filter(r#"{ users(where: { name_contains: "o", id_not: "1" }) { id } }"#)
+    );
+}
+
+#[test]
+fn boolean_operators() {
+    assert_eq!(
+        r#"OR(name Equals(String("Alice")), name Equals(String("Bob")))"#,
+        filter(r#"{ users(where: { OR: [{ name: "Alice" }, { name: "Bob" }] }) { id } }"#)
+    );
+
+    // A single filter may be given without the list.
+    assert_eq!(
+        r#"NOT(name Equals(String("Alice")))"#,
+        filter(r#"{ users(where: { NOT: { name: "Alice" } }) { id } }"#)
+    );
+}
+
+#[test]
+fn scalar_list_filters() {
+    assert_eq!(
+        "cats Contains(Int(1))",
+        filter(r#"{ users(where: { cats_contains: 1 }) { id } }"#)
+    );
+
+    assert_eq!(
+        "cats ContainsEvery([Int(1), Int(2)])",
+        filter(r#"{ users(where: { cats_contains_every: [1, 2] }) { id } }"#)
+    );
+
+    // A single value may be given without the list.
+    assert_eq!(
+        "cats ContainsSome([Int(3)])",
+        filter(r#"{ users(where: { cats_contains_some: 3 }) { id } }"#)
+    );
+}
+
+#[test]
+fn insensitive_filters() {
+    assert_eq!(
+        r#"name StartsWith(String("b")) insensitive"#,
+        filter(r#"{ users(where: { _mode: insensitive, name_starts_with: "b" }) { id } }"#)
+    );
+
+    // The mode doesn't apply to nested objects.
+    assert_eq!(
+        r#"OR(name Equals(String("bob")))"#,
+        filter(r#"{ users(where: { _mode: insensitive, OR: [{ name: "bob" }] }) { id } }"#)
+    );
+}
+
+#[test]
+fn json_filters() {
+    assert_eq!(
+        r#"meta["a", "b"] Equals(Number(3))"#,
+        filter(r#"{ sites(where: { meta_json: { path: ["a", "b"], equals: 3 } }) { id } }"#)
+    );
+
+    // Without a path the condition applies to the whole document.
+    assert_eq!(
+        r#"meta[] StringContains("x")"#,
+        filter(r#"{ sites(where: { meta_json: { string_contains: "x" } }) { id } }"#)
+    );
+
+    // The conditions of one filter share the path.
+    assert_eq!(
+        r#"AND(meta["tags"] ArrayContains(String("x")), meta["tags"] Equals(Null))"#,
+        filter(r#"{ sites(where: { meta_json: { path: ["tags"], array_contains: "x", equals: null } }) { id } }"#)
+    );
+
+    let schema = schema();
+
+    assert!(query_arguments(&schema, r#"{ sites(where: { meta_json: { path: [-1] } }) { id } }"#).is_err());
+    assert!(query_arguments(
+        &schema,
+        r#"{ sites(where: { meta_json: { string_contains: 1 } }) { id } }"#
+    )
+    .is_err());
+    assert!(query_arguments(&schema, r#"{ sites(where: { meta_json: 1 }) { id } }"#).is_err());
+}
+
+#[test]
+fn relation_filters() {
+    assert_eq!(
+        r#"sites ToOneRelatedNode(name Equals(String("Blog")))"#,
+        filter(r#"{ users(where: { sites: { name: "Blog" } }) { id } }"#)
+    );
+
+    assert_eq!(
+        "sites ToOneRelatedNode(true)",
+        filter(r#"{ users(where: { sites: {} }) { id } }"#)
+    );
+
+    assert_eq!("user IS NULL", filter(r#"{ sites(where: { user: null }) { id } }"#));
+}
+
+#[test]
+fn invalid_filters() {
+    let schema = schema();
+
+    assert!(query_arguments(&schema, r#"{ users(where: { nickname: "Bob" }) { id } }"#).is_err());
+    assert!(query_arguments(&schema, r#"{ users(where: { name: 1 }) { id } }"#).is_err());
+    // Only list relations can be filtered with `_every`, `_some` and `_none`.
+    assert!(query_arguments(&schema, r#"{ users(where: { sites_some: { name: "Blog" } }) { id } }"#).is_err());
+    assert!(query_arguments(&schema, r#"{ users(where: { user: null }) { id } }"#).is_err());
+    // Only list fields can be filtered with `_contains_every` and `_contains_some`.
+    assert!(query_arguments(&schema, r#"{ users(where: { name_contains_every: ["a"] }) { id } }"#).is_err());
+}
@@ -79,7 +79,11 @@ impl IntoFilter for pb::ScalarFilter {
             NotIn(mc) => ScalarCondition::NotIn(mc.values.into_iter().map(|value| value.into()).collect()),
         };
 
-        Filter::Scalar(ScalarFilter { field, condition })
+        Filter::Scalar(ScalarFilter {
+            field,
+            condition,
+            mode: StringMode::Default,
+        })
     }
 }
 
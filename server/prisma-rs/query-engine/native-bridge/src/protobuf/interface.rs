@@ -4,6 +4,10 @@ use crate::{
     BridgeError, BridgeResult, ExternalInterface,
 };
 use connector::{error::ConnectorError, filter::NodeSelector, DataResolver, DatabaseMutactionExecutor};
+#[cfg(feature = "in-memory")]
+use in_memory_connector::InMemory;
+use mysql_connector::Mysql;
+use postgres_connector::PostgreSql;
 use prisma_common::config::*;
 use prisma_models::prelude::*;
 use prost::Message;
@@ -17,18 +21,38 @@ pub struct ProtoBufInterface {
 
 impl ProtoBufInterface {
     pub fn new(config: &PrismaConfig) -> ProtoBufInterface {
-        let connector = match config.databases.get("default") {
+        match config.databases.get("default") {
             Some(PrismaDatabase::Explicit(ref config))
                 if config.connector == "sqlite-native" || config.connector == "native-integration-tests" =>
             {
                 let test_mode = true;
                 let sqlite = Sqlite::new(config.limit(), test_mode).unwrap();
 
-                Arc::new(sqlite)
+                Self::with_connector(Arc::new(sqlite))
             }
-            _ => panic!("Database connector is not supported, use sqlite with a file for now!"),
-        };
+            Some(PrismaDatabase::Explicit(ref config)) if config.connector == "postgres-native" => {
+                let postgres = PostgreSql::new(config).unwrap();
 
+                Self::with_connector(Arc::new(postgres))
+            }
+            Some(PrismaDatabase::Explicit(ref config)) if config.connector == "mysql-native" => {
+                let mysql = Mysql::new(config).unwrap();
+
+                Self::with_connector(Arc::new(mysql))
+            }
+            // Keeps all data in the process, only for tests.
+            #[cfg(feature = "in-memory")]
+            Some(PrismaDatabase::Explicit(ref config)) if config.connector == "in-memory" => {
+                Self::with_connector(Arc::new(InMemory::new()))
+            }
+            _ => panic!("Database connector is not supported, use sqlite-native, postgres-native or mysql-native!"),
+        }
+    }
+
+    fn with_connector<C>(connector: Arc<C>) -> ProtoBufInterface
+    where
+        C: DataResolver + DatabaseMutactionExecutor + Send + Sync + 'static,
+    {
         ProtoBufInterface {
             data_resolver: connector.clone(),
             database_mutaction_executor: connector,
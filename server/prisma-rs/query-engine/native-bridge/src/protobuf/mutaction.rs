@@ -142,6 +142,7 @@ pub fn convert_update(m: crate::protobuf::prisma::UpdateNode, project: ProjectRe
         where_: convert_node_select(m.where_, Arc::clone(&project)),
         non_list_args: convert_prisma_args(m.non_list_args),
         list_args: convert_list_args(m.list_args),
+        json_updates: vec![],
         nested_mutactions: convert_nested_mutactions(m.nested, Arc::clone(&project)),
     }
 }
@@ -160,6 +161,7 @@ pub fn convert_nested_update(m: crate::protobuf::prisma::NestedUpdateNode, proje
         where_: m.where_.map(|w| convert_node_select(w, Arc::clone(&project))),
         non_list_args: convert_prisma_args(m.non_list_args),
         list_args: convert_list_args(m.list_args),
+        json_updates: vec![],
         nested_mutactions: convert_nested_mutactions(m.nested, Arc::clone(&project)),
     }
 }
@@ -416,8 +418,6 @@ pub fn convert_mutaction_result(result: DatabaseMutactionResult) -> crate::proto
             let result = crate::protobuf::prisma::Unit {};
             let typ = database_mutaction_result::Type::Unit(result);
             crate::protobuf::prisma::DatabaseMutactionResult { type_: Some(typ) }
-        }
-
-        // x => panic!("can't handle result type {:?}", x),
+        } // x => panic!("can't handle result type {:?}", x),
     }
 }
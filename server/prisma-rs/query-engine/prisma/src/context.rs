@@ -1,29 +1,28 @@
-use crate::{schema, PrismaResult};
-use core::QueryExecutor;
+use crate::{schema, PrismaError, PrismaResult};
+use connector::{DataResolver, DatabaseMutactionExecutor};
+use core::{GraphQlSchema, QueryExecutor};
+#[cfg(feature = "in-memory")]
+use in_memory_connector::InMemory;
+#[cfg(feature = "sql")]
+use mysql_connector::Mysql;
+#[cfg(feature = "sql")]
+use postgres_connector::PostgreSql;
 use prisma_common::config::{self, ConnectionLimit, PrismaConfig, PrismaDatabase};
 use prisma_models::SchemaRef;
+#[cfg(feature = "sql")]
 use sqlite_connector::Sqlite;
 use std::sync::Arc;
 
 pub struct PrismaContext {
     pub config: PrismaConfig,
     pub schema: SchemaRef,
+    pub graphql_schema: GraphQlSchema,
     pub query_executor: QueryExecutor,
 }
 
 impl PrismaContext {
     pub fn new() -> PrismaResult<Self> {
         let config = config::load().unwrap();
-        let data_resolver = match config.databases.get("default") {
-            Some(PrismaDatabase::Explicit(ref config)) if config.connector == "sqlite-native" => {
-                let test_mode = false;
-                let sqlite = Sqlite::new(config.limit(), test_mode).unwrap();
-                Arc::new(sqlite)
-            }
-            _ => panic!("Database connector is not supported, use sqlite with a file for now!"),
-        };
-
-        let query_executor: QueryExecutor = QueryExecutor { data_resolver };
 
         let db_name = config
             .databases
@@ -33,10 +32,75 @@ impl PrismaContext {
             .expect("database was not set");
 
         let schema = schema::load_schema(db_name)?;
+
+        let (data_resolver, write_executor) = match config.databases.get("default") {
+            #[cfg(feature = "sql")]
+            Some(PrismaDatabase::Explicit(ref config)) if config.connector == "sqlite-native" => {
+                let test_mode = false;
+                let sqlite = Sqlite::new(config.limit(), test_mode).unwrap();
+
+                sqlite.create_full_text_search_indexes(&schema).map_err(|e| {
+                    PrismaError::ConfigurationError(format!("Unable to create the full-text search indexes: {}", e))
+                })?;
+
+                Self::executors(Arc::new(sqlite))
+            }
+            #[cfg(feature = "sql")]
+            Some(PrismaDatabase::Explicit(ref config)) if config.connector == "postgres-native" => {
+                let postgres = PostgreSql::new(config)
+                    .map_err(|e| PrismaError::ConfigurationError(format!("Unable to connect to Postgres: {}", e)))?;
+
+                Self::executors(Arc::new(postgres))
+            }
+            #[cfg(feature = "sql")]
+            Some(PrismaDatabase::Explicit(ref config)) if config.connector == "mysql-native" => {
+                let mysql = Mysql::new(config)
+                    .map_err(|e| PrismaError::ConfigurationError(format!("Unable to connect to MySQL: {}", e)))?;
+
+                Self::executors(Arc::new(mysql))
+            }
+            // Keeps all data in the process, only for tests.
+            #[cfg(feature = "in-memory")]
+            Some(PrismaDatabase::Explicit(ref config)) if config.connector == "in-memory" => {
+                Self::executors(Arc::new(InMemory::new()))
+            }
+            _ => {
+                return Err(PrismaError::ConfigurationError(
+                    "Database connector is not supported, use sqlite-native, postgres-native or mysql-native".into(),
+                ));
+            }
+        };
+
+        let query_executor: QueryExecutor = QueryExecutor {
+            data_resolver,
+            write_executor,
+        };
+
+        let graphql_schema = GraphQlSchema::build(&schema);
+
         Ok(Self {
             config: config,
             schema: schema,
+            graphql_schema: graphql_schema,
             query_executor: query_executor,
         })
     }
+
+    /// The number of connections of the default database, and so the number of requests that
+    /// can be executed at the same time.
+    pub fn connection_limit(&self) -> u32 {
+        self.config.databases.get("default").map(|db| db.limit()).unwrap_or(1)
+    }
+
+    fn executors<C>(
+        connector: Arc<C>,
+    ) -> (
+        Arc<DataResolver + Send + Sync + 'static>,
+        Arc<DatabaseMutactionExecutor + Send + Sync + 'static>,
+    )
+    where
+        C: DataResolver + DatabaseMutactionExecutor + Send + Sync + 'static,
+    {
+        (connector.clone(), connector)
+    }
 }
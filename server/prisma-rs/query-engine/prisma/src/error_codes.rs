@@ -0,0 +1,143 @@
+//! Error code catalogue
+//!
+//! Every error returned to clients carries a stable code in `extensions.code`,
+//! and structured details in `extensions.meta` where available.
+//!
+//! | Code  | Error                                                       |
+//! |-------|-------------------------------------------------------------|
+//! | P1000 | Error creating a database connection                        |
+//! | P1001 | Unsupported connection arguments                            |
+//! | P2001 | No record found for the `where` of a query or mutation      |
+//! | P2002 | Unique constraint violated                                  |
+//! | P2008 | The query could not be parsed                               |
+//! | P2009 | The query is invalid                                        |
+//! | P2010 | The database failed to execute the query                    |
+//! | P2011 | Null constraint violated                                    |
+//! | P2014 | The change would violate a required relation                |
+//! | P2017 | The records of a nested mutation are not connected          |
+//! | P2023 | A column value doesn't match the model                      |
+//! | P2025 | A record required by the operation does not exist           |
+//! | P3000 | The data model is inconsistent                              |
+//! | P5000 | Internal error of the query engine                          |
+//! | P5001 | A result could not be serialized                            |
+//! | P5002 | The configuration is invalid                                |
+
+use crate::error::PrismaError;
+use connector::error::{ConnectorError, NodeSelectorInfo};
+use core::CoreError;
+use prisma_models::DomainError;
+use serde_json::{json, Value};
+
+pub trait ErrorCode {
+    /// The stable code of the error.
+    fn code(&self) -> &'static str;
+
+    /// Structured details of the error.
+    fn meta(&self) -> Value {
+        json!({})
+    }
+}
+
+impl ErrorCode for ConnectorError {
+    fn code(&self) -> &'static str {
+        match self {
+            ConnectorError::ConnectionError(_) => "P1000",
+            ConnectorError::InvalidConnectionArguments => "P1001",
+            ConnectorError::NodeNotFoundForWhere(_) => "P2001",
+            ConnectorError::UniqueConstraintViolation { .. } => "P2002",
+            ConnectorError::QueryError(_) => "P2010",
+            ConnectorError::FieldCannotBeNull { .. } => "P2011",
+            ConnectorError::RelationViolation { .. } => "P2014",
+            ConnectorError::NodesNotConnected { .. } => "P2017",
+            ConnectorError::ColumnReadFailure(_) => "P2023",
+            ConnectorError::NodeDoesNotExist => "P2025",
+            ConnectorError::MissingParentId => "P5000",
+            ConnectorError::DomainError(e) => e.code(),
+        }
+    }
+
+    fn meta(&self) -> Value {
+        match self {
+            ConnectorError::NodeNotFoundForWhere(info) => node_selector_meta(info),
+            ConnectorError::UniqueConstraintViolation { field_name } => json!({ "field_name": field_name }),
+            ConnectorError::FieldCannotBeNull { field } => json!({ "field_name": field }),
+            ConnectorError::RelationViolation {
+                relation_name,
+                model_a_name,
+                model_b_name,
+            } => json!({
+                "relation_name": relation_name,
+                "model_a_name": model_a_name,
+                "model_b_name": model_b_name,
+            }),
+            ConnectorError::NodesNotConnected {
+                relation_name,
+                parent_name,
+                parent_where,
+                child_name,
+                child_where,
+            } => json!({
+                "relation_name": relation_name,
+                "parent_name": parent_name,
+                "parent_where": parent_where.as_ref().map(node_selector_meta),
+                "child_name": child_name,
+                "child_where": child_where.as_ref().map(node_selector_meta),
+            }),
+            ConnectorError::DomainError(e) => e.meta(),
+            _ => json!({}),
+        }
+    }
+}
+
+impl ErrorCode for DomainError {
+    fn code(&self) -> &'static str {
+        "P3000"
+    }
+}
+
+impl ErrorCode for CoreError {
+    fn code(&self) -> &'static str {
+        match self {
+            CoreError::ConnectorError(e) => e.code(),
+            CoreError::DomainError(e) => e.code(),
+            CoreError::QueryValidationError(_) => "P2009",
+            CoreError::MutactionResultError(_) => "P5000",
+        }
+    }
+
+    fn meta(&self) -> Value {
+        match self {
+            CoreError::ConnectorError(e) => e.meta(),
+            CoreError::DomainError(e) => e.meta(),
+            CoreError::QueryValidationError(_) | CoreError::MutactionResultError(_) => json!({}),
+        }
+    }
+}
+
+impl ErrorCode for PrismaError {
+    fn code(&self) -> &'static str {
+        match self {
+            PrismaError::QueryParsingError(_) => "P2008",
+            PrismaError::QueryValidationError(_) => "P2009",
+            PrismaError::CoreError(e) => e.code(),
+            PrismaError::SerializationError(_) => "P5001",
+            PrismaError::ConfigurationError(_) => "P5002",
+            PrismaError::JsonDecodeError(_) | PrismaError::IOError(_) => "P5000",
+        }
+    }
+
+    fn meta(&self) -> Value {
+        match self {
+            PrismaError::CoreError(e) => e.meta(),
+            _ => json!({}),
+        }
+    }
+}
+
+fn node_selector_meta(info: &NodeSelectorInfo) -> Value {
+    json!({
+        "model": info.model,
+        "field": info.field,
+        "value": info.value,
+    })
+}
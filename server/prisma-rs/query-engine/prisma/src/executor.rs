@@ -0,0 +1,47 @@
+use crate::{
+    context::PrismaContext,
+    req_handlers::{GraphQlBody, GraphQlRequestHandler, PrismaRequest, RequestHandler},
+};
+use actix::prelude::*;
+use std::sync::Arc;
+
+/// A GraphQL request to execute, answered with the JSON response.
+pub struct GraphQlRequest(pub PrismaRequest<GraphQlBody>);
+
+impl Message for GraphQlRequest {
+    type Result = serde_json::Value;
+}
+
+/// Executes requests on threads of its own, away from the event loop of the HTTP server.
+///
+/// The connectors block until the database answers, so every executor handles one request at a
+/// time. Running as many executors as the connector has connections lets the requests use the
+/// whole pool, while the others wait in the mailbox instead of waiting for a connection.
+pub struct RequestExecutor {
+    context: Arc<PrismaContext>,
+    graphql_request_handler: GraphQlRequestHandler,
+}
+
+impl RequestExecutor {
+    /// Starts one executor per connection of the default database.
+    pub fn start(context: Arc<PrismaContext>) -> Addr<RequestExecutor> {
+        let threads = context.connection_limit() as usize;
+
+        SyncArbiter::start(threads, move || RequestExecutor {
+            context: Arc::clone(&context),
+            graphql_request_handler: GraphQlRequestHandler,
+        })
+    }
+}
+
+impl Actor for RequestExecutor {
+    type Context = SyncContext<Self>;
+}
+
+impl Handler<GraphQlRequest> for RequestExecutor {
+    type Result = MessageResult<GraphQlRequest>;
+
+    fn handle(&mut self, req: GraphQlRequest, _: &mut Self::Context) -> Self::Result {
+        MessageResult(self.graphql_request_handler.handle(req.0, &self.context))
+    }
+}
@@ -3,48 +3,54 @@ extern crate log;
 
 mod context;
 mod error;
+mod error_codes;
+mod executor;
 mod req_handlers;
 mod schema;
 mod utilities;
 
 mod serializer;
 
-use actix_web::{fs, http::Method, server, App, HttpRequest, Json, Responder};
+use actix::Addr;
+use actix_web::{fs, http::Method, server, App, Error, HttpRequest, Json, Responder};
 use context::PrismaContext;
 use error::PrismaError;
-use req_handlers::{GraphQlBody, GraphQlRequestHandler, PrismaRequest, RequestHandler};
-use serde_json;
+use executor::{GraphQlRequest, RequestExecutor};
+use futures::Future;
+use req_handlers::{GraphQlBody, PrismaRequest};
 use std::sync::Arc;
 
 pub type PrismaResult<T> = Result<T, PrismaError>;
 
 struct HttpHandler {
-    context: PrismaContext,
-    graphql_request_handler: GraphQlRequestHandler,
+    context: Arc<PrismaContext>,
+    executor: Addr<RequestExecutor>,
 }
 
 #[allow(unused_variables)]
 fn main() {
     env_logger::init();
 
-    let context = PrismaContext::new().unwrap();
+    let context = Arc::new(PrismaContext::new().unwrap());
     let port = context.config.port;
+    let sys = actix::System::new("prisma");
+
     let http_handler = HttpHandler {
+        executor: RequestExecutor::start(Arc::clone(&context)),
         context: context,
-        graphql_request_handler: GraphQlRequestHandler,
     };
 
     let http_handler_arc = Arc::new(http_handler);
-    let sys = actix::System::new("prisma");
     let address = ("0.0.0.0", port);
 
     server::new(move || {
         App::with_state(Arc::clone(&http_handler_arc))
             .resource("/", |r| {
                 r.method(Method::GET).with(playground);
-                r.method(Method::POST).with(handler);
+                r.method(Method::POST).with_async(handler);
             })
             .resource("/datamodel", |r| r.method(Method::GET).with(data_model_handler))
+            .resource("/schema.graphql", |r| r.method(Method::GET).with(graphql_schema_handler))
     })
     .bind(address)
     .unwrap()
@@ -54,9 +60,10 @@ fn main() {
     let _ = sys.run();
 }
 
-fn handler((json, req): (Json<Option<GraphQlBody>>, HttpRequest<Arc<HttpHandler>>)) -> impl Responder {
-    let http_handler = req.state();
-    let req: PrismaRequest<GraphQlBody> = PrismaRequest {
+fn handler(
+    (json, req): (Json<Option<GraphQlBody>>, HttpRequest<Arc<HttpHandler>>),
+) -> impl Future<Item = String, Error = Error> {
+    let request: PrismaRequest<GraphQlBody> = PrismaRequest {
         body: json.clone().unwrap(),
         path: req.path().into(),
         headers: req
@@ -66,14 +73,22 @@ fn handler((json, req): (Json<Option<GraphQlBody>>, HttpRequest<Arc<HttpHandler>
             .collect(),
     };
 
-    let result = http_handler.graphql_request_handler.handle(req, &http_handler.context);
-    serde_json::to_string(&result)
+    // The request is executed on the executor threads, the event loop only waits for the response.
+    req.state()
+        .executor
+        .send(GraphQlRequest(request))
+        .from_err()
+        .map(|result| result.to_string())
 }
 
 fn data_model_handler<T>(_: HttpRequest<T>) -> impl Responder {
     schema::load_datamodel_file().unwrap()
 }
 
+fn graphql_schema_handler(req: HttpRequest<Arc<HttpHandler>>) -> impl Responder {
+    req.state().context.graphql_schema.to_sdl()
+}
+
 fn playground<T>(_: HttpRequest<T>) -> impl Responder {
     fs::NamedFile::open("prisma-rs/playground.html")
 }
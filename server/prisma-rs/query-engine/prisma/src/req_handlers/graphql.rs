@@ -1,11 +1,11 @@
 use super::{PrismaRequest, RequestHandler};
-use crate::{context::PrismaContext, error::PrismaError, schema::Validatable, PrismaResult};
-use core::{PrismaQuery, PrismaQueryResult, RootQueryBuilder};
+use crate::{context::PrismaContext, error::PrismaError, error_codes::ErrorCode, PrismaResult};
+use core::{Operation, PrismaQueryResult, RootQueryBuilder, ValidationError};
 use graphql_parser as gql;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
-use serde_json::{Map, Value};
+use serde_json::{json, Map, Value};
 
 use crate::serializer::{ir::IrBuilder, json};
 
@@ -16,7 +16,8 @@ type JsonMap = Map<String, Value>;
 pub struct GraphQlBody {
     query: String,
     operation_name: Option<String>,
-    variables: HashMap<String, String>,
+    #[serde(default)]
+    variables: Option<JsonMap>,
 }
 
 pub struct GraphQlRequestHandler;
@@ -26,14 +27,10 @@ impl RequestHandler for GraphQlRequestHandler {
     type Body = GraphQlBody;
 
     fn handle<S: Into<PrismaRequest<Self::Body>>>(&self, req: S, ctx: &PrismaContext) -> Value {
-        // Handle incoming request and deal with errors properly
+        // Errors raised before execution fail the whole request, without any data
         match handle_safely(req.into(), ctx) {
             Ok(val) => val,
-            Err(err) => {
-                let mut map = Map::new();
-                map.insert("reason".into(), format!("{}", err).into());
-                json_envelope("error", map)
-            }
+            Err(err) => json!({ "errors": [response_error(&err, vec![])] }),
         }
     }
 }
@@ -44,36 +41,131 @@ fn handle_safely(req: PrismaRequest<GraphQlBody>, ctx: &PrismaContext) -> Prisma
         Err(e) => return Err(PrismaError::QueryParsingError(format!("{:?}", e))),
     };
 
-    // Let's validate the schema!
-    if let Err(_) = ctx.schema.validate(&query_doc) {
-        return Err(PrismaError::QueryValidationError(
-            "Schema validation failed for unknown reasons".into(),
-        ));
-    }
-
     dbg!(&query_doc);
 
+    let variables: BTreeMap<String, gql::query::Value> = req
+        .body
+        .variables
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, value)| (name, json_to_gql_value(value)))
+        .collect();
+
     let qb = RootQueryBuilder {
         query: query_doc,
         schema: ctx.schema.clone(),
         operation_name: req.body.operation_name,
+        variables,
     };
 
-    let queries: Vec<PrismaQuery> = qb.build()?;
+    // The document is normalized once, then validated and built as normalized.
+    let operation = match qb.normalize() {
+        Ok(operation) => operation,
+        Err(e) => return Ok(json!({ "errors": [validation_error(e.into())] })),
+    };
 
-    let results: Vec<PrismaQueryResult> = dbg!(ctx.query_executor.execute(&queries))?
-        .into_iter()
-        .map(|r| r.filter())
-        .collect();
+    if let Err(errors) = ctx.graphql_schema.validate(&qb.query, &operation) {
+        let errors: Vec<Value> = errors.into_iter().map(validation_error).collect();
+        return Ok(json!({ "errors": errors }));
+    }
+
+    let operations: Vec<Operation> = qb.build_normalized(&operation)?;
+    let mut data = JsonMap::new();
+    let mut errors = vec![];
+
+    // Operations are executed in order, so that mutations can't interleave.
+    // A failing root field is `null` in the data, the others are still returned.
+    for op in operations.iter() {
+        match execute(op, ctx) {
+            Ok(result) => data.extend(result),
+            Err(err) => {
+                data.insert(op.name().to_owned(), Value::Null);
+                errors.push(response_error(&err, vec![op.name().to_owned()]));
+            }
+        }
+    }
+
+    let mut response = JsonMap::new();
+    response.insert("data".into(), Value::Object(data));
+
+    if !errors.is_empty() {
+        response.insert("errors".into(), Value::Array(errors));
+    }
+
+    Ok(Value::Object(response))
+}
+
+/// Executes a single root field, returning its data keyed by the response name.
+fn execute(op: &Operation, ctx: &PrismaContext) -> PrismaResult<JsonMap> {
+    let results: Vec<PrismaQueryResult> = match op {
+        Operation::Read(query) => ctx.query_executor.execute(std::slice::from_ref(query))?,
+        Operation::Write(query) => ctx.query_executor.execute_write(query)?,
+        Operation::Introspection(query) => {
+            let mut map = JsonMap::new();
+            map.insert(query.name().to_owned(), query.resolve(&ctx.graphql_schema)?);
+
+            return Ok(map);
+        }
+    };
+
+    let results: Vec<PrismaQueryResult> = results.into_iter().map(|r| r.filter()).collect();
 
     Ok(json::serialize(
         results.iter().fold(IrBuilder::new(), |b, res| b.add(res)).build(),
     ))
 }
 
-/// Create a json envelope
-fn json_envelope(id: &str, map: serde_json::Map<String, Value>) -> Value {
-    let mut envelope = JsonMap::new();
-    envelope.insert(id.to_owned(), Value::Object(map));
-    Value::Object(envelope)
+/// Renders an error as an entry of the `errors` of a GraphQL response.
+fn response_error(err: &PrismaError, path: Vec<String>) -> Value {
+    let mut map = JsonMap::new();
+    map.insert("message".into(), format!("{}", err).into());
+
+    if !path.is_empty() {
+        map.insert("path".into(), path.into());
+    }
+
+    map.insert("extensions".into(), json!({ "code": err.code(), "meta": err.meta() }));
+
+    Value::Object(map)
+}
+
+/// Renders a validation error, with the locations of the offending fields in the document.
+fn validation_error(error: ValidationError) -> Value {
+    let err = PrismaError::QueryValidationError(error.message);
+    let mut value = response_error(&err, error.path);
+
+    if !error.locations.is_empty() {
+        let locations: Vec<Value> = error
+            .locations
+            .iter()
+            .map(|pos| json!({ "line": pos.line, "column": pos.column }))
+            .collect();
+
+        value["locations"] = Value::Array(locations);
+    }
+
+    value
+}
+
+/// Converts a JSON variable value into a GraphQL input value
+fn json_to_gql_value(value: Value) -> gql::query::Value {
+    use gql::query::{Number, Value as GqlValue};
+
+    match value {
+        Value::Null => GqlValue::Null,
+        Value::Bool(b) => GqlValue::Boolean(b),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) if i >= i64::from(i32::min_value()) && i <= i64::from(i32::max_value()) => {
+                GqlValue::Int(Number::from(i as i32))
+            }
+            _ => GqlValue::Float(n.as_f64().unwrap_or_default()),
+        },
+        Value::String(s) => GqlValue::String(s),
+        Value::Array(values) => GqlValue::List(values.into_iter().map(json_to_gql_value).collect()),
+        Value::Object(obj) => GqlValue::Object(
+            obj.into_iter()
+                .map(|(k, v)| (k, json_to_gql_value(v)))
+                .collect::<BTreeMap<String, GqlValue>>(),
+        ),
+    }
 }
@@ -9,18 +9,21 @@
 //! This IR fixes that issue, allowing us to serialize to various
 //! flexible formats.
 
-use core::{MultiPrismaQueryResult, PrismaQueryResult, SinglePrismaQueryResult};
-use prisma_models::PrismaValue;
+use connector::AggregationRow;
+use core::{
+    cursor, AggregateField, AggregateQueryResult, AggregateValue, ConnectionField, ConnectionResult,
+    MultiPrismaQueryResult, PrismaQueryResult, SinglePrismaQueryResult,
+};
+use prisma_models::{GraphqlId, PrismaValue};
 use serde::Serialize;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// A set of responses to provided queries
 pub type Responses = Vec<IrResponse>;
 
-#[allow(dead_code)]
+/// The response to a single root field, keyed by its name
 pub enum IrResponse {
-    Data(Item),
-    Error(String), // TODO: Get a better error kind?
+    Data(String, Item),
 }
 
 /// A key -> value map to an IR item
@@ -29,8 +32,11 @@ pub type Map = BTreeMap<String, Item>;
 /// A list of IR items
 pub type List = Vec<Item>;
 
+/// Records of a multi result with their ids
+type Records = Vec<(GraphqlId, Item)>;
+
 /// An IR item that either expands to a subtype or leaf-node
-#[derive(Serialize)] // TODO: REMOVE AGAIN
+#[derive(Clone, Serialize)] // TODO: REMOVE AGAIN
 pub enum Item {
     Map(Map),
     List(List),
@@ -55,14 +61,25 @@ impl<'results> IrBuilder<'results> {
     pub fn build(self) -> Responses {
         self.0.into_iter().fold(vec![], |mut vec, res| {
             vec.push(match res {
-                PrismaQueryResult::Single(query) => IrResponse::Data(Item::Map(build_map(query))),
-                PrismaQueryResult::Multi(query) => IrResponse::Data(Item::List(build_list(query))),
+                PrismaQueryResult::Single(query) => IrResponse::Data(query.name.clone(), build_single(query)),
+                PrismaQueryResult::Multi(query) => {
+                    IrResponse::Data(query.name.clone(), multi_item(query, records(query), None))
+                }
+                PrismaQueryResult::Aggregate(query) => IrResponse::Data(query.name.clone(), build_aggregate(query)),
             });
             vec
         })
     }
 }
 
+/// A single result without a record is `null`
+fn build_single(result: &SinglePrismaQueryResult) -> Item {
+    match result.result {
+        Some(_) => Item::Map(build_map(result)),
+        None => Item::Value(PrismaValue::Null),
+    }
+}
+
 fn build_map(result: &SinglePrismaQueryResult) -> Map {
     // Build selected fields first
     let outer = match &result.result {
@@ -74,14 +91,18 @@ fn build_map(result: &SinglePrismaQueryResult) -> Map {
                 map.insert(name.clone(), Item::Value(val.clone()));
                 map
             }),
-        None => panic!("No result found"), // FIXME: Can this ever happen?
+        None => Map::new(),
     };
 
-    // Then add nested selected fields
+    // Then add nested selected fields, which all belong to this record
     result.nested.iter().fold(outer, |mut map, query| {
         match query {
-            PrismaQueryResult::Single(nested) => map.insert(nested.name.clone(), Item::Map(build_map(nested))),
-            PrismaQueryResult::Multi(nested) => map.insert(nested.name.clone(), Item::List(build_list(nested))),
+            PrismaQueryResult::Single(nested) => map.insert(nested.name.clone(), build_single(nested)),
+            PrismaQueryResult::Multi(nested) => map.insert(
+                nested.name.clone(),
+                multi_item(nested, records(nested), result.id.as_ref()),
+            ),
+            PrismaQueryResult::Aggregate(_) => unreachable!("Aggregations are only queried on the root"),
         };
 
         map
@@ -89,27 +110,163 @@ fn build_map(result: &SinglePrismaQueryResult) -> Map {
 }
 
 fn build_list(result: &MultiPrismaQueryResult) -> List {
-    let mut vec: Vec<Item> = result
+    let mut maps: Vec<Map> = result
         .result
         .as_pairs()
         .iter()
         .map(|vec| {
-            Item::Map(vec.iter().fold(Map::new(), |mut map, (name, value)| {
+            vec.iter().fold(Map::new(), |mut map, (name, value)| {
                 map.insert(name.clone(), Item::Value(value.clone()));
                 map
-            }))
+            })
         })
         .collect();
 
-    result.nested.iter().zip(&mut vec).for_each(|(nested, map)| {
-        match map {
-            Item::Map(ref mut map) => match nested {
-                PrismaQueryResult::Single(nested) => map.insert(nested.name.clone(), Item::Map(build_map(nested))),
-                PrismaQueryResult::Multi(nested) => map.insert(nested.name.clone(), Item::List(build_list(nested))),
-            },
-            _ => unreachable!(),
-        };
-    });
+    // Nested results hold the records of all parents, associated by parent id
+    for nested in result.nested.iter() {
+        match nested {
+            PrismaQueryResult::Multi(nested) => {
+                let grouped = group_by_parent(nested);
+
+                // Parents may appear more than once, like the author shared by several posts
+                for (id, map) in result.ids.iter().zip(maps.iter_mut()) {
+                    let records = grouped.get(id).cloned().unwrap_or_default();
+                    map.insert(nested.name.clone(), multi_item(nested, records, Some(id)));
+                }
+            }
+            PrismaQueryResult::Single(_) => unreachable!("Nested queries are loaded for all parents at once"),
+            PrismaQueryResult::Aggregate(_) => unreachable!("Aggregations are only queried on the root"),
+        }
+    }
+
+    maps.into_iter().map(Item::Map).collect()
+}
+
+fn records(result: &MultiPrismaQueryResult) -> Records {
+    result.ids.iter().cloned().zip(build_list(result)).collect()
+}
+
+/// Groups the records of a nested result by the id of their parent
+fn group_by_parent(result: &MultiPrismaQueryResult) -> HashMap<GraphqlId, Records> {
+    result
+        .result
+        .nodes
+        .iter()
+        .zip(records(result))
+        .fold(HashMap::new(), |mut map, (node, record)| {
+            if let Some(ref parent_id) = node.parent_id {
+                map.entry(parent_id.clone()).or_insert_with(Vec::new).push(record);
+            }
+
+            map
+        })
+}
+
+/// The records of a multi result for a single parent (or the root): a list, a connection,
+/// or a record (or null) for to-one relations
+fn multi_item(result: &MultiPrismaQueryResult, records: Records, parent_id: Option<&GraphqlId>) -> Item {
+    match result.connection {
+        Some(ref connection) => build_connection(connection, records, parent_id),
+        None if result.is_to_one() => records
+            .into_iter()
+            .next()
+            .map(|(_, item)| item)
+            .unwrap_or(Item::Value(PrismaValue::Null)),
+        None => Item::List(records.into_iter().map(|(_, item)| item).collect()),
+    }
+}
+
+/// Wraps the records of a connection in edges, next to the page info and the aggregate
+fn build_connection(connection: &ConnectionResult, records: Records, parent_id: Option<&GraphqlId>) -> Item {
+    let page = connection.page(parent_id);
+    let model = &connection.model_name;
+    let start_cursor = records.first().map(|(id, _)| cursor(id));
+    let end_cursor = records.last().map(|(id, _)| cursor(id));
+
+    object(
+        &format!("{}Connection", model),
+        &connection.selection.fields,
+        |field| match field.name.as_str() {
+            "edges" => Item::List(
+                records
+                    .iter()
+                    .map(|(id, node)| {
+                        object(
+                            &format!("{}Edge", model),
+                            &field.fields,
+                            |edge_field| match edge_field.name.as_str() {
+                                "cursor" => string(cursor(id)),
+                                _ => node.clone(),
+                            },
+                        )
+                    })
+                    .collect(),
+            ),
+            "pageInfo" => object("PageInfo", &field.fields, |info_field| match info_field.name.as_str() {
+                "hasNextPage" => Item::Value(PrismaValue::Boolean(page.has_next_page)),
+                "hasPreviousPage" => Item::Value(PrismaValue::Boolean(page.has_previous_page)),
+                "startCursor" => start_cursor
+                    .clone()
+                    .map(string)
+                    .unwrap_or(Item::Value(PrismaValue::Null)),
+                _ => end_cursor.clone().map(string).unwrap_or(Item::Value(PrismaValue::Null)),
+            }),
+            _ => object(&format!("Aggregate{}", model), &field.fields, |_| {
+                Item::Value(PrismaValue::Int(page.count.unwrap_or(0) as i32))
+            }),
+        },
+    )
+}
+
+/// Grouped aggregations are a list of groups, others a single object
+fn build_aggregate(result: &AggregateQueryResult) -> Item {
+    let mut objects = result.rows.iter().map(|row| aggregate_object(&result.fields, row));
+
+    if result.grouped {
+        Item::List(objects.collect())
+    } else {
+        objects.next().unwrap_or(Item::Value(PrismaValue::Null))
+    }
+}
+
+fn aggregate_object(fields: &[AggregateField], row: &AggregationRow) -> Item {
+    Item::Map(
+        fields
+            .iter()
+            .map(|field| {
+                let item = match field.value {
+                    AggregateValue::Group(position) => Item::Value(row.group[position].clone()),
+                    AggregateValue::Aggregation(position) => Item::Value(row.values[position].clone()),
+                    AggregateValue::Object(ref fields) => aggregate_object(fields, row),
+                    AggregateValue::TypeName(ref name) => string(name.clone()),
+                };
+
+                (field.key.clone(), item)
+            })
+            .collect(),
+    )
+}
+
+/// Resolves the selected fields of a connection type, keyed by response key
+fn object<F>(type_name: &str, fields: &[ConnectionField], resolve: F) -> Item
+where
+    F: Fn(&ConnectionField) -> Item,
+{
+    Item::Map(
+        fields
+            .iter()
+            .map(|field| {
+                let item = match field.name.as_str() {
+                    "__typename" => string(type_name.to_string()),
+                    _ => resolve(field),
+                };
+
+                (field.key.clone(), item)
+            })
+            .collect(),
+    )
+}
 
-    vec
+fn string(s: String) -> Item {
+    Item::Value(PrismaValue::String(s))
 }
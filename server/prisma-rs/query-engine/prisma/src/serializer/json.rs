@@ -10,24 +10,6 @@ use super::ir::{Responses, IrResponse, Item};
 type JsonMap = Map<String, Value>;
 type JsonVec = Vec<Value>;
 
-pub fn serialize(resp: Responses) -> Value {
-    let mut map = Map::new();
-
-    let vals: Vec<Value> = resp.into_iter().map(|res| match res {
-        IrResponse::Data(Item::List(list)) => Value::Array(serialize_list(list)),
-        IrResponse::Data(Item::Map(map)) => Value::Object(serialize_map(map)),
-        _ => unreachable!()
-    }).collect();
-
-    map.insert("data".into(), if vals.len() == 1 {
-         vals.first().unwrap().clone()
-    } else {
-        Value::Array(vals)
-    });
-
-    Value::Object(map)
-}
-
 macro_rules! match_serialize {
     ($val:ident) => {
         match $val {
@@ -38,6 +20,17 @@ macro_rules! match_serialize {
     };
 }
 
+/// Serializes the responses into a map of root field names to their data
+pub fn serialize(resp: Responses) -> JsonMap {
+    resp.into_iter().fold(JsonMap::new(), |mut map, res| {
+        match res {
+            IrResponse::Data(name, item) => map.insert(name, match_serialize!(item)),
+        };
+
+        map
+    })
+}
+
 /// Recursively serialize query results
 fn serialize_map(map: BTreeMap<String, Item>) -> JsonMap {
     map.into_iter().fold(JsonMap::new(), |mut map, (k, v)| {
@@ -61,28 +54,26 @@ fn serialize_prisma_value(value: PrismaValue) -> PrismaResult<Value> {
             None => return Err(PrismaError::SerializationError("`f64` number was invalid".into())),
         }),
         PrismaValue::Boolean(x) => Value::Bool(x),
-        PrismaValue::DateTime(_) => unimplemented!(),
+        PrismaValue::DateTime(x) => Value::String(x.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()),
         PrismaValue::Enum(x) => Value::String(x.clone()),
         PrismaValue::Json(x) => serde_json::from_str(&x)?,
-        PrismaValue::Int(x) => Value::Number(match Number::from_f64(x as f64) {
-            Some(num) => num,
-            None => return Err(PrismaError::SerializationError("`f64` number was invalid".into())),
-        }),
-        PrismaValue::Relation(_) => unimplemented!(),
+        PrismaValue::Int(x) => Value::Number(Number::from(x)),
+        PrismaValue::Relation(x) => Value::Number(Number::from(x)),
         PrismaValue::Null => Value::Null,
         PrismaValue::Uuid(x) => Value::String(x.to_hyphenated().to_string()),
         PrismaValue::GraphqlId(x) => serialize_graphql_id(&x)?,
-        PrismaValue::List(_) => unimplemented!(),
+        PrismaValue::List(x) => Value::Array(
+            x.into_iter()
+                .map(serialize_prisma_value)
+                .collect::<PrismaResult<Vec<Value>>>()?,
+        ),
     })
 }
 
 fn serialize_graphql_id(id: &GraphqlId) -> PrismaResult<Value> {
     Ok(match id {
         GraphqlId::String(x) => Value::String(x.clone()),
-        GraphqlId::Int(x) => Value::Number(match serde_json::Number::from_f64(*x as f64) {
-            Some(num) => num,
-            None => return Err(PrismaError::SerializationError("`f64` number was invalid".into())),
-        }),
+        GraphqlId::Int(x) => Value::Number(Number::from(*x)),
         GraphqlId::UUID(x) => Value::String(x.to_hyphenated().to_string()),
     })
 }